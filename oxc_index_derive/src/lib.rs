@@ -0,0 +1,139 @@
+//! `#[derive(Idx)]`, an alternative to `oxc_index::define_index_type!` for
+//! when you'd rather write the struct out yourself.
+//!
+//! Use it through the `derive` feature of `oxc_index`, rather than depending on
+//! this crate directly.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Expr, Fields, LitStr, parse_macro_input};
+
+/// Implement `Idx` for a tuple struct wrapping an unsigned integer, along
+/// with everything `define_index_type!` generates for it (constructors,
+/// `Debug`, conversions from and to `usize`, arithmetic operators, ...).
+///
+/// The struct has to derive the traits `Idx` requires itself, i.e. `Copy`,
+/// `Clone`, `PartialEq`, `Eq`, `Hash`, `PartialOrd` and `Ord`.
+///
+/// ```rust,ignore
+/// use oxc_index::Idx;
+///
+/// #[derive(Idx, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// #[idx(max = 0xffff_ff00, debug_format = "Node({})")]
+/// pub struct NodeId(u32);
+/// ```
+///
+/// The options of `#[idx(...)]` mirror those of `define_index_type!`:
+///
+/// - `max = <expr>`: like `MAX_INDEX`.
+/// - `disable_max_index_check = <expr>`: like `DISABLE_MAX_INDEX_CHECK`.
+/// - `debug_format = "..."`: like `DEBUG_FORMAT`.
+/// - `display_format = "..."`: like `DISPLAY_FORMAT`.
+/// - `default = <expr>`: like `DEFAULT`.
+/// - `no_arithmetic`: like `NO_ARITHMETIC = true`.
+#[proc_macro_derive(Idx, attributes(idx))]
+pub fn derive_idx(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+#[derive(Default)]
+struct Options {
+    max: Option<Expr>,
+    disable_max_index_check: Option<Expr>,
+    debug_format: Option<LitStr>,
+    display_format: Option<LitStr>,
+    default: Option<Expr>,
+    no_arithmetic: bool,
+}
+
+fn parse_options(input: &DeriveInput) -> syn::Result<Options> {
+    let mut options = Options::default();
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("idx")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("max") {
+                options.max = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("disable_max_index_check") {
+                options.disable_max_index_check = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("debug_format") {
+                options.debug_format = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("display_format") {
+                options.display_format = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("default") {
+                options.default = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("no_arithmetic") {
+                options.no_arithmetic = true;
+            } else {
+                return Err(meta.error("unknown `idx` option"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "index types can't be generic"));
+    }
+    let raw = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &data.fields,
+                    "`Idx` can only be derived for a tuple struct with a single field",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "`Idx` can only be derived for a tuple struct with a single field",
+            ));
+        }
+    };
+
+    let options = parse_options(input)?;
+    let vis = &input.vis;
+    let max = options.max.map_or_else(|| quote!((<#raw>::MAX as usize)), |max| quote!(#max));
+    let no_check_max =
+        options.disable_max_index_check.map_or_else(|| quote!(false), |e| quote!(#e));
+    let debug_format = options.debug_format.map_or_else(|| quote!("{}"), |f| quote!(#f));
+    let arith = if options.no_arithmetic { quote!(false) } else { quote!(true) };
+
+    let display = options.display_format.map(|format| {
+        quote! {
+            impl ::core::fmt::Display for #name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    write!(f, #format, self.index())
+                }
+            }
+        }
+    });
+    let default = options.default.map(|default| {
+        quote! {
+            impl ::core::default::Default for #name {
+                #[inline]
+                fn default() -> Self {
+                    #default
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        ::oxc_index::__define_index_type_body! {
+            @decl [#vis struct #name (#raw)]
+            @debug_fmt [#debug_format]
+            @max [#max]
+            @no_check_max [#no_check_max]
+            @arith [#arith]
+            @sentinel []
+            @ts_branded [false]
+        }
+        #display
+        #default
+    })
+}
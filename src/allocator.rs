@@ -0,0 +1,143 @@
+use core::{
+    fmt, iter,
+    marker::PhantomData,
+    ops::Range,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{Idx, IndexCounter, Len};
+
+type Block<I> = iter::Map<Range<usize>, fn(usize) -> I>;
+
+/// A thread-safe source of fresh indices, backed by an atomic counter.
+///
+/// Every call to [`IdxAllocator::alloc`] returns an index no other call (on
+/// any thread) has returned, so parallel front-ends can mint IDs before the
+/// table those IDs refer to is filled in. Threads which mint many IDs can
+/// [`reserve`](IdxAllocator::reserve) a block at a time to avoid contending
+/// on the counter.
+///
+/// Like [`IndexCounter`], this stores no data. Once the parallel section is
+/// over, size the tables with [`IdxAllocator::len_token`].
+///
+/// ```rust
+/// use oxc_index::IdxAllocator;
+///
+/// oxc_index::define_index_type! {
+///     pub struct SymbolId = u32;
+/// }
+///
+/// let ids = IdxAllocator::<SymbolId>::new();
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| {
+///             ids.alloc();
+///             for _id in ids.reserve(10) {}
+///         });
+///     }
+/// });
+/// assert_eq!(ids.len(), 44);
+/// ```
+pub struct IdxAllocator<I: Idx> {
+    next: AtomicUsize,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx> IdxAllocator<I> {
+    /// Construct an allocator whose first index is 0.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::starting_at(0)
+    }
+
+    /// Construct an allocator as if `len` indices were already handed out.
+    #[inline]
+    pub const fn starting_at(len: usize) -> Self {
+        Self { next: AtomicUsize::new(len), _marker: PhantomData }
+    }
+
+    /// Returns the number of indices handed out so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.next.load(Ordering::Acquire)
+    }
+
+    /// Returns true if no indices were handed out yet.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hand out a fresh index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index would exceed `I::MAX`.
+    #[inline]
+    pub fn alloc(&self) -> I {
+        match self.try_alloc() {
+            Some(idx) => idx,
+            None => panic!("index allocator exhausted: no index left below {}", I::MAX),
+        }
+    }
+
+    /// Hand out a fresh index, or `None` if it would exceed `I::MAX`.
+    #[inline]
+    pub fn try_alloc(&self) -> Option<I> {
+        self.try_reserve(1).map(|mut block| block.next().unwrap())
+    }
+
+    /// Hand out `n` fresh, consecutive indices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the indices would exceed `I::MAX`.
+    #[inline]
+    pub fn reserve(&self, n: usize) -> Block<I> {
+        match self.try_reserve(n) {
+            Some(block) => block,
+            None => {
+                panic!("index allocator exhausted: can't reserve {} indices below {}", n, I::MAX)
+            }
+        }
+    }
+
+    /// Hand out `n` fresh, consecutive indices, or `None` if they would exceed
+    /// `I::MAX`. Nothing is handed out on failure.
+    pub fn try_reserve(&self, n: usize) -> Option<Block<I>> {
+        let start = self
+            .next
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |next| {
+                // The last index handed out is `next + n - 1`.
+                next.checked_add(n).filter(|&end| n == 0 || end - 1 <= I::MAX)
+            })
+            .ok()?;
+        Some((start..start + n).map(I::from_usize as fn(usize) -> I))
+    }
+
+    /// Mint a [`Len`] token covering every index handed out so far.
+    #[inline]
+    pub fn len_token(&self) -> Len<I> {
+        Len::new(self.len())
+    }
+
+    /// Convert into a single-threaded [`IndexCounter`] continuing from where
+    /// we left off.
+    #[inline]
+    pub fn into_counter(self) -> IndexCounter<I> {
+        IndexCounter::with_len(self.next.into_inner())
+    }
+}
+
+impl<I: Idx> Default for IdxAllocator<I> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx> fmt::Debug for IdxAllocator<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "IdxAllocator({})", self.len())
+    }
+}
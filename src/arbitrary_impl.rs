@@ -0,0 +1,22 @@
+use alloc::vec::Vec;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{Idx, IndexVec};
+
+/// Generates at most `I::MAX` elements, so the length (the next index to be
+/// pushed) is a valid index, as `IndexVec::from_vec` requires.
+impl<'a, I: Idx, T: Arbitrary<'a>> Arbitrary<'a> for IndexVec<I, T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.arbitrary_iter()?.take(I::MAX).collect::<Result<Vec<T>>>().map(Self::from_vec)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        u.arbitrary_take_rest_iter()?.take(I::MAX).collect::<Result<Vec<T>>>().map(Self::from_vec)
+    }
+
+    #[inline]
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
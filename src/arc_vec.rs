@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use arc_swap::{ArcSwap, Guard};
+
+use crate::{Idx, IndexVec};
+
+/// An [`IndexVec`] snapshot which many threads can read without locking,
+/// while a writer occasionally replaces the whole table.
+///
+/// Readers [`load`](ArcIndexVec::load) the current snapshot and keep using it
+/// for as long as they like; a concurrent [`store`](ArcIndexVec::store) only
+/// affects later loads. This fits language-server style state, where query
+/// threads read constantly and a single writer rebuilds the table after each
+/// edit. Requires the `arc-swap` feature.
+///
+/// ```rust
+/// use oxc_index::{ArcIndexVec, index_vec};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let table = ArcIndexVec::<NodeIdx, u32>::new(index_vec![1, 2]);
+/// let before = table.load_full();
+/// table.update(|v| {
+///     let mut v = v.clone();
+///     v.push(3);
+///     v
+/// });
+/// assert_eq!(before.len(), 2);
+/// assert_eq!(table.load().len(), 3);
+/// ```
+pub struct ArcIndexVec<I: Idx, T> {
+    current: ArcSwap<IndexVec<I, T>>,
+}
+
+impl<I: Idx, T> ArcIndexVec<I, T> {
+    /// Construct a table whose first snapshot is `vec`.
+    #[inline]
+    pub fn new(vec: IndexVec<I, T>) -> Self {
+        Self { current: ArcSwap::from_pointee(vec) }
+    }
+
+    /// Get a cheap, short-lived handle to the current snapshot.
+    ///
+    /// Prefer [`ArcIndexVec::load_full`] if the snapshot will be held for a
+    /// long time.
+    #[inline]
+    pub fn load(&self) -> Guard<Arc<IndexVec<I, T>>> {
+        self.current.load()
+    }
+
+    /// Get an owning handle to the current snapshot.
+    #[inline]
+    pub fn load_full(&self) -> Arc<IndexVec<I, T>> {
+        self.current.load_full()
+    }
+
+    /// Replace the whole table. Readers holding the previous snapshot keep it.
+    #[inline]
+    pub fn store(&self, vec: IndexVec<I, T>) {
+        self.current.store(Arc::new(vec));
+    }
+
+    /// Replace the whole table, returning the previous snapshot.
+    #[inline]
+    pub fn replace(&self, vec: IndexVec<I, T>) -> Arc<IndexVec<I, T>> {
+        self.current.swap(Arc::new(vec))
+    }
+
+    /// Build a new table from the current snapshot with `f` and store it,
+    /// returning the snapshot it was built from.
+    ///
+    /// If another writer stores in the meantime, `f` is called again on the
+    /// newer snapshot, so it shouldn't have side effects.
+    #[inline]
+    pub fn update<F: FnMut(&IndexVec<I, T>) -> IndexVec<I, T>>(
+        &self,
+        mut f: F,
+    ) -> Arc<IndexVec<I, T>> {
+        self.current.rcu(|v| f(v))
+    }
+
+    /// Unwrap the current snapshot.
+    #[inline]
+    pub fn into_inner(self) -> Arc<IndexVec<I, T>> {
+        self.current.into_inner()
+    }
+}
+
+impl<I: Idx, T> Default for ArcIndexVec<I, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(IndexVec::new())
+    }
+}
+
+impl<I: Idx, T> From<IndexVec<I, T>> for ArcIndexVec<I, T> {
+    #[inline]
+    fn from(vec: IndexVec<I, T>) -> Self {
+        Self::new(vec)
+    }
+}
+
+impl<I: Idx, T: core::fmt::Debug> core::fmt::Debug for ArcIndexVec<I, T> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self.current.load(), fmt)
+    }
+}
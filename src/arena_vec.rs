@@ -0,0 +1,256 @@
+use core::{fmt, marker::PhantomData};
+
+use bumpalo::{Bump, collections::Vec};
+
+use crate::{Idx, IdxSliceIndex, IndexSlice};
+
+/// An arena-allocated `Vec` that only accepts indices of a specific type.
+///
+/// This wraps `bumpalo::collections::Vec`, so a side table can live in the
+/// same [`Bump`] arena as the data it describes and be freed with it. Use it
+/// with `oxc_allocator::Allocator` by passing the allocator's underlying
+/// `Bump`. Like [`IndexVec`](crate::IndexVec), the backing vector is public
+/// (as `raw`), and it derefs to [`IndexSlice`], so all the methods on that
+/// are available too. Requires the `bumpalo` feature.
+///
+/// ```rust
+/// use oxc_index::{ArenaIndexVec, bumpalo::Bump};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let arena = Bump::new();
+/// let mut spans = ArenaIndexVec::<NodeIdx, (u32, u32)>::new_in(&arena);
+/// let root = spans.push((0, 10));
+/// let child = spans.push((2, 5));
+/// assert_eq!(spans[child], (2, 5));
+///
+/// let frozen = spans.into_bump_slice();
+/// assert_eq!(frozen[root], (0, 10));
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArenaIndexVec<'alloc, I: Idx, T> {
+    /// Our wrapped arena `Vec`.
+    pub raw: Vec<'alloc, T>,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<'alloc, I: Idx, T> ArenaIndexVec<'alloc, I, T> {
+    /// Construct an empty `ArenaIndexVec` in `arena`.
+    #[inline]
+    pub fn new_in(arena: &'alloc Bump) -> Self {
+        Self { raw: Vec::new_in(arena), _marker: PhantomData }
+    }
+
+    /// Construct an empty `ArenaIndexVec` in `arena`, with room for
+    /// `capacity` elements.
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, arena: &'alloc Bump) -> Self {
+        Self { raw: Vec::with_capacity_in(capacity, arena), _marker: PhantomData }
+    }
+
+    /// Construct an `ArenaIndexVec` from an arena `Vec`.
+    ///
+    /// Panics if its length is too large for our index type.
+    #[inline]
+    pub fn from_vec(raw: Vec<'alloc, T>) -> Self {
+        // See if `I::from_usize` might be upset by this length.
+        let _ = I::from_usize(raw.len());
+        Self { raw, _marker: PhantomData }
+    }
+
+    /// Construct an `ArenaIndexVec` in `arena` from the items of `iter`.
+    #[inline]
+    pub fn from_iter_in<J: IntoIterator<Item = T>>(iter: J, arena: &'alloc Bump) -> Self {
+        Self::from_vec(Vec::from_iter_in(iter, arena))
+    }
+
+    /// The arena this vector allocates in.
+    #[inline]
+    pub fn bump(&self) -> &'alloc Bump {
+        self.raw.bump()
+    }
+
+    /// Gives the next index that will be assigned when `push` is called.
+    #[inline]
+    pub fn next_idx(&self) -> I {
+        I::from_usize(self.raw.len())
+    }
+
+    /// Push a new item onto the vector, and return its index.
+    #[inline]
+    pub fn push(&mut self, value: T) -> I {
+        let idx = self.next_idx();
+        self.raw.push(value);
+        idx
+    }
+
+    /// Pops the last item off, returning it.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.raw.pop()
+    }
+
+    /// Shortens the vector to `len` elements, dropping the rest.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.raw.truncate(len);
+    }
+
+    /// Remove every element.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.raw.clear();
+    }
+
+    /// Reserve room for `additional` more elements.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.raw.reserve(additional);
+    }
+
+    /// Get a ref to the item at the provided index, or None for out of bounds.
+    #[inline]
+    pub fn get<J: IdxSliceIndex<I, T>>(&self, index: J) -> Option<&J::Output> {
+        index.get(self.as_slice())
+    }
+
+    /// Get a mut ref to the item at the provided index, or None for out of
+    /// bounds
+    #[inline]
+    pub fn get_mut<J: IdxSliceIndex<I, T>>(&mut self, index: J) -> Option<&mut J::Output> {
+        index.get_mut(self.as_mut_slice())
+    }
+
+    /// Resize ourselves in-place to `new_len`. See [`Vec::resize`].
+    #[inline]
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        self.raw.resize(new_len, value);
+    }
+
+    /// Remove the item at `index`. See [`Vec::remove`].
+    #[inline]
+    pub fn remove(&mut self, index: I) -> T {
+        self.raw.remove(index.index())
+    }
+
+    /// Remove the item at `index` without maintaining order. See
+    /// [`Vec::swap_remove`].
+    #[inline]
+    pub fn swap_remove(&mut self, index: I) -> T {
+        self.raw.swap_remove(index.index())
+    }
+
+    /// Insert an item at `index`. See [`Vec::insert`].
+    #[inline]
+    pub fn insert(&mut self, index: I, element: T) {
+        self.raw.insert(index.index(), element);
+    }
+
+    /// Append all items in the slice to the end of our vector.
+    ///
+    /// See [`Vec::extend_from_slice`].
+    #[inline]
+    pub fn extend_from_slice(&mut self, other: &IndexSlice<I, [T]>)
+    where
+        T: Clone,
+    {
+        self.raw.extend_from_slice(&other.raw);
+    }
+
+    /// Forwards to the `Vec::retain` implementation.
+    #[inline]
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.raw.retain(f);
+    }
+
+    /// Convert into a slice that lives as long as the arena. The elements
+    /// won't be dropped, as the arena never runs destructors.
+    #[inline]
+    pub fn into_bump_slice(self) -> &'alloc IndexSlice<I, [T]> {
+        IndexSlice::new(self.raw.into_bump_slice())
+    }
+
+    /// Convert into a mutable slice that lives as long as the arena. The
+    /// elements won't be dropped, as the arena never runs destructors.
+    #[inline]
+    pub fn into_bump_slice_mut(self) -> &'alloc mut IndexSlice<I, [T]> {
+        IndexSlice::new_mut(self.raw.into_bump_slice_mut())
+    }
+
+    /// Get the elements as an `IndexSlice`.
+    #[inline]
+    pub fn as_slice(&self) -> &IndexSlice<I, [T]> {
+        IndexSlice::new(&self.raw)
+    }
+
+    /// Get the elements as a mutable `IndexSlice`.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut IndexSlice<I, [T]> {
+        IndexSlice::new_mut(&mut self.raw)
+    }
+}
+
+impl<I: Idx, T> core::ops::Deref for ArenaIndexVec<'_, I, T> {
+    type Target = IndexSlice<I, [T]>;
+
+    #[inline]
+    fn deref(&self) -> &IndexSlice<I, [T]> {
+        self.as_slice()
+    }
+}
+
+impl<I: Idx, T> core::ops::DerefMut for ArenaIndexVec<'_, I, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut IndexSlice<I, [T]> {
+        self.as_mut_slice()
+    }
+}
+
+impl<I: Idx, T> Extend<T> for ArenaIndexVec<'_, I, T> {
+    #[inline]
+    fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
+        self.raw.extend(iter);
+        let _ = self.next_idx();
+    }
+}
+
+impl<'alloc, I: Idx, T> IntoIterator for ArenaIndexVec<'alloc, I, T> {
+    type Item = T;
+    type IntoIter = bumpalo::collections::vec::IntoIter<'alloc, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.into_iter()
+    }
+}
+
+impl<'a, I: Idx, T> IntoIterator for &'a ArenaIndexVec<'_, I, T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter()
+    }
+}
+
+impl<'a, I: Idx, T> IntoIterator for &'a mut ArenaIndexVec<'_, I, T> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter_mut()
+    }
+}
+
+impl<I: Idx, T: fmt::Debug> fmt::Debug for ArenaIndexVec<'_, I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.raw, fmt)
+    }
+}
@@ -0,0 +1,133 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::{Idx, IndexSlice};
+
+/// A fixed-size array that only accepts indices of a specific type.
+///
+/// This is a thin wrapper around `[T; N]`, with the array a public property
+/// (called `raw`), for small tables whose size is known up front and which
+/// shouldn't need a heap allocation. It derefs to [`IndexSlice`], so all the
+/// methods on that are available too, and it can be built in a `const`.
+///
+/// ```rust
+/// use oxc_index::IndexArray;
+///
+/// oxc_index::define_index_type! {
+///     pub struct OpIdx = u8;
+/// }
+///
+/// const PRECEDENCE: IndexArray<OpIdx, u8, 3> = IndexArray::new([1, 2, 2]);
+/// assert_eq!(PRECEDENCE[OpIdx::new(1)], 2);
+///
+/// let squares = IndexArray::<OpIdx, usize, 4>::from_fn(|i| i.index() * i.index());
+/// assert_eq!(squares.raw, [0, 1, 4, 9]);
+/// assert_eq!(squares.iter_enumerated().last(), Some((OpIdx::new(3), &9)));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IndexArray<I: Idx, T, const N: usize> {
+    /// Our wrapped array.
+    pub raw: [T; N],
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx, T, const N: usize> IndexArray<I, T, N> {
+    /// Wrap `raw`.
+    #[inline]
+    pub const fn new(raw: [T; N]) -> Self {
+        Self { raw, _marker: PhantomData }
+    }
+
+    /// Construct an `IndexArray` by calling `f` with each index in turn.
+    ///
+    /// Panics if `N` is too large for our index type.
+    #[inline]
+    pub fn from_fn<F: FnMut(I) -> T>(mut f: F) -> Self {
+        // See if `I::from_usize` might be upset by this length.
+        let _ = I::from_usize(N);
+        Self::new(core::array::from_fn(|i| f(I::from_usize(i))))
+    }
+
+    /// Unwrap the array.
+    #[inline]
+    pub fn into_inner(self) -> [T; N] {
+        self.raw
+    }
+
+    /// Get the elements as an `IndexSlice`.
+    #[inline]
+    pub fn as_slice(&self) -> &IndexSlice<I, [T]> {
+        IndexSlice::new(&self.raw)
+    }
+
+    /// Get the elements as a mutable `IndexSlice`.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut IndexSlice<I, [T]> {
+        IndexSlice::new_mut(&mut self.raw)
+    }
+}
+
+impl<I: Idx, T, const N: usize> core::ops::Deref for IndexArray<I, T, N> {
+    type Target = IndexSlice<I, [T]>;
+
+    #[inline]
+    fn deref(&self) -> &IndexSlice<I, [T]> {
+        self.as_slice()
+    }
+}
+
+impl<I: Idx, T, const N: usize> core::ops::DerefMut for IndexArray<I, T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut IndexSlice<I, [T]> {
+        self.as_mut_slice()
+    }
+}
+
+impl<I: Idx, T: Default, const N: usize> Default for IndexArray<I, T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::from_fn(|_| T::default())
+    }
+}
+
+impl<I: Idx, T, const N: usize> From<[T; N]> for IndexArray<I, T, N> {
+    #[inline]
+    fn from(raw: [T; N]) -> Self {
+        Self::new(raw)
+    }
+}
+
+impl<I: Idx, T, const N: usize> IntoIterator for IndexArray<I, T, N> {
+    type Item = T;
+    type IntoIter = core::array::IntoIter<T, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.into_iter()
+    }
+}
+
+impl<'a, I: Idx, T, const N: usize> IntoIterator for &'a IndexArray<I, T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter()
+    }
+}
+
+impl<'a, I: Idx, T, const N: usize> IntoIterator for &'a mut IndexArray<I, T, N> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter_mut()
+    }
+}
+
+impl<I: Idx, T: fmt::Debug, const N: usize> fmt::Debug for IndexArray<I, T, N> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.raw, fmt)
+    }
+}
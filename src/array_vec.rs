@@ -0,0 +1,242 @@
+use core::{fmt, hash::Hash, marker::PhantomData, mem::MaybeUninit};
+
+use crate::{Idx, IndexSlice};
+
+/// A `Vec`-like container with a fixed capacity `N` and inline storage, which
+/// only accepts indices of a specific type.
+///
+/// Nothing is ever allocated, so this works without `alloc` and can be
+/// built in a `const`. Pushing past the capacity fails rather than
+/// reallocating. It derefs to [`IndexSlice`], so all the methods on that are
+/// available too.
+///
+/// ```rust
+/// use oxc_index::IndexArrayVec;
+///
+/// oxc_index::define_index_type! {
+///     pub struct PinIdx = u8;
+/// }
+///
+/// let mut pins = IndexArrayVec::<PinIdx, &str, 2>::new();
+/// assert_eq!(pins.try_push("led"), Ok(PinIdx::new(0)));
+/// assert_eq!(pins.try_push("button"), Ok(PinIdx::new(1)));
+/// assert_eq!(pins.try_push("buzzer"), Err("buzzer"));
+/// assert_eq!(pins[PinIdx::new(1)], "button");
+/// ```
+pub struct IndexArrayVec<I: Idx, T, const N: usize> {
+    raw: [MaybeUninit<T>; N],
+    len: usize,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx, T, const N: usize> IndexArrayVec<I, T, N> {
+    /// Construct an empty `IndexArrayVec`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { raw: [const { MaybeUninit::uninit() }; N], len: 0, _marker: PhantomData }
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no elements.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the capacity, `N`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns true if no more elements fit.
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Gives the next index that will be assigned when `push` is called.
+    #[inline]
+    pub fn next_idx(&self) -> I {
+        I::from_usize(self.len)
+    }
+
+    /// Push an element, and return its index. Returns `Err(value)` if the
+    /// vector is full.
+    #[inline]
+    pub fn try_push(&mut self, value: T) -> Result<I, T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        let idx = self.next_idx();
+        self.raw[self.len].write(value);
+        self.len += 1;
+        Ok(idx)
+    }
+
+    /// Push an element, and return its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is full.
+    #[inline]
+    #[track_caller]
+    pub fn push(&mut self, value: T) -> I {
+        assert!(!self.is_full(), "`IndexArrayVec` is full (capacity {N})");
+        let Ok(idx) = self.try_push(value) else { unreachable!() };
+        idx
+    }
+
+    /// Remove and return the last element, if any.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: the element was initialized, and is now past `len` so it
+        // won't be read again.
+        Some(unsafe { self.raw[self.len].assume_init_read() })
+    }
+
+    /// Shorten the vector to `len` elements, dropping the rest. Does nothing
+    /// if it's already shorter.
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.pop();
+        }
+    }
+
+    /// Remove every element.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Get the elements as an `IndexSlice`.
+    #[inline]
+    pub fn as_slice(&self) -> &IndexSlice<I, [T]> {
+        // SAFETY: the first `len` elements are initialized.
+        let raw = unsafe { core::slice::from_raw_parts(self.raw.as_ptr().cast::<T>(), self.len) };
+        IndexSlice::new(raw)
+    }
+
+    /// Get the elements as a mutable `IndexSlice`.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut IndexSlice<I, [T]> {
+        // SAFETY: the first `len` elements are initialized.
+        let raw =
+            unsafe { core::slice::from_raw_parts_mut(self.raw.as_mut_ptr().cast::<T>(), self.len) };
+        IndexSlice::new_mut(raw)
+    }
+}
+
+impl<I: Idx, T, const N: usize> Drop for IndexArrayVec<I, T, N> {
+    fn drop(&mut self) {
+        if core::mem::needs_drop::<T>() {
+            self.clear();
+        }
+    }
+}
+
+impl<I: Idx, T, const N: usize> core::ops::Deref for IndexArrayVec<I, T, N> {
+    type Target = IndexSlice<I, [T]>;
+
+    #[inline]
+    fn deref(&self) -> &IndexSlice<I, [T]> {
+        self.as_slice()
+    }
+}
+
+impl<I: Idx, T, const N: usize> core::ops::DerefMut for IndexArrayVec<I, T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut IndexSlice<I, [T]> {
+        self.as_mut_slice()
+    }
+}
+
+impl<I: Idx, T: Clone, const N: usize> Clone for IndexArrayVec<I, T, N> {
+    fn clone(&self) -> Self {
+        self.as_slice().raw.iter().cloned().collect()
+    }
+}
+
+impl<I: Idx, T, const N: usize> Default for IndexArrayVec<I, T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, T: PartialEq, const N: usize> PartialEq for IndexArrayVec<I, T, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice().raw == other.as_slice().raw
+    }
+}
+
+impl<I: Idx, T: Eq, const N: usize> Eq for IndexArrayVec<I, T, N> {}
+
+impl<I: Idx, T: Hash, const N: usize> Hash for IndexArrayVec<I, T, N> {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().raw.hash(state);
+    }
+}
+
+impl<I: Idx, T, const N: usize> Extend<T> for IndexArrayVec<I, T, N> {
+    /// Push every element yielded by `iter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if they don't all fit.
+    #[inline]
+    fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<I: Idx, T, const N: usize> FromIterator<T> for IndexArrayVec<I, T, N> {
+    /// # Panics
+    ///
+    /// Panics if the elements don't all fit.
+    #[inline]
+    fn from_iter<J: IntoIterator<Item = T>>(iter: J) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<'a, I: Idx, T, const N: usize> IntoIterator for &'a IndexArrayVec<I, T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().raw.iter()
+    }
+}
+
+impl<'a, I: Idx, T, const N: usize> IntoIterator for &'a mut IndexArrayVec<I, T, N> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_mut_slice().raw.iter_mut()
+    }
+}
+
+impl<I: Idx, T: fmt::Debug, const N: usize> fmt::Debug for IndexArrayVec<I, T, N> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.as_slice().raw, fmt)
+    }
+}
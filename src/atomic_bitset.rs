@@ -0,0 +1,177 @@
+use alloc::boxed::Box;
+use core::{
+    fmt,
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{
+    Idx,
+    bitset::{WORD_BITS, hash_bits, set_bits},
+};
+
+/// A fixed-size bitset keyed by an index type, which can be updated
+/// concurrently through a shared reference.
+///
+/// Each bit lives in an `AtomicUsize` word, so parallel marking phases (e.g.
+/// reachability, or the mark part of a mark and sweep) can share one visited
+/// set without a mutex.
+///
+/// ```rust
+/// use oxc_index::AtomicIndexBitSet;
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let visited = AtomicIndexBitSet::<NodeIdx>::new(100);
+/// assert!(visited.insert(NodeIdx::new(42)));
+/// assert!(!visited.insert(NodeIdx::new(42)));
+/// assert!(visited.contains(NodeIdx::new(42)));
+/// ```
+pub struct AtomicIndexBitSet<I: Idx> {
+    words: Box<[AtomicUsize]>,
+    domain_size: usize,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx> AtomicIndexBitSet<I> {
+    /// Construct an empty bitset able to hold the indices `0..domain_size`.
+    #[inline]
+    pub fn new(domain_size: usize) -> Self {
+        let words = (0..domain_size.div_ceil(WORD_BITS)).map(|_| AtomicUsize::new(0)).collect();
+        Self { words, domain_size, _marker: PhantomData }
+    }
+
+    /// The number of indices this set can hold.
+    #[inline]
+    pub const fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    #[inline]
+    fn word_and_mask(&self, idx: I) -> (&AtomicUsize, usize) {
+        let idx = idx.index();
+        assert!(idx < self.domain_size, "index {} out of bounds for {}", idx, self.domain_size);
+        (&self.words[idx / WORD_BITS], 1 << (idx % WORD_BITS))
+    }
+
+    /// Insert `idx`, returning true if it was not already present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is outside the domain.
+    #[inline]
+    pub fn insert(&self, idx: I) -> bool {
+        let (word, mask) = self.word_and_mask(idx);
+        word.fetch_or(mask, Ordering::AcqRel) & mask == 0
+    }
+
+    /// Remove `idx`, returning true if it was present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is outside the domain.
+    #[inline]
+    pub fn remove(&self, idx: I) -> bool {
+        let (word, mask) = self.word_and_mask(idx);
+        word.fetch_and(!mask, Ordering::AcqRel) & mask != 0
+    }
+
+    /// Returns true if `idx` is present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is outside the domain.
+    #[inline]
+    pub fn contains(&self, idx: I) -> bool {
+        let (word, mask) = self.word_and_mask(idx);
+        word.load(Ordering::Acquire) & mask != 0
+    }
+
+    /// Count the indices currently present.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.load(Ordering::Acquire).count_ones() as usize).sum()
+    }
+
+    /// Remove every index.
+    #[inline]
+    pub fn clear(&mut self) {
+        for w in &mut self.words {
+            *w.get_mut() = 0;
+        }
+    }
+
+    /// Iterate over the indices currently present, in ascending order.
+    ///
+    /// Takes `&mut self`, so it always observes a settled state.
+    pub fn iter(&mut self) -> impl Iterator<Item = I> + '_ {
+        set_bits(self.words.iter_mut().map(|word| *word.get_mut())).map(I::from_usize)
+    }
+}
+
+impl<I: Idx> Extend<I> for AtomicIndexBitSet<I> {
+    /// Insert every index yielded by `iter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is outside the domain.
+    #[inline]
+    fn extend<J: IntoIterator<Item = I>>(&mut self, iter: J) {
+        for idx in iter {
+            self.insert(idx);
+        }
+    }
+}
+
+impl<I: Idx> FromIterator<I> for AtomicIndexBitSet<I> {
+    /// Collect indices into a set whose domain ends just past the largest one.
+    fn from_iter<J: IntoIterator<Item = I>>(iter: J) -> Self {
+        let indices: alloc::vec::Vec<I> = iter.into_iter().collect();
+        let domain_size = indices.iter().map(|i| i.index() + 1).max().unwrap_or(0);
+        let mut set = Self::new(domain_size);
+        set.extend(indices);
+        set
+    }
+}
+
+impl<I: Idx> core::hash::Hash for AtomicIndexBitSet<I> {
+    /// Hashes the domain size and the bits as little endian bytes, so the
+    /// result doesn't depend on the platform's word size.
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        let words = self.words.iter().map(|w| w.load(Ordering::Acquire));
+        hash_bits(self.domain_size, words, state);
+    }
+}
+
+impl<I: Idx> fmt::Debug for AtomicIndexBitSet<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("AtomicIndexBitSet")
+            .field("domain_size", &self.domain_size)
+            .field("count", &self.count())
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I: Idx> crate::serde::ser::Serialize for AtomicIndexBitSet<I> {
+    fn serialize<S: crate::serde::ser::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let words = self.words.iter().map(|w| w.load(Ordering::Acquire));
+        crate::serde_bits::serialize(self.domain_size, words, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I: Idx> crate::serde::de::Deserialize<'de> for AtomicIndexBitSet<I> {
+    fn deserialize<D: crate::serde::de::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let (domain_size, words) = crate::serde_bits::deserialize(deserializer)?;
+        let words = words.into_iter().map(AtomicUsize::new).collect();
+        Ok(Self { words, domain_size, _marker: PhantomData })
+    }
+}
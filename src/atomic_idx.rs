@@ -0,0 +1,332 @@
+use core::{
+    fmt,
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::Idx;
+
+/// An atomic integer which can hold an index, for [`AtomicIdx`].
+///
+/// Values go in and out as `usize`s, and are stored in the atomic's own
+/// width, so they have to fit in it.
+pub trait RawAtomic: Send + Sync {
+    /// Construct the atomic holding `value`.
+    fn new(value: usize) -> Self;
+
+    /// See [`AtomicUsize::load`].
+    fn load(&self, order: Ordering) -> usize;
+
+    /// See [`AtomicUsize::store`].
+    fn store(&self, value: usize, order: Ordering);
+
+    /// See [`AtomicUsize::swap`].
+    fn swap(&self, value: usize, order: Ordering) -> usize;
+
+    /// See [`AtomicUsize::compare_exchange`].
+    ///
+    /// # Errors
+    ///
+    /// Gives the actual value back if it wasn't `current`.
+    fn compare_exchange(
+        &self,
+        current: usize,
+        new: usize,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<usize, usize>;
+
+    /// See [`AtomicUsize::compare_exchange_weak`].
+    ///
+    /// # Errors
+    ///
+    /// Gives the actual value back if it wasn't `current`, or on a spurious
+    /// failure.
+    fn compare_exchange_weak(
+        &self,
+        current: usize,
+        new: usize,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<usize, usize>;
+
+    /// See [`AtomicUsize::fetch_update`].
+    ///
+    /// # Errors
+    ///
+    /// Gives the current value back if `f` returns `None`.
+    fn fetch_update<F: FnMut(usize) -> Option<usize>>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        f: F,
+    ) -> Result<usize, usize>;
+
+    /// Unwrap the value.
+    fn into_inner(self) -> usize;
+}
+
+macro_rules! impl_raw_atomic {
+    ($($atomic:ident($int:ty) if $width:literal),* $(,)?) => {$(
+        #[cfg(target_has_atomic = $width)]
+        impl RawAtomic for core::sync::atomic::$atomic {
+            #[inline]
+            fn new(value: usize) -> Self {
+                Self::new(value as $int)
+            }
+
+            #[inline]
+            fn load(&self, order: Ordering) -> usize {
+                self.load(order) as usize
+            }
+
+            #[inline]
+            fn store(&self, value: usize, order: Ordering) {
+                self.store(value as $int, order);
+            }
+
+            #[inline]
+            fn swap(&self, value: usize, order: Ordering) -> usize {
+                self.swap(value as $int, order) as usize
+            }
+
+            #[inline]
+            fn compare_exchange(
+                &self,
+                current: usize,
+                new: usize,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<usize, usize> {
+                self.compare_exchange(current as $int, new as $int, success, failure)
+                    .map(|v| v as usize)
+                    .map_err(|v| v as usize)
+            }
+
+            #[inline]
+            fn compare_exchange_weak(
+                &self,
+                current: usize,
+                new: usize,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<usize, usize> {
+                self.compare_exchange_weak(current as $int, new as $int, success, failure)
+                    .map(|v| v as usize)
+                    .map_err(|v| v as usize)
+            }
+
+            #[inline]
+            fn fetch_update<F: FnMut(usize) -> Option<usize>>(
+                &self,
+                set_order: Ordering,
+                fetch_order: Ordering,
+                mut f: F,
+            ) -> Result<usize, usize> {
+                self.fetch_update(set_order, fetch_order, |v| f(v as usize).map(|v| v as $int))
+                    .map(|v| v as usize)
+                    .map_err(|v| v as usize)
+            }
+
+            #[inline]
+            fn into_inner(self) -> usize {
+                self.into_inner() as usize
+            }
+        }
+    )*};
+}
+
+impl_raw_atomic! {
+    AtomicU8(u8) if "8",
+    AtomicU16(u16) if "16",
+    AtomicU32(u32) if "32",
+    AtomicUsize(usize) if "ptr",
+}
+
+/// An index type which knows the atomic integer to store it in, so an
+/// [`AtomicIdx`] is no bigger than the index itself.
+///
+/// Index types from `define_index_type!`, `#[derive(Idx)]` and the niche
+/// macros implement this, picking the atomic matching their raw type. For a
+/// hand-written [`Idx`] impl, `AtomicUsize` always works.
+pub trait HasAtomic: Idx {
+    /// The atomic integer, which has to fit every index up to `Self::MAX`.
+    type Atomic: RawAtomic;
+}
+
+/// Maps the raw integer of an index type to the atomic holding it. `u64` maps
+/// to `AtomicUsize`, as an index always fits a `usize`, as do the others on
+/// targets without atomics of their width.
+#[doc(hidden)]
+pub trait __RawAtomicFor {
+    type Atomic: RawAtomic;
+}
+
+macro_rules! impl_raw_atomic_for {
+    ($($int:ty => $atomic:ident if $width:literal),* $(,)?) => {$(
+        impl __RawAtomicFor for $int {
+            #[cfg(target_has_atomic = $width)]
+            type Atomic = core::sync::atomic::$atomic;
+            #[cfg(not(target_has_atomic = $width))]
+            type Atomic = AtomicUsize;
+        }
+    )*};
+}
+
+impl_raw_atomic_for! {
+    u8 => AtomicU8 if "8",
+    u16 => AtomicU16 if "16",
+    u32 => AtomicU32 if "32",
+}
+
+impl __RawAtomicFor for u64 {
+    type Atomic = AtomicUsize;
+}
+
+impl __RawAtomicFor for usize {
+    type Atomic = AtomicUsize;
+}
+
+/// An index which can be shared between threads, like an atomic integer that
+/// only holds indices of a specific type.
+///
+/// This is handy for parallel passes which publish a result per node (e.g. a
+/// resolved reference's target), without going through raw integers. The
+/// index is stored in the atomic matching the index type's raw integer (see
+/// [`HasAtomic`]), so an `AtomicIdx` of a `u32` index takes 4 bytes.
+///
+/// ```rust
+/// use std::sync::atomic::Ordering;
+///
+/// use oxc_index::AtomicIdx;
+///
+/// oxc_index::define_index_type! {
+///     pub struct SymbolId = u32;
+/// }
+///
+/// let target = AtomicIdx::new(SymbolId::new(0));
+/// assert_eq!(size_of_val(&target), 4);
+/// std::thread::scope(|s| {
+///     s.spawn(|| target.store(SymbolId::new(7), Ordering::Release));
+/// });
+/// assert_eq!(target.load(Ordering::Acquire), SymbolId::new(7));
+/// assert_eq!(
+///     target.compare_exchange(SymbolId::new(7), SymbolId::new(8), Ordering::AcqRel, Ordering::Acquire),
+///     Ok(SymbolId::new(7)),
+/// );
+/// ```
+pub struct AtomicIdx<I: HasAtomic> {
+    raw: I::Atomic,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: HasAtomic> AtomicIdx<I> {
+    /// Construct an `AtomicIdx` holding `idx`.
+    #[inline]
+    pub fn new(idx: I) -> Self {
+        Self { raw: I::Atomic::new(idx.index()), _marker: PhantomData }
+    }
+
+    /// Turn a raw value we stored back into an index.
+    #[inline]
+    fn from_raw(raw: usize) -> I {
+        I::from_usize(raw)
+    }
+
+    /// Load the index. See [`RawAtomic::load`].
+    #[inline]
+    pub fn load(&self, order: Ordering) -> I {
+        Self::from_raw(self.raw.load(order))
+    }
+
+    /// Store `idx`. See [`RawAtomic::store`].
+    #[inline]
+    pub fn store(&self, idx: I, order: Ordering) {
+        self.raw.store(idx.index(), order);
+    }
+
+    /// Store `idx`, returning the previous index. See [`RawAtomic::swap`].
+    #[inline]
+    pub fn swap(&self, idx: I, order: Ordering) -> I {
+        Self::from_raw(self.raw.swap(idx.index(), order))
+    }
+
+    /// Store `new` if the current index is `current`. See
+    /// [`RawAtomic::compare_exchange`].
+    ///
+    /// # Errors
+    ///
+    /// Gives the actual index back if it wasn't `current`.
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: I,
+        new: I,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<I, I> {
+        self.raw
+            .compare_exchange(current.index(), new.index(), success, failure)
+            .map(Self::from_raw)
+            .map_err(Self::from_raw)
+    }
+
+    /// Like [`AtomicIdx::compare_exchange`], but may fail spuriously. See
+    /// [`RawAtomic::compare_exchange_weak`].
+    ///
+    /// # Errors
+    ///
+    /// Gives the actual index back if it wasn't `current`, or on a spurious
+    /// failure.
+    #[inline]
+    pub fn compare_exchange_weak(
+        &self,
+        current: I,
+        new: I,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<I, I> {
+        self.raw
+            .compare_exchange_weak(current.index(), new.index(), success, failure)
+            .map(Self::from_raw)
+            .map_err(Self::from_raw)
+    }
+
+    /// Replace the index with the result of `f`, retrying if another thread
+    /// changes it first. See [`RawAtomic::fetch_update`].
+    ///
+    /// # Errors
+    ///
+    /// Gives the current index back if `f` returns `None`.
+    #[inline]
+    pub fn fetch_update<F: FnMut(I) -> Option<I>>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<I, I> {
+        self.raw
+            .fetch_update(set_order, fetch_order, |raw| f(Self::from_raw(raw)).map(I::index))
+            .map(Self::from_raw)
+            .map_err(Self::from_raw)
+    }
+
+    /// Unwrap the index.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        Self::from_raw(self.raw.into_inner())
+    }
+}
+
+impl<I: HasAtomic> From<I> for AtomicIdx<I> {
+    #[inline]
+    fn from(idx: I) -> Self {
+        Self::new(idx)
+    }
+}
+
+impl<I: HasAtomic> fmt::Debug for AtomicIdx<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(Ordering::Relaxed), fmt)
+    }
+}
@@ -0,0 +1,54 @@
+use alloc::vec::Vec;
+
+use bincode::{
+    BorrowDecode, Decode, Encode,
+    de::{BorrowDecoder, Decoder},
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+};
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// Check a decoded vector fits our index type, like `IndexVec::from_vec`
+/// does, but with an error rather than a panic.
+#[inline]
+fn check_len<I: Idx, T>(raw: Vec<T>) -> Result<IndexVec<I, T>, DecodeError> {
+    if I::try_from_usize(raw.len()).is_none() {
+        return Err(DecodeError::Other("`IndexVec` too long for its index type"));
+    }
+    Ok(IndexVec::from_vec(raw))
+}
+
+/// Encoded exactly like a `Vec<T>`.
+impl<I: Idx, T: Encode> Encode for IndexVec<I, T> {
+    #[inline]
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.raw.encode(encoder)
+    }
+}
+
+/// Encoded exactly like a `[T]`.
+impl<I: Idx, T: Encode> Encode for IndexSlice<I, [T]> {
+    #[inline]
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.raw.encode(encoder)
+    }
+}
+
+impl<Context, I: Idx, T: Decode<Context>> Decode<Context> for IndexVec<I, T> {
+    #[inline]
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        check_len(Vec::decode(decoder)?)
+    }
+}
+
+impl<'de, Context, I: Idx, T: BorrowDecode<'de, Context>> BorrowDecode<'de, Context>
+    for IndexVec<I, T>
+{
+    #[inline]
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        check_len(Vec::borrow_decode(decoder)?)
+    }
+}
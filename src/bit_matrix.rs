@@ -0,0 +1,182 @@
+use alloc::boxed::Box;
+use core::{fmt, marker::PhantomData};
+
+use crate::{
+    Idx,
+    bitset::{WORD_BITS, set_bits},
+};
+
+/// A fixed-size relation between two index domains, stored as one bit per
+/// `(row, column)` pair.
+///
+/// Each row is a bitset of columns, so questions like "which symbols can
+/// scope `R` see" are answered by iterating a row, and propagating a relation
+/// along an edge is a [`BitMatrix::union_rows`].
+///
+/// ```rust
+/// use oxc_index::BitMatrix;
+///
+/// oxc_index::define_index_type! {
+///     pub struct ScopeId = u32;
+/// }
+/// oxc_index::define_index_type! {
+///     pub struct SymbolId = u32;
+/// }
+///
+/// let mut visible = BitMatrix::<ScopeId, SymbolId>::new(2, 10);
+/// visible.insert(ScopeId::new(0), SymbolId::new(3));
+/// visible.insert(ScopeId::new(1), SymbolId::new(7));
+/// // The child scope sees everything the parent does.
+/// assert!(visible.union_rows(ScopeId::new(0), ScopeId::new(1)));
+/// assert_eq!(
+///     visible.iter(ScopeId::new(1)).collect::<Vec<_>>(),
+///     [SymbolId::new(3), SymbolId::new(7)],
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct BitMatrix<R: Idx, C: Idx> {
+    num_rows: usize,
+    num_columns: usize,
+    words: Box<[usize]>,
+    _marker: PhantomData<fn(&R, &C)>,
+}
+
+impl<R: Idx, C: Idx> BitMatrix<R, C> {
+    /// Construct an empty matrix with `num_rows` rows and `num_columns`
+    /// columns.
+    #[inline]
+    pub fn new(num_rows: usize, num_columns: usize) -> Self {
+        let words = alloc::vec![0; num_rows * num_columns.div_ceil(WORD_BITS)];
+        Self { num_rows, num_columns, words: words.into_boxed_slice(), _marker: PhantomData }
+    }
+
+    /// The number of rows.
+    #[inline]
+    pub const fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// The number of columns.
+    #[inline]
+    pub const fn num_columns(&self) -> usize {
+        self.num_columns
+    }
+
+    #[inline]
+    fn row_words(&self, row: R) -> core::ops::Range<usize> {
+        let row = row.index();
+        assert!(row < self.num_rows, "row {} out of bounds for {}", row, self.num_rows);
+        let words_per_row = self.num_columns.div_ceil(WORD_BITS);
+        row * words_per_row..(row + 1) * words_per_row
+    }
+
+    #[inline]
+    fn word_and_mask(&self, row: R, column: C) -> (usize, usize) {
+        let column = column.index();
+        assert!(
+            column < self.num_columns,
+            "column {} out of bounds for {}",
+            column,
+            self.num_columns
+        );
+        (self.row_words(row).start + column / WORD_BITS, 1 << (column % WORD_BITS))
+    }
+
+    /// Set the bit at `(row, column)`, returning true if it was not already
+    /// set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` or `column` is out of bounds.
+    #[inline]
+    pub fn insert(&mut self, row: R, column: C) -> bool {
+        let (word, mask) = self.word_and_mask(row, column);
+        let word = &mut self.words[word];
+        let changed = *word & mask == 0;
+        *word |= mask;
+        changed
+    }
+
+    /// Clear the bit at `(row, column)`, returning true if it was set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` or `column` is out of bounds.
+    #[inline]
+    pub fn remove(&mut self, row: R, column: C) -> bool {
+        let (word, mask) = self.word_and_mask(row, column);
+        let word = &mut self.words[word];
+        let changed = *word & mask != 0;
+        *word &= !mask;
+        changed
+    }
+
+    /// Returns true if the bit at `(row, column)` is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` or `column` is out of bounds.
+    #[inline]
+    pub fn contains(&self, row: R, column: C) -> bool {
+        let (word, mask) = self.word_and_mask(row, column);
+        self.words[word] & mask != 0
+    }
+
+    /// Set every bit of row `write` which is set in row `read`, returning true
+    /// if `write` changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either row is out of bounds.
+    pub fn union_rows(&mut self, read: R, write: R) -> bool {
+        let (read, write) = (self.row_words(read), self.row_words(write));
+        let mut changed = false;
+        for (r, w) in read.zip(write) {
+            let new = self.words[w] | self.words[r];
+            changed |= new != self.words[w];
+            self.words[w] = new;
+        }
+        changed
+    }
+
+    /// Count the bits set in `row`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds.
+    #[inline]
+    pub fn count(&self, row: R) -> usize {
+        self.words[self.row_words(row)].iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Clear every bit.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Iterate over the columns set in `row`, in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds.
+    pub fn iter(&self, row: R) -> impl Iterator<Item = C> + '_ {
+        set_bits(self.words[self.row_words(row)].iter().copied()).map(C::from_usize)
+    }
+}
+
+impl<R: Idx, C: Idx> fmt::Debug for BitMatrix<R, C> {
+    /// Formats the non-empty rows as a map from row to set of columns.
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Row<'a, R: Idx, C: Idx>(&'a BitMatrix<R, C>, R);
+
+        impl<R: Idx, C: Idx> fmt::Debug for Row<'_, R, C> {
+            fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt.debug_set().entries(self.0.iter(self.1)).finish()
+            }
+        }
+
+        let rows = (0..self.num_rows).map(R::from_usize).filter(|&row| self.count(row) != 0);
+        fmt.debug_map().entries(rows.map(|row| (row, Row(self, row)))).finish()
+    }
+}
@@ -0,0 +1,253 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+use crate::Idx;
+
+pub(crate) const WORD_BITS: usize = usize::BITS as usize;
+
+/// A fixed-size bitset keyed by an index type.
+///
+/// This stores one bit per index, rather than the byte per index of an
+/// `IndexVec<I, bool>`, and supports in-place set operations. Those return
+/// whether they changed the set, which is what a dataflow analysis needs to
+/// find its fixpoint.
+///
+/// ```rust
+/// use oxc_index::IndexBitSet;
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let mut live = IndexBitSet::<NodeIdx>::new(100);
+/// assert!(live.insert(NodeIdx::new(42)));
+/// assert!(!live.insert(NodeIdx::new(42)));
+///
+/// let mut uses = IndexBitSet::new(100);
+/// uses.insert(NodeIdx::new(7));
+/// assert!(live.union(&uses));
+/// assert!(!live.union(&uses));
+/// assert_eq!(live.iter().collect::<Vec<_>>(), [NodeIdx::new(7), NodeIdx::new(42)]);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct IndexBitSet<I: Idx> {
+    words: Box<[usize]>,
+    domain_size: usize,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx> IndexBitSet<I> {
+    /// Construct an empty bitset able to hold the indices `0..domain_size`.
+    #[inline]
+    pub fn new(domain_size: usize) -> Self {
+        let words = alloc::vec![0; domain_size.div_ceil(WORD_BITS)].into_boxed_slice();
+        Self { words, domain_size, _marker: PhantomData }
+    }
+
+    /// The number of indices this set can hold.
+    #[inline]
+    pub const fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    #[inline]
+    fn word_and_mask(&self, idx: I) -> (usize, usize) {
+        let idx = idx.index();
+        assert!(idx < self.domain_size, "index {} out of bounds for {}", idx, self.domain_size);
+        (idx / WORD_BITS, 1 << (idx % WORD_BITS))
+    }
+
+    /// Insert `idx`, returning true if it was not already present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is outside the domain.
+    #[inline]
+    pub fn insert(&mut self, idx: I) -> bool {
+        let (word, mask) = self.word_and_mask(idx);
+        let word = &mut self.words[word];
+        let changed = *word & mask == 0;
+        *word |= mask;
+        changed
+    }
+
+    /// Remove `idx`, returning true if it was present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is outside the domain.
+    #[inline]
+    pub fn remove(&mut self, idx: I) -> bool {
+        let (word, mask) = self.word_and_mask(idx);
+        let word = &mut self.words[word];
+        let changed = *word & mask != 0;
+        *word &= !mask;
+        changed
+    }
+
+    /// Returns true if `idx` is present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is outside the domain.
+    #[inline]
+    pub fn contains(&self, idx: I) -> bool {
+        let (word, mask) = self.word_and_mask(idx);
+        self.words[word] & mask != 0
+    }
+
+    /// Count the indices present.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Returns true if no index is present.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Remove every index.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    fn combine(&mut self, other: &Self, op: impl Fn(usize, usize) -> usize) -> bool {
+        assert_eq!(self.domain_size, other.domain_size, "bitset domain sizes differ");
+        let mut changed = false;
+        for (word, &other) in self.words.iter_mut().zip(&*other.words) {
+            let new = op(*word, other);
+            changed |= new != *word;
+            *word = new;
+        }
+        changed
+    }
+
+    /// Add every index in `other`, returning true if `self` changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the domain sizes differ.
+    #[inline]
+    pub fn union(&mut self, other: &Self) -> bool {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// Remove every index not in `other`, returning true if `self` changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the domain sizes differ.
+    #[inline]
+    pub fn intersect(&mut self, other: &Self) -> bool {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// Remove every index in `other`, returning true if `self` changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the domain sizes differ.
+    #[inline]
+    pub fn difference(&mut self, other: &Self) -> bool {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    /// Iterate over the indices present, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = I> + '_ {
+        set_bits(self.words.iter().copied()).map(I::from_usize)
+    }
+}
+
+/// Hash a bitset's domain size and its bits as little endian bytes, shared by
+/// the bitset types so that equal sets hash the same whichever stores them.
+pub(crate) fn hash_bits<H: core::hash::Hasher>(
+    domain_size: usize,
+    words: impl IntoIterator<Item = usize>,
+    state: &mut H,
+) {
+    state.write_u64(domain_size as u64);
+    let mut remaining = domain_size.div_ceil(8);
+    for word in words.into_iter().take(domain_size.div_ceil(WORD_BITS)) {
+        let bytes = word.to_le_bytes();
+        let n = remaining.min(bytes.len());
+        state.write(&bytes[..n]);
+        remaining -= n;
+    }
+}
+
+/// The positions of the set bits in `words`, in ascending order.
+pub(crate) fn set_bits(words: impl IntoIterator<Item = usize>) -> impl Iterator<Item = usize> {
+    words.into_iter().enumerate().flat_map(|(w, word)| {
+        let mut bits = word;
+        core::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let bit = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(w * WORD_BITS + bit)
+        })
+    })
+}
+
+impl<I: Idx> Extend<I> for IndexBitSet<I> {
+    /// Insert every index yielded by `iter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is outside the domain.
+    #[inline]
+    fn extend<J: IntoIterator<Item = I>>(&mut self, iter: J) {
+        for idx in iter {
+            self.insert(idx);
+        }
+    }
+}
+
+impl<I: Idx> FromIterator<I> for IndexBitSet<I> {
+    /// Collect indices into a set whose domain ends just past the largest one.
+    fn from_iter<J: IntoIterator<Item = I>>(iter: J) -> Self {
+        let indices: Vec<I> = iter.into_iter().collect();
+        let domain_size = indices.iter().map(|i| i.index() + 1).max().unwrap_or(0);
+        let mut set = Self::new(domain_size);
+        set.extend(indices);
+        set
+    }
+}
+
+impl<I: Idx> core::hash::Hash for IndexBitSet<I> {
+    /// Hashes the domain size and the bits as little endian bytes, so the
+    /// result doesn't depend on the platform's word size.
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        hash_bits(self.domain_size, self.words.iter().copied(), state);
+    }
+}
+
+impl<I: Idx> fmt::Debug for IndexBitSet<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I: Idx> crate::serde::ser::Serialize for IndexBitSet<I> {
+    fn serialize<S: crate::serde::ser::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        crate::serde_bits::serialize(self.domain_size, self.words.iter().copied(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I: Idx> crate::serde::de::Deserialize<'de> for IndexBitSet<I> {
+    fn deserialize<D: crate::serde::de::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let (domain_size, words) = crate::serde_bits::deserialize(deserializer)?;
+        Ok(Self { words: words.into_boxed_slice(), domain_size, _marker: PhantomData })
+    }
+}
@@ -0,0 +1,216 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// An invariant lifetime, which ties branded indices to the one
+/// [`BrandedIndexVec`] they came from.
+type Brand<'id> = PhantomData<fn(&'id ()) -> &'id ()>;
+
+/// An `IndexVec` carrying a unique, compiler-checked brand `'id`, which lets
+/// it hand out [`BrandedIdx`]es that are known to be in bounds.
+///
+/// A `BrandedIndexVec` only exists within a call to
+/// [`BrandedIndexVec::scope`], and every call picks a fresh `'id`, so a
+/// `BrandedIdx<'id, I>` can't be used with any other vector. As the vector
+/// can only grow, not shrink, indexing with a branded index skips the bounds
+/// check, without any `unsafe` at the call site.
+///
+/// ```rust
+/// use oxc_index::{BrandedIndexVec, IndexVec, index_vec};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let nodes: IndexVec<NodeIdx, u32> = index_vec![3, 1, 4];
+/// let (sum, nodes) = BrandedIndexVec::scope(nodes, |mut nodes| {
+///     let five = nodes.push(5);
+///     let mut sum = 0;
+///     for idx in nodes.indices() {
+///         sum += nodes[idx]; // no bounds check
+///     }
+///     nodes[five] = 9;
+///     (sum, nodes.into_inner())
+/// });
+/// assert_eq!(sum, 13);
+/// assert_eq!(nodes.raw, [3, 1, 4, 9]);
+/// ```
+///
+/// Branded indices from one scope are rejected by another:
+///
+/// ```rust,compile_fail
+/// # use oxc_index::{BrandedIndexVec, IndexVec, index_vec};
+/// # oxc_index::define_index_type! { pub struct NodeIdx = u32; }
+/// let a: IndexVec<NodeIdx, u32> = index_vec![1];
+/// let b: IndexVec<NodeIdx, u32> = index_vec![];
+/// BrandedIndexVec::scope(a, |a| {
+///     let idx = a.indices().next().unwrap();
+///     BrandedIndexVec::scope(b, |b| b[idx]);
+/// });
+/// ```
+pub struct BrandedIndexVec<'id, I: Idx, T> {
+    vec: IndexVec<I, T>,
+    _brand: Brand<'id>,
+}
+
+/// An index into the [`BrandedIndexVec`] with the brand `'id`, which is known
+/// to be in bounds for it.
+pub struct BrandedIdx<'id, I: Idx> {
+    /// The checked position. We keep this rather than the `I`, as a safe
+    /// `Idx` impl could give a different `index()` each time.
+    pos: usize,
+    _marker: PhantomData<fn(&I)>,
+    _brand: Brand<'id>,
+}
+
+impl<I: Idx> BrandedIdx<'_, I> {
+    /// Brand `pos`, which the caller has checked is in bounds.
+    #[inline]
+    fn new(pos: usize) -> Self {
+        Self { pos, _marker: PhantomData, _brand: PhantomData }
+    }
+}
+
+impl<I: Idx, T> BrandedIndexVec<'_, I, T> {
+    /// Brand `vec` for the duration of `f`. Use
+    /// [`BrandedIndexVec::into_inner`] to get it back out.
+    pub fn scope<R, F>(vec: IndexVec<I, T>, f: F) -> R
+    where
+        F: for<'id> FnOnce(BrandedIndexVec<'id, I, T>) -> R,
+    {
+        f(BrandedIndexVec { vec, _brand: PhantomData })
+    }
+}
+
+impl<'id, I: Idx, T> BrandedIndexVec<'id, I, T> {
+    /// Unwrap the vector, dropping the brand.
+    #[inline]
+    pub fn into_inner(self) -> IndexVec<I, T> {
+        self.vec
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Returns true if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Get the elements as a regular `IndexSlice`.
+    #[inline]
+    pub fn as_index_slice(&self) -> &IndexSlice<I, [T]> {
+        &self.vec
+    }
+
+    /// Get the elements as a regular mutable `IndexSlice`. This can't change
+    /// the length, so the branded indices stay valid.
+    #[inline]
+    pub fn as_mut_index_slice(&mut self) -> &mut IndexSlice<I, [T]> {
+        &mut self.vec
+    }
+
+    /// Push `value`, and return its branded index.
+    #[inline]
+    pub fn push(&mut self, value: T) -> BrandedIdx<'id, I> {
+        let pos = self.len();
+        self.vec.push(value);
+        BrandedIdx::new(pos)
+    }
+
+    /// Brand `idx`, or return `None` if it's out of bounds.
+    #[inline]
+    pub fn check(&self, idx: I) -> Option<BrandedIdx<'id, I>> {
+        let pos = idx.index();
+        (pos < self.len()).then(|| BrandedIdx::new(pos))
+    }
+
+    /// Iterate over the branded indices of all the elements.
+    #[inline]
+    pub fn indices(&self) -> impl DoubleEndedIterator<Item = BrandedIdx<'id, I>> + use<'id, I, T> {
+        (0..self.len()).map(BrandedIdx::new)
+    }
+}
+
+impl<I: Idx> BrandedIdx<'_, I> {
+    /// Get the plain index.
+    #[inline]
+    pub fn idx(self) -> I {
+        I::from_usize(self.pos)
+    }
+}
+
+impl<'id, I: Idx, T> core::ops::Index<BrandedIdx<'id, I>> for BrandedIndexVec<'id, I, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, idx: BrandedIdx<'id, I>) -> &T {
+        // SAFETY: the brand says `idx.pos` was checked against this vector,
+        // which never shrinks.
+        unsafe { self.vec.raw.get_unchecked(idx.pos) }
+    }
+}
+
+impl<'id, I: Idx, T> core::ops::IndexMut<BrandedIdx<'id, I>> for BrandedIndexVec<'id, I, T> {
+    #[inline]
+    fn index_mut(&mut self, idx: BrandedIdx<'id, I>) -> &mut T {
+        // SAFETY: the brand says `idx.pos` was checked against this vector,
+        // which never shrinks.
+        unsafe { self.vec.raw.get_unchecked_mut(idx.pos) }
+    }
+}
+
+impl<I: Idx> Clone for BrandedIdx<'_, I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I: Idx> Copy for BrandedIdx<'_, I> {}
+
+impl<I: Idx> PartialEq for BrandedIdx<'_, I> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.pos == other.pos
+    }
+}
+
+impl<I: Idx> Eq for BrandedIdx<'_, I> {}
+
+impl<I: Idx> PartialOrd for BrandedIdx<'_, I> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<I: Idx> Ord for BrandedIdx<'_, I> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.pos.cmp(&other.pos)
+    }
+}
+
+impl<I: Idx> core::hash::Hash for BrandedIdx<'_, I> {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.pos.hash(state);
+    }
+}
+
+impl<I: Idx> fmt::Debug for BrandedIdx<'_, I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.idx().fmt(fmt)
+    }
+}
+
+impl<I: Idx, T: fmt::Debug> fmt::Debug for BrandedIndexVec<'_, I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.vec.fmt(fmt)
+    }
+}
@@ -0,0 +1,59 @@
+use bytemuck::{Pod, PodCastError};
+
+use crate::{Idx, IndexSlice};
+
+/// Byte views of tables of plain-old-data, for dumping a table to disk (or
+/// memory-mapping one back in) without copying. Index types made with
+/// `define_index_type!` are `Pod`, so tables of them qualify too.
+///
+/// ```rust
+/// use oxc_index::{IndexSlice, index_vec};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let parents = index_vec![NodeIdx::new(0), NodeIdx::new(0), NodeIdx::new(1)];
+/// let bytes = parents.as_bytes();
+/// assert_eq!(bytes.len(), 12);
+///
+/// let loaded: &IndexSlice<NodeIdx, [NodeIdx]> = IndexSlice::from_bytes(bytes);
+/// assert_eq!(loaded, &parents[..]);
+/// ```
+impl<I: Idx, T: Pod> IndexSlice<I, [T]> {
+    /// View the elements as bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.raw)
+    }
+
+    /// View the elements as mutable bytes.
+    #[inline]
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        bytemuck::cast_slice_mut(&mut self.raw)
+    }
+
+    /// View `bytes` as a table of `T`s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` isn't aligned for `T`, or its length isn't a multiple
+    /// of `T`'s size. See [`IndexSlice::try_from_bytes`] for a fallible
+    /// version.
+    #[inline]
+    #[track_caller]
+    pub fn from_bytes(bytes: &[u8]) -> &Self {
+        Self::new(bytemuck::cast_slice(bytes))
+    }
+
+    /// View `bytes` as a table of `T`s.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` isn't aligned for `T`, or its length isn't
+    /// a multiple of `T`'s size.
+    #[inline]
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<&Self, PodCastError> {
+        bytemuck::try_cast_slice(bytes).map(Self::new)
+    }
+}
@@ -0,0 +1,345 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+use crate::{
+    Idx,
+    bitset::{WORD_BITS, hash_bits, set_bits},
+};
+
+const CHUNK_BITS: usize = 2048;
+const CHUNK_WORDS: usize = CHUNK_BITS / WORD_BITS;
+
+type ChunkWords = [usize; CHUNK_WORDS];
+
+/// One `CHUNK_BITS` slice of the domain. `Mixed` is only used when the chunk
+/// is neither all zeros nor all ones, so equal chunks compare equal.
+#[derive(Clone, PartialEq, Eq)]
+enum Chunk {
+    Zeros,
+    Ones,
+    /// The count of set bits, and the bits. Bits past the domain are zero.
+    Mixed(usize, Box<ChunkWords>),
+}
+
+impl Chunk {
+    fn from_words(words: ChunkWords, len: usize) -> Self {
+        match words.iter().map(|w| w.count_ones() as usize).sum() {
+            0 => Chunk::Zeros,
+            count if count == len => Chunk::Ones,
+            count => Chunk::Mixed(count, Box::new(words)),
+        }
+    }
+
+    fn words(&self, len: usize) -> ChunkWords {
+        match self {
+            Chunk::Zeros => [0; CHUNK_WORDS],
+            Chunk::Ones => core::array::from_fn(|w| {
+                let bits = len.saturating_sub(w * WORD_BITS);
+                if bits >= WORD_BITS { !0 } else { (1 << bits) - 1 }
+            }),
+            Chunk::Mixed(_, words) => **words,
+        }
+    }
+}
+
+/// A fixed-size bitset keyed by an index type, which stays compact when
+/// (nearly) all or none of a region of the domain is set.
+///
+/// The domain is split into chunks of 2048 bits. A chunk which is all zeros
+/// or all ones takes no space beyond its tag; only mixed chunks store their
+/// bits. This is the layout rustc uses for dataflow over large functions,
+/// where a dense [`IndexBitSet`](crate::IndexBitSet) per basic block would
+/// be mostly zeros.
+///
+/// ```rust
+/// use oxc_index::ChunkedIndexBitSet;
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let mut live = ChunkedIndexBitSet::<NodeIdx>::new(1_000_000);
+/// assert!(live.insert(NodeIdx::new(42)));
+/// assert!(live.insert(NodeIdx::new(999_999)));
+/// assert!(!live.insert(NodeIdx::new(42)));
+/// assert_eq!(live.count(), 2);
+/// assert_eq!(live.iter().collect::<Vec<_>>(), [NodeIdx::new(42), NodeIdx::new(999_999)]);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ChunkedIndexBitSet<I: Idx> {
+    chunks: Box<[Chunk]>,
+    domain_size: usize,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx> ChunkedIndexBitSet<I> {
+    /// Construct an empty bitset able to hold the indices `0..domain_size`.
+    #[inline]
+    pub fn new(domain_size: usize) -> Self {
+        let chunks = alloc::vec![Chunk::Zeros; domain_size.div_ceil(CHUNK_BITS)];
+        Self { chunks: chunks.into_boxed_slice(), domain_size, _marker: PhantomData }
+    }
+
+    /// The number of indices this set can hold.
+    #[inline]
+    pub const fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    #[inline]
+    fn chunk_len(&self, chunk: usize) -> usize {
+        (self.domain_size - chunk * CHUNK_BITS).min(CHUNK_BITS)
+    }
+
+    /// Returns the chunk, the bit within it, and the chunk's length.
+    #[inline]
+    fn locate(&self, idx: I) -> (usize, usize, usize) {
+        let idx = idx.index();
+        assert!(idx < self.domain_size, "index {} out of bounds for {}", idx, self.domain_size);
+        let chunk = idx / CHUNK_BITS;
+        (chunk, idx % CHUNK_BITS, self.chunk_len(chunk))
+    }
+
+    /// Insert `idx`, returning true if it was not already present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is outside the domain.
+    pub fn insert(&mut self, idx: I) -> bool {
+        let (c, bit, len) = self.locate(idx);
+        let (word, mask) = (bit / WORD_BITS, 1 << (bit % WORD_BITS));
+        let chunk = &mut self.chunks[c];
+        match chunk {
+            Chunk::Ones => false,
+            Chunk::Zeros if len == 1 => {
+                *chunk = Chunk::Ones;
+                true
+            }
+            Chunk::Zeros => {
+                let mut words = Box::new([0; CHUNK_WORDS]);
+                words[word] |= mask;
+                *chunk = Chunk::Mixed(1, words);
+                true
+            }
+            Chunk::Mixed(_, words) if words[word] & mask != 0 => false,
+            Chunk::Mixed(count, words) => {
+                words[word] |= mask;
+                *count += 1;
+                if *count == len {
+                    *chunk = Chunk::Ones;
+                }
+                true
+            }
+        }
+    }
+
+    /// Remove `idx`, returning true if it was present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is outside the domain.
+    pub fn remove(&mut self, idx: I) -> bool {
+        let (c, bit, len) = self.locate(idx);
+        let (word, mask) = (bit / WORD_BITS, 1 << (bit % WORD_BITS));
+        let chunk = &mut self.chunks[c];
+        match chunk {
+            Chunk::Zeros => false,
+            Chunk::Ones if len == 1 => {
+                *chunk = Chunk::Zeros;
+                true
+            }
+            Chunk::Ones => {
+                let mut words = Box::new(chunk.words(len));
+                words[word] &= !mask;
+                *chunk = Chunk::Mixed(len - 1, words);
+                true
+            }
+            Chunk::Mixed(_, words) if words[word] & mask == 0 => false,
+            Chunk::Mixed(count, words) => {
+                words[word] &= !mask;
+                *count -= 1;
+                if *count == 0 {
+                    *chunk = Chunk::Zeros;
+                }
+                true
+            }
+        }
+    }
+
+    /// Returns true if `idx` is present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is outside the domain.
+    #[inline]
+    pub fn contains(&self, idx: I) -> bool {
+        let (c, bit, _) = self.locate(idx);
+        match &self.chunks[c] {
+            Chunk::Zeros => false,
+            Chunk::Ones => true,
+            Chunk::Mixed(_, words) => words[bit / WORD_BITS] & (1 << (bit % WORD_BITS)) != 0,
+        }
+    }
+
+    /// Count the indices present.
+    pub fn count(&self) -> usize {
+        self.chunks
+            .iter()
+            .enumerate()
+            .map(|(c, chunk)| match chunk {
+                Chunk::Zeros => 0,
+                Chunk::Ones => self.chunk_len(c),
+                Chunk::Mixed(count, _) => *count,
+            })
+            .sum()
+    }
+
+    /// Returns true if no index is present.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.chunks.iter().all(|chunk| *chunk == Chunk::Zeros)
+    }
+
+    /// Remove every index.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.chunks.fill(Chunk::Zeros);
+    }
+
+    fn combine(&mut self, other: &Self, op: impl Fn(usize, usize) -> usize) -> bool {
+        assert_eq!(self.domain_size, other.domain_size, "bitset domain sizes differ");
+        let mut changed = false;
+        for c in 0..self.chunks.len() {
+            let len = self.chunk_len(c);
+            let (chunk, other) = (&mut self.chunks[c], &other.chunks[c]);
+            let new = match (&*chunk, other) {
+                // Neither side stores bits, so one op on full words decides.
+                (Chunk::Zeros | Chunk::Ones, Chunk::Zeros | Chunk::Ones) => {
+                    let full = |chunk: &Chunk| if *chunk == Chunk::Ones { !0 } else { 0 };
+                    if op(full(chunk), full(other)) == 0 { Chunk::Zeros } else { Chunk::Ones }
+                }
+                _ => {
+                    let (a, b) = (chunk.words(len), other.words(len));
+                    Chunk::from_words(core::array::from_fn(|w| op(a[w], b[w])), len)
+                }
+            };
+            if new != *chunk {
+                *chunk = new;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Add every index in `other`, returning true if `self` changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the domain sizes differ.
+    #[inline]
+    pub fn union(&mut self, other: &Self) -> bool {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// Remove every index not in `other`, returning true if `self` changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the domain sizes differ.
+    #[inline]
+    pub fn intersect(&mut self, other: &Self) -> bool {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// Remove every index in `other`, returning true if `self` changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the domain sizes differ.
+    #[inline]
+    pub fn difference(&mut self, other: &Self) -> bool {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    /// The bits of every chunk, as if stored in one flat bitset.
+    fn words(&self) -> impl Iterator<Item = usize> + '_ {
+        self.chunks.iter().enumerate().flat_map(|(c, chunk)| chunk.words(self.chunk_len(c)))
+    }
+
+    /// Iterate over the indices present, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = I> + '_ {
+        self.chunks.iter().enumerate().filter(|(_, chunk)| **chunk != Chunk::Zeros).flat_map(
+            |(c, chunk)| {
+                let words = chunk.words(self.chunk_len(c));
+                set_bits(words).map(move |bit| I::from_usize(c * CHUNK_BITS + bit))
+            },
+        )
+    }
+}
+
+impl<I: Idx> Extend<I> for ChunkedIndexBitSet<I> {
+    /// Insert every index yielded by `iter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is outside the domain.
+    #[inline]
+    fn extend<J: IntoIterator<Item = I>>(&mut self, iter: J) {
+        for idx in iter {
+            self.insert(idx);
+        }
+    }
+}
+
+impl<I: Idx> FromIterator<I> for ChunkedIndexBitSet<I> {
+    /// Collect indices into a set whose domain ends just past the largest one.
+    fn from_iter<J: IntoIterator<Item = I>>(iter: J) -> Self {
+        let indices: Vec<I> = iter.into_iter().collect();
+        let domain_size = indices.iter().map(|i| i.index() + 1).max().unwrap_or(0);
+        let mut set = Self::new(domain_size);
+        set.extend(indices);
+        set
+    }
+}
+
+impl<I: Idx> fmt::Debug for ChunkedIndexBitSet<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<I: Idx> core::hash::Hash for ChunkedIndexBitSet<I> {
+    /// Hashes like an [`IndexBitSet`](crate::IndexBitSet) with the same
+    /// domain and indices, whether or not a chunk is compacted.
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        hash_bits(self.domain_size, self.words(), state);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<I: Idx> crate::serde::ser::Serialize for ChunkedIndexBitSet<I> {
+    fn serialize<S: crate::serde::ser::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        crate::serde_bits::serialize(self.domain_size, self.words(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I: Idx> crate::serde::de::Deserialize<'de> for ChunkedIndexBitSet<I> {
+    fn deserialize<D: crate::serde::de::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let (domain_size, words) = crate::serde_bits::deserialize(deserializer)?;
+        let mut set = Self::new(domain_size);
+        for (c, chunk) in set.chunks.iter_mut().enumerate() {
+            let words = &words[c * CHUNK_WORDS..words.len().min((c + 1) * CHUNK_WORDS)];
+            let len = (domain_size - c * CHUNK_BITS).min(CHUNK_BITS);
+            let mut chunk_words = [0; CHUNK_WORDS];
+            chunk_words[..words.len()].copy_from_slice(words);
+            *chunk = Chunk::from_words(chunk_words, len);
+        }
+        Ok(set)
+    }
+}
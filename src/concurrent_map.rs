@@ -0,0 +1,114 @@
+use std::hash::RandomState;
+
+use dashmap::{DashMap, mapref::one::Ref};
+
+use crate::Idx;
+
+/// A sparse map keyed by an index type, which can be read and written
+/// concurrently through a shared reference.
+///
+/// This is a thin wrapper around a sharded [`DashMap`], intended for
+/// memoization caches populated from rayon workers. Requires the `dashmap`
+/// feature.
+///
+/// This doesn't use [`IdxHasher`](crate::IdxHasher): `DashMap` picks the
+/// shard from the high bits of the hash, which identity hashing leaves zero
+/// for any realistic index, so every entry would land in the same shard.
+///
+/// ```rust
+/// use oxc_index::ConcurrentIndexMap;
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let cache = ConcurrentIndexMap::<NodeIdx, u64>::new();
+/// assert_eq!(*cache.get_or_insert_with(NodeIdx::new(3), || 9), 9);
+/// assert_eq!(*cache.get_or_insert_with(NodeIdx::new(3), || unreachable!()), 9);
+/// assert_eq!(cache.get(NodeIdx::new(3)).as_deref(), Some(&9));
+/// ```
+pub struct ConcurrentIndexMap<I: Idx, V> {
+    map: DashMap<I, V, RandomState>,
+}
+
+impl<I: Idx, V> ConcurrentIndexMap<I, V> {
+    /// Construct an empty map.
+    #[inline]
+    pub fn new() -> Self {
+        Self { map: DashMap::with_hasher(RandomState::new()) }
+    }
+
+    /// Construct an empty map with room for at least `capacity` entries.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { map: DashMap::with_capacity_and_hasher(capacity, RandomState::new()) }
+    }
+
+    /// Get a reference to the value at `idx`, if present.
+    ///
+    /// The returned guard holds a read lock on the entry's shard, so don't hold
+    /// onto it while writing to the same map.
+    #[inline]
+    pub fn get(&self, idx: I) -> Option<Ref<'_, I, V>> {
+        self.map.get(&idx)
+    }
+
+    /// Returns true if `idx` has a value.
+    #[inline]
+    pub fn contains(&self, idx: I) -> bool {
+        self.map.contains_key(&idx)
+    }
+
+    /// Insert `value` at `idx`, returning the previous value if any.
+    #[inline]
+    pub fn insert(&self, idx: I, value: V) -> Option<V> {
+        self.map.insert(idx, value)
+    }
+
+    /// Remove the value at `idx`, returning it if present.
+    #[inline]
+    pub fn remove(&self, idx: I) -> Option<V> {
+        self.map.remove(&idx).map(|(_, v)| v)
+    }
+
+    /// Get the value at `idx`, computing and inserting it with `f` first if
+    /// needed.
+    ///
+    /// `f` runs while holding a write lock on the entry's shard, so it must not
+    /// access this map.
+    #[inline]
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&self, idx: I, f: F) -> Ref<'_, I, V> {
+        self.map.entry(idx).or_insert_with(f).downgrade()
+    }
+
+    /// The number of entries in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the map has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Unwrap the underlying `DashMap`.
+    #[inline]
+    pub fn into_inner(self) -> DashMap<I, V, RandomState> {
+        self.map
+    }
+}
+
+impl<I: Idx, V> Default for ConcurrentIndexMap<I, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, V: core::fmt::Debug> core::fmt::Debug for ConcurrentIndexMap<I, V> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.map, fmt)
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    boxed::Box,
+    fmt,
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicUsize, Ordering},
+    },
+    vec::Vec,
+};
+
+use crate::{Idx, IndexVec};
+
+/// The values pushed to one shard, along with their indices.
+type Shard<I, T> = Mutex<Vec<(I, T)>>;
+
+/// An append-only vector which many threads can push to at once, each push
+/// getting a unique index from the same domain.
+///
+/// Indices come from a shared atomic counter, and values are stored in
+/// mutex-guarded shards chosen by index, so pushes from different threads
+/// rarely contend. Elements can't be read back until the vector is frozen
+/// into an [`IndexVec`] with [`into_index_vec`](Self::into_index_vec).
+/// Requires the `std` feature.
+///
+/// ```rust
+/// use oxc_index::ConcurrentIndexVec;
+///
+/// oxc_index::define_index_type! {
+///     pub struct FileIdx = u32;
+/// }
+///
+/// let files = ConcurrentIndexVec::<FileIdx, String>::new();
+/// std::thread::scope(|s| {
+///     for t in 0..4 {
+///         let files = &files;
+///         s.spawn(move || {
+///             for i in 0..10 {
+///                 files.push(format!("{t}-{i}"));
+///             }
+///         });
+///     }
+/// });
+/// let files = files.into_index_vec();
+/// assert_eq!(files.len(), 40);
+/// ```
+pub struct ConcurrentIndexVec<I: Idx, T> {
+    next: AtomicUsize,
+    shards: Box<[Shard<I, T>]>,
+}
+
+impl<I: Idx, T> ConcurrentIndexVec<I, T> {
+    /// Construct an empty vector, with a shard count based on the available
+    /// parallelism.
+    #[inline]
+    pub fn new() -> Self {
+        let threads = std::thread::available_parallelism().map_or(1, usize::from);
+        Self::with_shards(threads * 4)
+    }
+
+    /// Construct an empty vector with `shards` shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    #[inline]
+    pub fn with_shards(shards: usize) -> Self {
+        assert!(shards > 0, "`ConcurrentIndexVec` needs at least one shard");
+        Self {
+            next: AtomicUsize::new(0),
+            shards: (0..shards).map(|_| Mutex::new(Vec::new())).collect(),
+        }
+    }
+
+    /// Returns the number of indices handed out so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.next.load(Ordering::Relaxed)
+    }
+
+    /// Returns true if nothing has been pushed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Push a new item, and return its index. No other push, on this thread or
+    /// any other, gets the same index.
+    pub fn push(&self, value: T) -> I {
+        let idx = I::from_usize(self.next.fetch_add(1, Ordering::Relaxed));
+        let shard = &self.shards[idx.index() % self.shards.len()];
+        // Pushing onto a `Vec` can't leave it in a bad state, so ignore
+        // poisoning from a panic on another thread.
+        shard.lock().unwrap_or_else(PoisonError::into_inner).push((idx, value));
+        idx
+    }
+
+    /// Freeze the vector into an `IndexVec`, with every value at the index its
+    /// push returned.
+    pub fn into_index_vec(self) -> IndexVec<I, T> {
+        let shards = self.shards.into_vec().into_iter();
+        let shards: Vec<_> = shards
+            .map(|shard| shard.into_inner().unwrap_or_else(PoisonError::into_inner))
+            .collect();
+        let mut slots: Vec<Option<T>> =
+            (0..shards.iter().map(Vec::len).sum::<usize>()).map(|_| None).collect();
+        for (idx, value) in shards.into_iter().flatten() {
+            slots[idx.index()] = Some(value);
+        }
+        slots.into_iter().map(|value| value.expect("every index was pushed")).collect()
+    }
+}
+
+impl<I: Idx, T> Default for ConcurrentIndexVec<I, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, T> fmt::Debug for ConcurrentIndexVec<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ConcurrentIndexVec").field("len", &self.len()).finish_non_exhaustive()
+    }
+}
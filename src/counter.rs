@@ -0,0 +1,117 @@
+use core::{fmt, iter, marker::PhantomData, ops::Range};
+
+use crate::{Idx, Len};
+
+/// A counter which mints fresh indices, for when there's no data to store
+/// alongside them.
+///
+/// This is what an `IndexVec<I, ()>` would be used for, without pretending to
+/// own a vector: [`IndexCounter::push`] just bumps a counter and returns the
+/// next `I`. Side tables keyed by the minted indices can be sized with
+/// [`IndexCounter::len_token`].
+///
+/// ```rust
+/// use oxc_index::{IndexCounter, IndexVec};
+///
+/// oxc_index::define_index_type! {
+///     pub struct SymbolId = u32;
+/// }
+///
+/// let mut ids = IndexCounter::<SymbolId>::new();
+/// let a = ids.push();
+/// let b = ids.push();
+/// assert_eq!((a.index(), b.index()), (0, 1));
+///
+/// let names = IndexVec::<SymbolId, String>::new_for(ids.len_token());
+/// assert_eq!(names.len(), 2);
+/// ```
+pub struct IndexCounter<I: Idx> {
+    len: usize,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx> IndexCounter<I> {
+    /// Construct a counter which hasn't minted any indices.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_len(0)
+    }
+
+    /// Construct a counter as if `len` indices were already minted.
+    #[inline]
+    pub const fn with_len(len: usize) -> Self {
+        Self { len, _marker: PhantomData }
+    }
+
+    /// Returns the number of indices minted so far.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no indices were minted yet.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gives the index the next call to [`IndexCounter::push`] will return.
+    #[inline]
+    pub fn next_idx(&self) -> I {
+        I::from_usize(self.len)
+    }
+
+    /// Mint a fresh index.
+    #[inline]
+    pub fn push(&mut self) -> I {
+        let idx = self.next_idx();
+        self.len += 1;
+        idx
+    }
+
+    /// Mint `n` fresh, consecutive indices, returning their range.
+    #[inline]
+    pub fn push_n(&mut self, n: usize) -> Range<I> {
+        let start = self.next_idx();
+        self.len += n;
+        start..self.next_idx()
+    }
+
+    /// Returns true if `idx` was already minted.
+    #[inline]
+    pub fn contains(&self, idx: I) -> bool {
+        idx.index() < self.len
+    }
+
+    /// Get an iterator over every index minted so far.
+    #[inline]
+    pub fn indices(&self) -> iter::Map<Range<usize>, fn(usize) -> I> {
+        (0..self.len).map(I::from_usize)
+    }
+
+    /// Mint a [`Len`] token for the indices minted so far.
+    #[inline]
+    pub const fn len_token(&self) -> Len<I> {
+        Len::new(self.len)
+    }
+}
+
+impl<I: Idx> Clone for IndexCounter<I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::with_len(self.len)
+    }
+}
+
+impl<I: Idx> Default for IndexCounter<I> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx> fmt::Debug for IndexCounter<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "IndexCounter({})", self.len)
+    }
+}
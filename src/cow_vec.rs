@@ -0,0 +1,148 @@
+use alloc::sync::Arc;
+use core::{fmt, hash::Hash};
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// A copy-on-write [`IndexVec`], which is cheap to clone and can be shared
+/// between threads.
+///
+/// Clones share the same `Arc`'d table, so snapshotting a large per-node
+/// table between edits costs a reference count bump. Reads go through
+/// [`IndexSlice`] as usual; [`make_mut`](CowIndexVec::make_mut) gives
+/// mutable access, cloning the table first only if it's shared.
+///
+/// ```rust
+/// use oxc_index::{CowIndexVec, index_vec};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let mut current = CowIndexVec::<NodeIdx, u32>::new(index_vec![1, 2]);
+/// let snapshot = current.clone();
+/// assert!(CowIndexVec::ptr_eq(&current, &snapshot));
+///
+/// current.make_mut().push(3);
+/// assert!(!CowIndexVec::ptr_eq(&current, &snapshot));
+/// assert_eq!(snapshot.len(), 2);
+/// assert_eq!(current[NodeIdx::new(2)], 3);
+/// ```
+pub struct CowIndexVec<I: Idx, T> {
+    raw: Arc<IndexVec<I, T>>,
+}
+
+impl<I: Idx, T> CowIndexVec<I, T> {
+    /// Construct a `CowIndexVec` owning `vec`.
+    #[inline]
+    pub fn new(vec: IndexVec<I, T>) -> Self {
+        Self { raw: Arc::new(vec) }
+    }
+
+    /// Get the shared table.
+    #[inline]
+    pub fn as_index_vec(&self) -> &IndexVec<I, T> {
+        &self.raw
+    }
+
+    /// Get mutable access to the table, first cloning it if any other
+    /// `CowIndexVec` shares it. See [`Arc::make_mut`].
+    #[inline]
+    pub fn make_mut(&mut self) -> &mut IndexVec<I, T>
+    where
+        T: Clone,
+    {
+        Arc::make_mut(&mut self.raw)
+    }
+
+    /// Get mutable access to the table if no other `CowIndexVec` shares it.
+    /// See [`Arc::get_mut`].
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut IndexVec<I, T>> {
+        Arc::get_mut(&mut self.raw)
+    }
+
+    /// Returns true if `a` and `b` share the same table.
+    #[inline]
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.raw, &b.raw)
+    }
+
+    /// Unwrap the table, cloning it if it's shared.
+    #[inline]
+    pub fn into_index_vec(self) -> IndexVec<I, T>
+    where
+        T: Clone,
+    {
+        Arc::unwrap_or_clone(self.raw)
+    }
+}
+
+impl<I: Idx, T> core::ops::Deref for CowIndexVec<I, T> {
+    type Target = IndexSlice<I, [T]>;
+
+    #[inline]
+    fn deref(&self) -> &IndexSlice<I, [T]> {
+        self.raw.as_slice()
+    }
+}
+
+impl<I: Idx, T> Clone for CowIndexVec<I, T> {
+    /// Share the table with a new `CowIndexVec`, without cloning it.
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { raw: Arc::clone(&self.raw) }
+    }
+}
+
+impl<I: Idx, T> Default for CowIndexVec<I, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(IndexVec::new())
+    }
+}
+
+impl<I: Idx, T> From<IndexVec<I, T>> for CowIndexVec<I, T> {
+    #[inline]
+    fn from(vec: IndexVec<I, T>) -> Self {
+        Self::new(vec)
+    }
+}
+
+impl<I: Idx, T> FromIterator<T> for CowIndexVec<I, T> {
+    #[inline]
+    fn from_iter<J: IntoIterator<Item = T>>(iter: J) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<I: Idx, T: PartialEq> PartialEq for CowIndexVec<I, T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Self::ptr_eq(self, other) || self.raw == other.raw
+    }
+}
+
+impl<I: Idx, T: Eq> Eq for CowIndexVec<I, T> {}
+
+impl<I: Idx, T: Hash> Hash for CowIndexVec<I, T> {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.raw.hash(state);
+    }
+}
+
+impl<'a, I: Idx, T> IntoIterator for &'a CowIndexVec<I, T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter()
+    }
+}
+
+impl<I: Idx, T: fmt::Debug> fmt::Debug for CowIndexVec<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.raw.raw, fmt)
+    }
+}
@@ -0,0 +1,244 @@
+use alloc::collections::{VecDeque, vec_deque};
+use core::{fmt, marker::PhantomData};
+
+use crate::Idx;
+
+/// A `VecDeque` that only accepts indices of a specific type.
+///
+/// Like [`IndexVec`](crate::IndexVec), this is a thin wrapper whose backing
+/// `VecDeque` is public (as `raw`). Indices count from the front, so pushing
+/// to or popping from the front shifts the index of every other element,
+/// exactly as with `VecDeque`'s own `usize` indices.
+///
+/// ```rust
+/// use oxc_index::IndexVecDeque;
+///
+/// oxc_index::define_index_type! {
+///     pub struct SlotIdx = u32;
+/// }
+///
+/// let mut queue = IndexVecDeque::<SlotIdx, &str>::new();
+/// let a = queue.push_back("a");
+/// let b = queue.push_back("b");
+/// assert_eq!((a, b), (SlotIdx::new(0), SlotIdx::new(1)));
+/// assert_eq!(queue[b], "b");
+/// assert_eq!(queue.pop_front(), Some("a"));
+/// assert_eq!(queue[SlotIdx::new(0)], "b");
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IndexVecDeque<I: Idx, T> {
+    /// Our wrapped `VecDeque`.
+    pub raw: VecDeque<T>,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx, T> IndexVecDeque<I, T> {
+    /// Construct an empty `IndexVecDeque`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { raw: VecDeque::new(), _marker: PhantomData }
+    }
+
+    /// Construct an empty `IndexVecDeque` with room for `capacity` elements.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { raw: VecDeque::with_capacity(capacity), _marker: PhantomData }
+    }
+
+    /// Construct an `IndexVecDeque` from a `VecDeque<T>`.
+    ///
+    /// Panics if its length is too large for our index type.
+    #[inline]
+    pub fn from_deque(deque: VecDeque<T>) -> Self {
+        // See if `I::from_usize` might be upset by this length.
+        let _ = I::from_usize(deque.len());
+        Self { raw: deque, _marker: PhantomData }
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.raw.len()
+    }
+
+    /// Returns true if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+
+    /// Gives the index the next `push_back` will return.
+    #[inline]
+    pub fn next_idx(&self) -> I {
+        I::from_usize(self.len())
+    }
+
+    /// Push an element onto the back, returning its index.
+    #[inline]
+    pub fn push_back(&mut self, value: T) -> I {
+        let idx = self.next_idx();
+        self.raw.push_back(value);
+        idx
+    }
+
+    /// Push an element onto the front, which shifts the index of every other
+    /// element up by one.
+    #[inline]
+    pub fn push_front(&mut self, value: T) {
+        let _ = self.next_idx();
+        self.raw.push_front(value);
+    }
+
+    /// Remove the element at the front, which shifts the index of every other
+    /// element down by one.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.raw.pop_front()
+    }
+
+    /// Remove the element at the back.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.raw.pop_back()
+    }
+
+    /// Get the element at the front.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.raw.front()
+    }
+
+    /// Get the element at the back.
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        self.raw.back()
+    }
+
+    /// Get the element at `idx`, or `None` if `idx` is out of bounds.
+    #[inline]
+    pub fn get(&self, idx: I) -> Option<&T> {
+        self.raw.get(idx.index())
+    }
+
+    /// Get the element at `idx` mutably, or `None` if `idx` is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, idx: I) -> Option<&mut T> {
+        self.raw.get_mut(idx.index())
+    }
+
+    /// Remove every element.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.raw.clear();
+    }
+
+    /// Iterate over the elements, front to back.
+    #[inline]
+    pub fn iter(&self) -> vec_deque::Iter<'_, T> {
+        self.raw.iter()
+    }
+
+    /// Iterate mutably over the elements, front to back.
+    #[inline]
+    pub fn iter_mut(&mut self) -> vec_deque::IterMut<'_, T> {
+        self.raw.iter_mut()
+    }
+
+    /// Iterate over the elements along with their indices, front to back.
+    #[inline]
+    pub fn iter_enumerated(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (I, &T)> + ExactSizeIterator + '_ {
+        self.raw.iter().enumerate().map(|(i, t)| (I::from_usize(i), t))
+    }
+
+    /// Iterate mutably over the elements along with their indices, front to
+    /// back.
+    #[inline]
+    pub fn iter_mut_enumerated(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = (I, &mut T)> + ExactSizeIterator + '_ {
+        self.raw.iter_mut().enumerate().map(|(i, t)| (I::from_usize(i), t))
+    }
+
+    /// Iterate over the indices, front to back.
+    #[inline]
+    pub fn indices(&self) -> impl DoubleEndedIterator<Item = I> + ExactSizeIterator + use<I, T> {
+        (0..self.len()).map(I::from_usize)
+    }
+}
+
+impl<I: Idx, T> Default for IndexVecDeque<I, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, T> core::ops::Index<I> for IndexVecDeque<I, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, idx: I) -> &T {
+        &self.raw[idx.index()]
+    }
+}
+
+impl<I: Idx, T> core::ops::IndexMut<I> for IndexVecDeque<I, T> {
+    #[inline]
+    fn index_mut(&mut self, idx: I) -> &mut T {
+        &mut self.raw[idx.index()]
+    }
+}
+
+impl<I: Idx, T> Extend<T> for IndexVecDeque<I, T> {
+    /// Push every element yielded by `iter` onto the back.
+    #[inline]
+    fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
+        self.raw.extend(iter);
+        let _ = self.next_idx();
+    }
+}
+
+impl<I: Idx, T> FromIterator<T> for IndexVecDeque<I, T> {
+    #[inline]
+    fn from_iter<J: IntoIterator<Item = T>>(iter: J) -> Self {
+        Self::from_deque(iter.into_iter().collect())
+    }
+}
+
+impl<I: Idx, T> IntoIterator for IndexVecDeque<I, T> {
+    type Item = T;
+    type IntoIter = vec_deque::IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> vec_deque::IntoIter<T> {
+        self.raw.into_iter()
+    }
+}
+
+impl<'a, I: Idx, T> IntoIterator for &'a IndexVecDeque<I, T> {
+    type Item = &'a T;
+    type IntoIter = vec_deque::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> vec_deque::Iter<'a, T> {
+        self.raw.iter()
+    }
+}
+
+impl<'a, I: Idx, T> IntoIterator for &'a mut IndexVecDeque<I, T> {
+    type Item = &'a mut T;
+    type IntoIter = vec_deque::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> vec_deque::IterMut<'a, T> {
+        self.raw.iter_mut()
+    }
+}
+
+impl<I: Idx, T: fmt::Debug> fmt::Debug for IndexVecDeque<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.raw, fmt)
+    }
+}
@@ -0,0 +1,116 @@
+use alloc::vec::Vec;
+use core::{fmt, ops::Range};
+
+use crate::{Idx, IndexSlice};
+
+/// A summary of the differences between two tables keyed by the same index
+/// type, as produced by [`IndexSlice::diff`].
+///
+/// Indices present in both tables which hold different values are listed in
+/// `changed`. Whatever exists only in the new table is described by
+/// `appended`, and whatever exists only in the old one by `truncated`. At most
+/// one of those two ranges is non-empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexDiff<I: Idx> {
+    /// Indices present in both tables whose values differ, in ascending order.
+    pub changed: Vec<I>,
+    /// Indices only present in the new table.
+    pub appended: Range<I>,
+    /// Indices only present in the old table.
+    pub truncated: Range<I>,
+}
+
+impl<I: Idx> IndexDiff<I> {
+    /// Returns true if the two tables were equal.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.appended.is_empty() && self.truncated.is_empty()
+    }
+}
+
+impl<I: Idx, T> IndexSlice<I, [T]> {
+    /// Compare `self` (the old table) against `new`, and summarize which
+    /// indices changed, were appended, or were truncated.
+    ///
+    /// This is meant for incremental pipelines, which can use the result to
+    /// redo only the downstream work affected by a rebuilt table.
+    pub fn diff<U>(&self, new: &IndexSlice<I, [U]>) -> IndexDiff<I>
+    where
+        T: PartialEq<U>,
+    {
+        let common = self.len().min(new.len());
+        let changed = self.raw[..common]
+            .iter()
+            .zip(&new.raw[..common])
+            .enumerate()
+            .filter(|(_, (a, b))| *a != *b)
+            .map(|(i, _)| I::from_usize(i))
+            .collect();
+        let common = I::from_usize(common);
+        IndexDiff {
+            changed,
+            appended: common..new.len_idx().max(common),
+            truncated: common..self.len_idx().max(common),
+        }
+    }
+}
+
+/// The entries which differ between two tables, formatted for a test failure.
+///
+/// Produced by [`IndexSlice::diff_debug`], and used by
+/// [`assert_index_vec_eq!`](crate::assert_index_vec_eq). Its `Display` impl
+/// lists only the differing entries, with their typed indices, instead of
+/// dumping both tables in full.
+pub struct DiffDebug<'a, I: Idx, T> {
+    left: &'a IndexSlice<I, [T]>,
+    right: &'a IndexSlice<I, [T]>,
+}
+
+impl<I: Idx, T: PartialEq> DiffDebug<'_, I, T> {
+    /// The maximum number of differing entries printed.
+    pub const MAX_ENTRIES: usize = 32;
+
+    /// Returns true if the two tables were equal.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.left.raw == self.right.raw
+    }
+}
+
+impl<I: Idx, T: PartialEq + fmt::Debug> fmt::Display for DiffDebug<'_, I, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (left, right) = (&self.left.raw, &self.right.raw);
+        if left.len() != right.len() {
+            writeln!(f, "lengths differ: left has {}, right has {}", left.len(), right.len())?;
+        }
+        let mut differing = (0..left.len().max(right.len()))
+            .filter(|&i| left.get(i) != right.get(i))
+            .map(I::from_usize);
+        for idx in differing.by_ref().take(Self::MAX_ENTRIES) {
+            let i = idx.index();
+            write!(f, "  [{idx:?}] ")?;
+            match left.get(i) {
+                Some(l) => write!(f, "left: {l:?}")?,
+                None => write!(f, "left: <missing>")?,
+            }
+            match right.get(i) {
+                Some(r) => writeln!(f, ", right: {r:?}")?,
+                None => writeln!(f, ", right: <missing>")?,
+            }
+        }
+        let rest = differing.count();
+        if rest > 0 {
+            writeln!(f, "  ... and {rest} more")?;
+        }
+        Ok(())
+    }
+}
+
+impl<I: Idx, T> IndexSlice<I, [T]> {
+    /// Compare against `other`, producing a [`DiffDebug`] which prints only
+    /// the differing entries with their indices.
+    #[inline]
+    pub fn diff_debug<'a>(&'a self, other: &'a Self) -> DiffDebug<'a, I, T> {
+        DiffDebug { left: self, right: other }
+    }
+}
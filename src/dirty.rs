@@ -0,0 +1,170 @@
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use crate::{
+    Idx, IndexSlice, IndexVec,
+    bitset::{WORD_BITS, set_bits},
+};
+
+/// An [`IndexVec`] wrapper which records which indices were written since the
+/// last call to [`DirtyIndexVec::take_dirty`].
+///
+/// This lets incremental passes re-process only the entries which changed.
+/// The dirty set is a bitset, so marking is cheap and each index is reported
+/// at most once, in ascending order.
+///
+/// Read access is available through `Deref` to [`IndexSlice`]. Mutable access
+/// only goes through the methods on this type, so that no write goes
+/// unrecorded. Compare [`WatchedIndexVec`](crate::WatchedIndexVec), which
+/// reports every write to a callback as it happens.
+///
+/// ```rust
+/// use oxc_index::{DirtyIndexVec, index_vec};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let mut nodes = DirtyIndexVec::<NodeIdx, u32>::new(index_vec![1, 2, 3]);
+/// nodes.set(NodeIdx::new(2), 30);
+/// *nodes.get_mut(NodeIdx::new(0)).unwrap() += 10;
+/// nodes.set(NodeIdx::new(2), 300);
+/// assert_eq!(nodes.take_dirty(), [NodeIdx::new(0), NodeIdx::new(2)]);
+/// assert!(nodes.take_dirty().is_empty());
+/// ```
+#[derive(Clone)]
+pub struct DirtyIndexVec<I: Idx, T> {
+    vec: IndexVec<I, T>,
+    dirty: Vec<usize>,
+}
+
+impl<I: Idx, T> DirtyIndexVec<I, T> {
+    /// Wrap `vec`, with nothing marked dirty.
+    #[inline]
+    pub fn new(vec: IndexVec<I, T>) -> Self {
+        Self { vec, dirty: Vec::new() }
+    }
+
+    /// Unwrap the underlying vector, discarding the dirty set.
+    #[inline]
+    pub fn into_inner(self) -> IndexVec<I, T> {
+        self.vec
+    }
+
+    /// Get the underlying vector.
+    #[inline(always)]
+    pub fn as_index_vec(&self) -> &IndexVec<I, T> {
+        &self.vec
+    }
+
+    #[inline]
+    fn mark(&mut self, idx: I) {
+        let idx = idx.index();
+        let word = idx / WORD_BITS;
+        if word >= self.dirty.len() {
+            self.dirty.resize(word + 1, 0);
+        }
+        self.dirty[word] |= 1 << (idx % WORD_BITS);
+    }
+
+    /// Mark `idx` as dirty without writing to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    #[inline]
+    pub fn mark_dirty(&mut self, idx: I) {
+        assert!(idx.index() < self.vec.len(), "index {} out of bounds", idx.index());
+        self.mark(idx);
+    }
+
+    /// Returns true if `idx` was written since the last
+    /// [`DirtyIndexVec::take_dirty`].
+    #[inline]
+    pub fn is_dirty(&self, idx: I) -> bool {
+        let idx = idx.index();
+        self.dirty.get(idx / WORD_BITS).is_some_and(|w| w & (1 << (idx % WORD_BITS)) != 0)
+    }
+
+    /// Returns true if any index was written since the last
+    /// [`DirtyIndexVec::take_dirty`].
+    #[inline]
+    pub fn has_dirty(&self) -> bool {
+        self.dirty.iter().any(|&w| w != 0)
+    }
+
+    /// Return the indices written since the last call, in ascending order, and
+    /// clear the dirty set.
+    pub fn take_dirty(&mut self) -> Vec<I> {
+        let out = set_bits(self.dirty.iter().copied()).map(I::from_usize).collect();
+        self.dirty.clear();
+        out
+    }
+
+    /// Push a new item onto the vector, mark it dirty, and return its index.
+    #[inline]
+    pub fn push(&mut self, value: T) -> I {
+        let idx = self.vec.push(value);
+        self.mark(idx);
+        idx
+    }
+
+    /// Remove the last item, if any. Its index is no longer reported as dirty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.vec.pop()?;
+        let idx = self.vec.len();
+        if let Some(w) = self.dirty.get_mut(idx / WORD_BITS) {
+            *w &= !(1 << (idx % WORD_BITS));
+        }
+        Some(value)
+    }
+
+    /// Overwrite the item at `index`, mark it dirty, and return the previous
+    /// value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn set(&mut self, index: I, value: T) -> T {
+        let old = core::mem::replace(&mut self.vec[index], value);
+        self.mark(index);
+        old
+    }
+
+    /// Get a mut ref to the item at `index`, marking it dirty, or `None` if
+    /// out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, index: I) -> Option<&mut T> {
+        if index.index() >= self.vec.len() {
+            return None;
+        }
+        self.mark(index);
+        Some(&mut self.vec[index])
+    }
+
+    /// Extend the vector with the contents of an iterator, marking every new
+    /// element dirty.
+    #[inline]
+    pub fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<I: Idx, T> Deref for DirtyIndexVec<I, T> {
+    type Target = IndexSlice<I, [T]>;
+
+    #[inline]
+    fn deref(&self) -> &IndexSlice<I, [T]> {
+        self.vec.as_slice()
+    }
+}
+
+impl<I: Idx, T: core::fmt::Debug> core::fmt::Debug for DirtyIndexVec<I, T> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.vec, fmt)
+    }
+}
@@ -0,0 +1,193 @@
+use alloc::vec::Vec;
+use core::{
+    cell::{Cell, UnsafeCell},
+    fmt,
+    marker::PhantomData,
+};
+
+use crate::{Idx, IndexVec};
+
+/// The capacity of the first chunk. Each chunk after it is twice as big as
+/// the one before.
+const FIRST_CHUNK: usize = 16;
+
+/// An append-only vector that can be pushed to through a shared reference,
+/// which only accepts indices of a specific type.
+///
+/// Elements are stored in chunks that are never reallocated, so references
+/// handed out by [`get`](Self::get) stay valid while more elements are
+/// pushed. This lets a builder keep borrowing what it has already built,
+/// which a plain `IndexVec` won't allow. Elements can't be removed or
+/// (without `&mut self`) modified.
+///
+/// ```rust
+/// use oxc_index::FrozenIndexVec;
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let nodes = FrozenIndexVec::<NodeIdx, String>::new();
+/// let root = nodes.push("root".to_string());
+/// let root_name: &str = &nodes[root];
+/// let child = nodes.push(format!("{root_name}.child"));
+/// assert_eq!(root_name, "root");
+/// assert_eq!(nodes[child], "root.child");
+/// ```
+pub struct FrozenIndexVec<I: Idx, T> {
+    chunks: UnsafeCell<Vec<Vec<T>>>,
+    len: Cell<usize>,
+    _marker: PhantomData<fn(&I)>,
+}
+
+/// Returns the chunk holding element `i`, and the offset of `i` within it.
+#[inline]
+fn locate(i: usize) -> (usize, usize) {
+    let chunk = (i / FIRST_CHUNK + 1).ilog2() as usize;
+    (chunk, i - FIRST_CHUNK * ((1 << chunk) - 1))
+}
+
+impl<I: Idx, T> FrozenIndexVec<I, T> {
+    /// Construct an empty `FrozenIndexVec`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { chunks: UnsafeCell::new(Vec::new()), len: Cell::new(0), _marker: PhantomData }
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    /// Returns true if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gives the next index that will be assigned when `push` is called.
+    #[inline]
+    pub fn next_idx(&self) -> I {
+        I::from_usize(self.len())
+    }
+
+    /// Push a new item onto the vector, and return its index. References to
+    /// earlier items stay valid.
+    pub fn push(&self, value: T) -> I {
+        let idx = self.next_idx();
+        // SAFETY: we only ever hand out references into the chunks' buffers,
+        // never to `chunks` itself, and a chunk's buffer is never
+        // reallocated: a full chunk is left alone and a new one started. We
+        // don't call out to any code that could reach `self` while this
+        // reference is alive.
+        let chunks = unsafe { &mut *self.chunks.get() };
+        let (chunk, offset) = locate(self.len());
+        if offset == 0 {
+            chunks.push(Vec::with_capacity(FIRST_CHUNK << chunk));
+        }
+        chunks[chunk].push(value);
+        self.len.set(self.len() + 1);
+        idx
+    }
+
+    /// Get a ref to the item at `idx`, or None for out of bounds.
+    #[inline]
+    pub fn get(&self, idx: I) -> Option<&T> {
+        if idx.index() >= self.len() {
+            return None;
+        }
+        let (chunk, offset) = locate(idx.index());
+        // SAFETY: `push` never touches an element once it's been pushed, nor
+        // moves it, so a shared reference to it can live as long as `self`.
+        let chunks = unsafe { &*self.chunks.get() };
+        Some(&chunks[chunk][offset])
+    }
+
+    /// Get a mut ref to the item at `idx`, or None for out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, idx: I) -> Option<&mut T> {
+        if idx.index() >= self.len() {
+            return None;
+        }
+        let (chunk, offset) = locate(idx.index());
+        Some(&mut self.chunks.get_mut()[chunk][offset])
+    }
+
+    /// Iterate over the elements pushed so far.
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + '_ {
+        self.iter_enumerated().map(|(_, t)| t)
+    }
+
+    /// Iterate over the elements pushed so far, along with their indices.
+    #[inline]
+    pub fn iter_enumerated(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (I, &T)> + ExactSizeIterator + '_ {
+        (0..self.len()).map(I::from_usize).map(|idx| (idx, &self[idx]))
+    }
+
+    /// Move the elements into an `IndexVec`.
+    pub fn into_index_vec(self) -> IndexVec<I, T> {
+        let mut raw = Vec::with_capacity(self.len());
+        for chunk in self.chunks.into_inner() {
+            raw.extend(chunk);
+        }
+        IndexVec::from_vec(raw)
+    }
+}
+
+impl<I: Idx, T> core::ops::Index<I> for FrozenIndexVec<I, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, idx: I) -> &T {
+        self.get(idx).unwrap_or_else(|| panic!("no value at index {idx:?}"))
+    }
+}
+
+impl<I: Idx, T> core::ops::IndexMut<I> for FrozenIndexVec<I, T> {
+    #[inline]
+    fn index_mut(&mut self, idx: I) -> &mut T {
+        self.get_mut(idx).unwrap_or_else(|| panic!("no value at index {idx:?}"))
+    }
+}
+
+impl<I: Idx, T> Default for FrozenIndexVec<I, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, T> From<IndexVec<I, T>> for FrozenIndexVec<I, T> {
+    #[inline]
+    fn from(vec: IndexVec<I, T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<I: Idx, T> Extend<T> for FrozenIndexVec<I, T> {
+    #[inline]
+    fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<I: Idx, T> FromIterator<T> for FrozenIndexVec<I, T> {
+    #[inline]
+    fn from_iter<J: IntoIterator<Item = T>>(iter: J) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<I: Idx, T: fmt::Debug> fmt::Debug for FrozenIndexVec<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_list().entries(self.iter()).finish()
+    }
+}
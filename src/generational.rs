@@ -0,0 +1,199 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Idx, IndexVec};
+
+/// A key into a [`GenerationalIndexVec`]: a slot index, along with the
+/// generation the slot was in when the key was handed out.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GenerationalKey<K: Idx> {
+    index: K,
+    generation: u32,
+}
+
+impl<K: Idx> GenerationalKey<K> {
+    /// The slot this key refers to.
+    #[inline]
+    pub fn index(self) -> K {
+        self.index
+    }
+
+    /// The generation of the slot this key was handed out for.
+    #[inline]
+    pub fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+impl<K: Idx> fmt::Debug for GenerationalKey<K> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{:?}v{}", self.index, self.generation)
+    }
+}
+
+#[derive(Clone)]
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+/// A slot map: an `IndexVec` whose elements can be removed, with keys which
+/// detect when the slot they refer to has been reused since.
+///
+/// Every slot carries a generation, which is bumped whenever its value is
+/// removed, and every [`GenerationalKey`] remembers the generation it was
+/// created with. Looking up a stale key finds a mismatched generation and
+/// returns `None`, rather than whatever value has moved into the slot since.
+/// Removed slots are reused by later insertions. Insertion, removal and
+/// lookup are all O(1).
+///
+/// ```rust
+/// use oxc_index::GenerationalIndexVec;
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let mut nodes = GenerationalIndexVec::<NodeIdx, &str>::new();
+/// let a = nodes.insert("a");
+/// assert_eq!(nodes.remove(a), Some("a"));
+///
+/// let b = nodes.insert("b");
+/// assert_eq!(a.index(), b.index()); // the slot got reused...
+/// assert_eq!(nodes.get(a), None); // ...but `a` still can't see into it.
+/// assert_eq!(nodes[b], "b");
+/// ```
+#[derive(Clone)]
+pub struct GenerationalIndexVec<K: Idx, T> {
+    slots: IndexVec<K, Slot<T>>,
+    free: Vec<K>,
+}
+
+impl<K: Idx, T> GenerationalIndexVec<K, T> {
+    /// Construct an empty `GenerationalIndexVec`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { slots: IndexVec::new(), free: Vec::new() }
+    }
+
+    /// Construct an empty `GenerationalIndexVec` with room for `capacity`
+    /// values.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { slots: IndexVec::with_capacity(capacity), free: Vec::new() }
+    }
+
+    /// Returns the number of values.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Returns true if there are no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Insert `value`, reusing a free slot if there is one, and return its key.
+    pub fn insert(&mut self, value: T) -> GenerationalKey<K> {
+        if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index];
+            slot.value = Some(value);
+            GenerationalKey { index, generation: slot.generation }
+        } else {
+            let index = self.slots.push(Slot { generation: 0, value: Some(value) });
+            GenerationalKey { index, generation: 0 }
+        }
+    }
+
+    /// Remove the value for `key`, or return `None` if `key` is stale.
+    pub fn remove(&mut self, key: GenerationalKey<K>) -> Option<T> {
+        let slot = self.slots.get_mut(key.index)?;
+        if slot.generation != key.generation {
+            return None;
+        }
+        let value = slot.value.take()?;
+        // After 2^32 reuses of one slot, a very old key could alias a new
+        // one. That's the usual trade-off for fixed-size generations.
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(key.index);
+        Some(value)
+    }
+
+    /// Returns true if `key` refers to a live value.
+    #[inline]
+    pub fn contains(&self, key: GenerationalKey<K>) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Get the value for `key`, or `None` if `key` is stale.
+    #[inline]
+    pub fn get(&self, key: GenerationalKey<K>) -> Option<&T> {
+        let slot = self.slots.get(key.index)?;
+        if slot.generation == key.generation { slot.value.as_ref() } else { None }
+    }
+
+    /// Get the value for `key` mutably, or `None` if `key` is stale.
+    #[inline]
+    pub fn get_mut(&mut self, key: GenerationalKey<K>) -> Option<&mut T> {
+        let slot = self.slots.get_mut(key.index)?;
+        if slot.generation == key.generation { slot.value.as_mut() } else { None }
+    }
+
+    /// Remove every value. Keys handed out before are stale afterwards.
+    pub fn clear(&mut self) {
+        for (index, slot) in self.slots.iter_mut_enumerated() {
+            if slot.value.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push(index);
+            }
+        }
+    }
+
+    /// Iterate over the live values along with their keys, in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = (GenerationalKey<K>, &T)> + '_ {
+        self.slots.iter_enumerated().filter_map(|(index, slot)| {
+            let key = GenerationalKey { index, generation: slot.generation };
+            slot.value.as_ref().map(|value| (key, value))
+        })
+    }
+
+    /// Iterate mutably over the live values along with their keys, in slot
+    /// order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (GenerationalKey<K>, &mut T)> + '_ {
+        self.slots.iter_mut_enumerated().filter_map(|(index, slot)| {
+            let key = GenerationalKey { index, generation: slot.generation };
+            slot.value.as_mut().map(|value| (key, value))
+        })
+    }
+}
+
+impl<K: Idx, T> Default for GenerationalIndexVec<K, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Idx, T> core::ops::Index<GenerationalKey<K>> for GenerationalIndexVec<K, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, key: GenerationalKey<K>) -> &T {
+        self.get(key).unwrap_or_else(|| panic!("stale key {key:?}"))
+    }
+}
+
+impl<K: Idx, T> core::ops::IndexMut<GenerationalKey<K>> for GenerationalIndexVec<K, T> {
+    #[inline]
+    fn index_mut(&mut self, key: GenerationalKey<K>) -> &mut T {
+        self.get_mut(key).unwrap_or_else(|| panic!("stale key {key:?}"))
+    }
+}
+
+impl<K: Idx, T: fmt::Debug> fmt::Debug for GenerationalIndexVec<K, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,72 @@
+use core::hash::{BuildHasherDefault, Hasher};
+
+/// A [`Hasher`] which passes integer keys through unchanged.
+///
+/// Index types are already small, well distributed integers, so running them
+/// through a general purpose hash function is wasted work. This hasher is meant
+/// for keys produced by [`define_index_type!`](crate::define_index_type), which
+/// hash as a single integer write. Anything else still works, but falls back to
+/// a simple byte-wise mix.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IdxHasher(u64);
+
+impl Hasher for IdxHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = self.0.rotate_left(8) ^ u64::from(b);
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.0 = u64::from(i);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.0 = u64::from(i);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.0 = u64::from(i);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.0 = i;
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.0 = i as u64;
+    }
+}
+
+/// A [`BuildHasher`](core::hash::BuildHasher) producing [`IdxHasher`]s.
+pub type BuildIdxHasher = BuildHasherDefault<IdxHasher>;
+
+/// A `HashMap` keyed by an index type, using identity hashing.
+#[cfg(feature = "std")]
+pub type IdxHashMap<I, V> = std::collections::HashMap<I, V, BuildIdxHasher>;
+
+/// A `HashSet` of an index type, using identity hashing.
+#[cfg(feature = "std")]
+pub type IdxHashSet<I> = std::collections::HashSet<I, BuildIdxHasher>;
+
+/// A `HashMap` keyed by an index type, using `nohash_hasher`'s identity
+/// hasher, which checks (in debug builds) that each key is hashed as a single
+/// integer. Requires the `std` and `nohash-hasher` features.
+#[cfg(all(feature = "std", feature = "nohash-hasher"))]
+pub type IndexHashMap<I, V> = std::collections::HashMap<I, V, nohash_hasher::BuildNoHashHasher<I>>;
+
+/// A `HashSet` of an index type, using `nohash_hasher`'s identity hasher.
+/// Requires the `std` and `nohash-hasher` features.
+#[cfg(all(feature = "std", feature = "nohash-hasher"))]
+pub type IndexHashSet<I> = std::collections::HashSet<I, nohash_hasher::BuildNoHashHasher<I>>;
@@ -51,6 +51,31 @@ impl<I: Idx, T: fmt::Debug + ?Sized> fmt::Debug for IndexSlice<I, T> {
     }
 }
 /// `IndexBox<I, [T]>`: An alias for indexed boxed slice.
+///
+/// This is an owned, fixed-length table: unlike an `IndexVec` it carries no
+/// spare capacity and can't grow, which suits results that are frozen once
+/// computed. Build one with [`IndexVec::into_boxed_slice`] or from a
+/// `Box<[T]>` via `From`, and turn it back into an `IndexVec` with
+/// [`IndexSlice::into_vec`].
+///
+/// ```rust
+/// use oxc_index::{IndexBox, IndexVec};
+///
+/// oxc_index::define_index_type! {
+///     pub struct ScopeIdx = u32;
+/// }
+///
+/// let mut depths = IndexVec::<ScopeIdx, u32>::with_capacity(16);
+/// depths.push(0);
+/// depths.push(1);
+/// let frozen: IndexBox<ScopeIdx, [u32]> = depths.into_boxed_slice();
+/// assert_eq!(frozen[ScopeIdx::new(1)], 1);
+///
+/// let from_raw: IndexBox<ScopeIdx, [u32]> = vec![0, 1].into_boxed_slice().into();
+/// assert_eq!(frozen, from_raw);
+/// let raw: Box<[u32]> = frozen.into();
+/// assert_eq!(&*raw, [0, 1]);
+/// ```
 pub type IndexBox<I, T> = Box<IndexSlice<I, T>>;
 
 type SliceMapped<Iter, I, T> = iter::Map<Iter, fn(&[T]) -> &IndexSlice<I, [T]>>;
@@ -202,6 +227,107 @@ impl<I: Idx, T> IndexSlice<I, [T]> {
         self.raw.iter_mut().enumerate().map(|(i, t)| (I::from_usize(i), t))
     }
 
+    /// Call `f` with each element and its index, producing a new table keyed
+    /// by the same index type.
+    ///
+    /// This is `self.iter_enumerated().map(f).collect()`, without having to
+    /// spell out the type of the result.
+    #[inline]
+    pub fn map_enumerated<U, F: FnMut(I, &T) -> U>(&self, mut f: F) -> IndexVec<I, U> {
+        self.iter_enumerated().map(|(i, t)| f(i, t)).collect()
+    }
+
+    /// Call `f` with each element and its index.
+    #[inline]
+    pub fn for_each_enumerated<F: FnMut(I, &T)>(&self, mut f: F) {
+        self.iter_enumerated().for_each(|(i, t)| f(i, t));
+    }
+
+    /// Call `f` with a mutable reference to each element and its index.
+    #[inline]
+    pub fn for_each_mut_enumerated<F: FnMut(I, &mut T)>(&mut self, mut f: F) {
+        self.iter_mut_enumerated().for_each(|(i, t)| f(i, t));
+    }
+
+    /// Get an iterator over each pair of consecutive elements, along with
+    /// their indices. Yields nothing if there are fewer than two elements.
+    #[inline]
+    pub fn iter_adjacent(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = ((I, &T), (I, &T))> + ExactSizeIterator {
+        self.raw
+            .windows(2)
+            .enumerate()
+            .map(|(i, w)| ((I::from_usize(i), &w[0]), (I::from_usize(i + 1), &w[1])))
+    }
+
+    /// Call `f` with mutable references to each pair of consecutive elements,
+    /// along with their indices, in order.
+    ///
+    /// This is the mutable counterpart of [`IndexSlice::iter_adjacent`]; it
+    /// takes a callback since the pairs overlap.
+    #[inline]
+    pub fn for_each_adjacent_mut<F: FnMut((I, &mut T), (I, &mut T))>(&mut self, mut f: F) {
+        for i in 1..self.raw.len() {
+            let (head, tail) = self.raw.split_at_mut(i);
+            f((I::from_usize(i - 1), &mut head[i - 1]), (I::from_usize(i), &mut tail[0]));
+        }
+    }
+
+    /// Split into consecutive segments, one per key of `lengths`, where
+    /// segment `k` holds the next `lengths[k]` elements. Yields each key
+    /// along with its segment.
+    ///
+    /// This walks a flattened jagged table, such as all the bindings of every
+    /// scope stored back to back, without any manual offset bookkeeping.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lengths don't add up to our length.
+    pub fn iter_segments<'a, K: Idx>(
+        &'a self,
+        lengths: &'a IndexSlice<K, [usize]>,
+    ) -> impl Iterator<Item = (K, &'a Self)> + 'a {
+        self.assert_segment_lengths(lengths);
+        let mut rest = &self.raw;
+        lengths.iter_enumerated().map(move |(k, &n)| {
+            let (head, tail) = rest.split_at(n);
+            rest = tail;
+            (k, Self::new(head))
+        })
+    }
+
+    /// Like [`IndexSlice::iter_segments`], but yields mutable segments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lengths don't add up to our length.
+    pub fn iter_segments_mut<'a, K: Idx>(
+        &'a mut self,
+        lengths: &'a IndexSlice<K, [usize]>,
+    ) -> impl Iterator<Item = (K, &'a mut Self)> + 'a {
+        self.assert_segment_lengths(lengths);
+        let mut rest = &mut self.raw;
+        lengths.iter_enumerated().map(move |(k, &n)| {
+            let (head, tail) = core::mem::take(&mut rest).split_at_mut(n);
+            rest = tail;
+            (k, Self::new_mut(head))
+        })
+    }
+
+    #[inline]
+    #[track_caller]
+    fn assert_segment_lengths<K: Idx>(&self, lengths: &IndexSlice<K, [usize]>) {
+        let total: usize = lengths.iter().sum();
+        assert_eq!(
+            total,
+            self.len(),
+            "segment lengths add up to {}, but the slice has {} elements",
+            total,
+            self.len()
+        );
+    }
+
     /// Forwards to the slice's `sort` implementation.
     #[inline]
     pub fn sort(&mut self)
@@ -229,6 +355,27 @@ impl<I: Idx, T> IndexSlice<I, [T]> {
         self.raw.sort_by_cached_key(f);
     }
 
+    /// Stably sort by a key of some index type `J`, using a counting sort.
+    ///
+    /// This runs in `O(n + k)`, where `k` is the largest key plus one, which
+    /// beats a comparison sort when sorting many elements by small integer IDs.
+    /// For large, sparse keys prefer [`IndexSlice::sort_by_key`].
+    pub fn sort_by_index_key<J: Idx, F: FnMut(&T) -> J>(&mut self, mut f: F) {
+        let keys: Vec<usize> = self.raw.iter().map(|t| f(t).index()).collect();
+        let Some(&max) = keys.iter().max() else { return };
+        let mut starts = vec![0usize; max + 1];
+        for &k in &keys {
+            starts[k] += 1;
+        }
+        let mut total = 0;
+        for start in &mut starts {
+            let count = *start;
+            *start = total;
+            total += count;
+        }
+        permute_by_key(&mut self.raw, &keys, starts);
+    }
+
     /// Forwards to the slice's `sort_unstable` implementation.
     #[inline]
     pub fn sort_unstable(&mut self)
@@ -368,6 +515,78 @@ impl<I: Idx, T> IndexSlice<I, [T]> {
         self.raw.iter().rposition(f).map(I::from_usize)
     }
 
+    /// Reorders the slice in-place so that every element for which `pred`
+    /// returns true comes before every element for which it returns false,
+    /// returning the index where the second group begins.
+    ///
+    /// The relative order of the elements is not preserved.
+    #[inline]
+    pub fn partition_in_place<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> I {
+        let mut split = 0;
+        for i in 0..self.raw.len() {
+            if pred(&self.raw[i]) {
+                self.raw.swap(split, i);
+                split += 1;
+            }
+        }
+        I::from_usize(split)
+    }
+
+    /// Returns true if every element for which `pred` returns true comes
+    /// before every element for which it returns false. See
+    /// [`IndexSlice::partition_in_place`].
+    #[inline]
+    pub fn is_partitioned<F: FnMut(&T) -> bool>(&self, mut pred: F) -> bool {
+        let mut iter = self.raw.iter();
+        iter.all(&mut pred) || !iter.any(pred)
+    }
+
+    /// Returns true if the elements are sorted in ascending order.
+    #[inline]
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.raw.is_sorted()
+    }
+
+    /// Returns true if the elements are sorted by the key `f` extracts.
+    #[inline]
+    pub fn is_sorted_by_key<K: PartialOrd, F: FnMut(&T) -> K>(&self, f: F) -> bool {
+        self.raw.is_sorted_by_key(f)
+    }
+
+    /// Check that the elements are sorted in ascending order, returning the
+    /// index of the first element which is less than the one before it if
+    /// they aren't.
+    #[inline]
+    pub fn check_sorted(&self) -> Result<(), I>
+    where
+        T: PartialOrd,
+    {
+        match self.raw.windows(2).position(|w| !w[0].le(&w[1])) {
+            Some(i) => Err(I::from_usize(i + 1)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that the elements are sorted by the key `f` extracts, returning
+    /// the index of the first element whose key is less than the one before
+    /// it if they aren't.
+    pub fn check_sorted_by_key<K: PartialOrd, F: FnMut(&T) -> K>(&self, mut f: F) -> Result<(), I> {
+        let mut iter = self.raw.iter();
+        let Some(first) = iter.next() else { return Ok(()) };
+        let mut prev = f(first);
+        for (i, x) in iter.enumerate() {
+            let key = f(x);
+            if !prev.le(&key) {
+                return Err(I::from_usize(i + 1));
+            }
+            prev = key;
+        }
+        Ok(())
+    }
+
     /// Swaps two elements in our vector.
     #[inline]
     pub fn swap(&mut self, a: I, b: I) {
@@ -404,6 +623,22 @@ impl<I: Idx, T> IndexSlice<I, [T]> {
         self.raw.rotate_right(k.index());
     }
 
+    /// Rotates our data in-place by `n` positions to the left. Like
+    /// [`IndexSlice::rotate_left`], but takes a plain count rather than an
+    /// index.
+    #[inline]
+    pub fn rotate_left_by(&mut self, n: usize) {
+        self.raw.rotate_left(n);
+    }
+
+    /// Rotates our data in-place by `n` positions to the right. Like
+    /// [`IndexSlice::rotate_right`], but takes a plain count rather than an
+    /// index.
+    #[inline]
+    pub fn rotate_right_by(&mut self, n: usize) {
+        self.raw.rotate_right(n);
+    }
+
     /// Return the the last element, if we are not empty.
     #[inline(always)]
     pub const fn last(&self) -> Option<&T> {
@@ -451,6 +686,44 @@ impl<I: Idx, T> IndexSlice<I, [T]> {
         index.get_mut(self)
     }
 
+    /// Get mut refs to several distinct items at once, or `None` if any index
+    /// is out of bounds or appears more than once.
+    ///
+    /// This replaces the `split_at_mut` dance needed to e.g. merge one entry
+    /// into another.
+    #[inline]
+    pub fn get_many_mut<const N: usize>(&mut self, indices: [I; N]) -> Option<[&mut T; N]> {
+        // `Idx` is a safe trait, so `index()` is only called once per index:
+        // the positions checked are the positions used.
+        let pos = indices.map(|i| i.index());
+        let len = self.len();
+        for (k, &p) in pos.iter().enumerate() {
+            if p >= len || pos[..k].contains(&p) {
+                return None;
+            }
+        }
+        let ptr = self.raw.as_mut_ptr();
+        // SAFETY: every position is in bounds and distinct from the others, so
+        // the references don't alias, and they borrow `self` mutably for their
+        // whole lifetime.
+        Some(pos.map(|p| unsafe { &mut *ptr.add(p) }))
+    }
+
+    /// Get mut refs to several distinct items at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is out of bounds or appears more than once.
+    #[inline]
+    #[track_caller]
+    pub fn index_many_mut<const N: usize>(&mut self, indices: [I; N]) -> [&mut T; N] {
+        let len = self.len();
+        match self.get_many_mut(indices) {
+            Some(refs) => refs,
+            None => panic!("indices {indices:?} are out of bounds for {len} or overlap"),
+        }
+    }
+
     /// Wraps the underlying slice's `windows` iterator with one that yields
     /// `IndexSlice`s with the correct index type.
     #[inline]
@@ -668,6 +941,88 @@ impl<I: Idx, T> IndexSlice<I, [T]> {
     }
 }
 
+/// Stably move each of `values` into the group for its key in `keys`, where
+/// `starts[k]` is the position of the first value with key `k`.
+pub(crate) fn permute_by_key<T>(values: &mut [T], keys: &[usize], mut starts: Vec<usize>) {
+    // `dest[i]` is where the value currently at `i` has to go.
+    let mut dest: Vec<usize> = keys
+        .iter()
+        .map(|&k| {
+            let d = starts[k];
+            starts[k] += 1;
+            d
+        })
+        .collect();
+    for i in 0..dest.len() {
+        while dest[i] != i {
+            let d = dest[i];
+            values.swap(i, d);
+            dest.swap(i, d);
+        }
+    }
+}
+
+impl<I: Idx, J: Idx> IndexSlice<I, [J]> {
+    /// Sort stored index values with a counting sort. See
+    /// [`IndexSlice::sort_by_index_key`].
+    #[inline]
+    pub fn sort_indices(&mut self) {
+        self.sort_by_index_key(|&j| j);
+    }
+
+    /// Convert every stored index value to the sibling index type `K`, in a
+    /// single pass of checked conversions.
+    ///
+    /// Returns `None` if any value is too large for `K`. Combine with
+    /// [`IndexVec::try_convert_indices`] to narrow both sides of a table.
+    #[inline]
+    pub fn try_convert_values<K: Idx>(&self) -> Option<IndexVec<I, K>> {
+        self.raw
+            .iter()
+            .map(|j| {
+                let j = j.index();
+                // SAFETY: We checked `j` is valid for `K`.
+                (j <= K::MAX).then(|| unsafe { K::from_usize_unchecked(j) })
+            })
+            .collect()
+    }
+}
+
+impl<I: Idx> IndexSlice<I, [usize]> {
+    /// Exclusive prefix sum: returns a table where each index holds the sum of
+    /// all the counts before it, along with the total.
+    ///
+    /// This is the usual first step when building a CSR-style layout from
+    /// per-key counts: the result is the offset each key's data starts at.
+    #[inline]
+    pub fn prefix_sum(&self) -> (IndexVec<I, usize>, usize) {
+        let mut total = 0;
+        let offsets = self
+            .raw
+            .iter()
+            .map(|&n| {
+                let start = total;
+                total += n;
+                start
+            })
+            .collect();
+        (offsets, total)
+    }
+
+    /// Inclusive prefix sum: returns a table where each index holds the sum of
+    /// all the counts up to and including it.
+    #[inline]
+    pub fn inclusive_prefix_sum(&self) -> IndexVec<I, usize> {
+        self.raw
+            .iter()
+            .scan(0, |total, &n| {
+                *total += n;
+                Some(*total)
+            })
+            .collect()
+    }
+}
+
 impl<I: Idx, A, B> PartialEq<IndexSlice<I, [B]>> for IndexSlice<I, [A]>
 where
     A: PartialEq<B>,
@@ -790,6 +1145,14 @@ impl<I: Idx, T> From<Box<[T]>> for Box<IndexSlice<I, [T]>> {
     }
 }
 
+impl<I: Idx, T> From<Box<IndexSlice<I, [T]>>> for Box<[T]> {
+    #[inline]
+    fn from(b: Box<IndexSlice<I, [T]>>) -> Self {
+        // SAFETY: `IndexSlice` is a thin wrapper around `[T]` with the added marker for the index.
+        unsafe { Box::from_raw(Box::into_raw(b) as *mut [T]) }
+    }
+}
+
 impl<I: Idx, A> AsRef<[A]> for IndexSlice<I, [A]> {
     #[inline]
     fn as_ref(&self) -> &[A] {
@@ -0,0 +1,233 @@
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData, mem::MaybeUninit};
+
+use crate::{
+    Idx,
+    bitset::{WORD_BITS, set_bits},
+};
+
+/// A map from an index type to values, stored densely with an occupancy
+/// bitset.
+///
+/// Values live at their index in one `Vec`, like an `IndexVec<I, Option<V>>`,
+/// but whether a slot is occupied is tracked in a separate bitset. So for a
+/// `V` without a niche there's no `Option` discriminant per slot, and
+/// iteration skips whole words of empty slots at a time.
+///
+/// [`IndexMap::insert`] grows the map to include the index, so it doesn't need
+/// to be sized up front.
+///
+/// ```rust
+/// use oxc_index::IndexMap;
+///
+/// oxc_index::define_index_type! {
+///     pub struct SymbolId = u32;
+/// }
+///
+/// let mut types = IndexMap::<SymbolId, u64>::new();
+/// types.insert(SymbolId::new(10), 1);
+/// types.insert(SymbolId::new(3), 2);
+/// assert!(types.contains(SymbolId::new(3)));
+/// assert!(!types.contains(SymbolId::new(4)));
+/// assert_eq!(types.iter().collect::<Vec<_>>(), [(SymbolId::new(3), &2), (SymbolId::new(10), &1)]);
+/// ```
+pub struct IndexMap<I: Idx, V> {
+    values: Vec<MaybeUninit<V>>,
+    occupied: Vec<usize>,
+    len: usize,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx, V> IndexMap<I, V> {
+    /// Construct an empty `IndexMap`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { values: Vec::new(), occupied: Vec::new(), len: 0, _marker: PhantomData }
+    }
+
+    /// Returns the number of values.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    fn is_occupied(&self, i: usize) -> bool {
+        self.occupied.get(i / WORD_BITS).is_some_and(|w| w & (1 << (i % WORD_BITS)) != 0)
+    }
+
+    /// Returns true if there's a value at `idx`.
+    #[inline]
+    pub fn contains(&self, idx: I) -> bool {
+        self.is_occupied(idx.index())
+    }
+
+    /// Get the value at `idx`, or `None` if there is none.
+    #[inline]
+    pub fn get(&self, idx: I) -> Option<&V> {
+        let i = idx.index();
+        // SAFETY: occupied slots are initialized.
+        self.is_occupied(i).then(|| unsafe { self.values[i].assume_init_ref() })
+    }
+
+    /// Get the value at `idx` mutably, or `None` if there is none.
+    #[inline]
+    pub fn get_mut(&mut self, idx: I) -> Option<&mut V> {
+        let i = idx.index();
+        // SAFETY: occupied slots are initialized.
+        self.is_occupied(i).then(|| unsafe { self.values[i].assume_init_mut() })
+    }
+
+    /// Insert `value` at `idx`, growing the map if needed. Returns the value
+    /// previously there, if any.
+    pub fn insert(&mut self, idx: I, value: V) -> Option<V> {
+        let i = idx.index();
+        if i >= self.values.len() {
+            self.values.resize_with(i + 1, MaybeUninit::uninit);
+            self.occupied.resize(self.values.len().div_ceil(WORD_BITS), 0);
+        }
+        let mask = 1 << (i % WORD_BITS);
+        let word = &mut self.occupied[i / WORD_BITS];
+        let slot = &mut self.values[i];
+        if *word & mask == 0 {
+            *word |= mask;
+            self.len += 1;
+            slot.write(value);
+            None
+        } else {
+            // SAFETY: the slot is occupied, so it's initialized.
+            Some(core::mem::replace(unsafe { slot.assume_init_mut() }, value))
+        }
+    }
+
+    /// Remove the value at `idx`, returning it if there was one.
+    pub fn remove(&mut self, idx: I) -> Option<V> {
+        let i = idx.index();
+        if !self.is_occupied(i) {
+            return None;
+        }
+        self.occupied[i / WORD_BITS] &= !(1 << (i % WORD_BITS));
+        self.len -= 1;
+        // SAFETY: the slot was occupied, and is now marked vacant so it won't
+        // be read again.
+        Some(unsafe { self.values[i].assume_init_read() })
+    }
+
+    /// Remove every value.
+    pub fn clear(&mut self) {
+        // Forget the occupancy first, so a panicking `drop` leaks the rest
+        // rather than dropping anything twice.
+        let occupied = core::mem::take(&mut self.occupied);
+        self.len = 0;
+        for i in set_bits(occupied.iter().copied()) {
+            // SAFETY: occupied slots are initialized, and no longer marked as
+            // occupied.
+            unsafe { self.values[i].assume_init_drop() };
+        }
+        self.values.clear();
+    }
+
+    /// Iterate over the indices which have a value, in ascending order.
+    #[inline]
+    pub fn keys(&self) -> impl Iterator<Item = I> + '_ {
+        set_bits(self.occupied.iter().copied()).map(I::from_usize)
+    }
+
+    /// Iterate over the values along with their indices, in index order.
+    pub fn iter(&self) -> impl Iterator<Item = (I, &V)> + '_ {
+        set_bits(self.occupied.iter().copied()).map(|i| {
+            // SAFETY: occupied slots are initialized.
+            (I::from_usize(i), unsafe { self.values[i].assume_init_ref() })
+        })
+    }
+
+    /// Iterate mutably over the values along with their indices, in index
+    /// order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (I, &mut V)> + '_ {
+        let occupied = &self.occupied;
+        self.values.iter_mut().enumerate().filter_map(move |(i, slot)| {
+            let occupied = occupied[i / WORD_BITS] & (1 << (i % WORD_BITS)) != 0;
+            // SAFETY: occupied slots are initialized.
+            occupied.then(|| (I::from_usize(i), unsafe { slot.assume_init_mut() }))
+        })
+    }
+}
+
+impl<I: Idx, V> Drop for IndexMap<I, V> {
+    fn drop(&mut self) {
+        if core::mem::needs_drop::<V>() {
+            self.clear();
+        }
+    }
+}
+
+impl<I: Idx, V: Clone> Clone for IndexMap<I, V> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::new();
+        for (idx, value) in self.iter() {
+            clone.insert(idx, value.clone());
+        }
+        clone
+    }
+}
+
+impl<I: Idx, V> Default for IndexMap<I, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, V: PartialEq> PartialEq for IndexMap<I, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<I: Idx, V: Eq> Eq for IndexMap<I, V> {}
+
+impl<I: Idx, V> core::ops::Index<I> for IndexMap<I, V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, idx: I) -> &V {
+        self.get(idx).unwrap_or_else(|| panic!("no value at index {idx:?}"))
+    }
+}
+
+impl<I: Idx, V> core::ops::IndexMut<I> for IndexMap<I, V> {
+    #[inline]
+    fn index_mut(&mut self, idx: I) -> &mut V {
+        self.get_mut(idx).unwrap_or_else(|| panic!("no value at index {idx:?}"))
+    }
+}
+
+impl<I: Idx, V> Extend<(I, V)> for IndexMap<I, V> {
+    /// Insert every `(index, value)` pair yielded by `iter`, replacing any
+    /// existing values.
+    fn extend<J: IntoIterator<Item = (I, V)>>(&mut self, iter: J) {
+        for (idx, value) in iter {
+            self.insert(idx, value);
+        }
+    }
+}
+
+impl<I: Idx, V> FromIterator<(I, V)> for IndexMap<I, V> {
+    fn from_iter<J: IntoIterator<Item = (I, V)>>(iter: J) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<I: Idx, V: fmt::Debug> fmt::Debug for IndexMap<I, V> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.iter()).finish()
+    }
+}
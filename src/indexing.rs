@@ -1,4 +1,4 @@
-use crate::{Idx, IndexSlice};
+use crate::{Idx, IdxRange, IndexSlice};
 
 mod private_slice_index {
     pub trait Sealed {}
@@ -77,12 +77,14 @@ impl<I: Idx> private_slice_index::Sealed for core::ops::RangeFrom<I> {}
 impl<I: Idx> private_slice_index::Sealed for core::ops::RangeTo<I> {}
 impl<I: Idx> private_slice_index::Sealed for core::ops::RangeInclusive<I> {}
 impl<I: Idx> private_slice_index::Sealed for core::ops::RangeToInclusive<I> {}
+impl<I: Idx> private_slice_index::Sealed for IdxRange<I> {}
 
 range_slice!(core::ops::Range<I>);
 range_slice!(core::ops::RangeFrom<I>);
 range_slice!(core::ops::RangeTo<I>);
 range_slice!(core::ops::RangeInclusive<I>);
 range_slice!(core::ops::RangeToInclusive<I>);
+range_slice!(IdxRange<I>);
 // range_slice!(core::ops::RangeFull);
 impl private_slice_index::Sealed for core::ops::RangeFull {}
 impl<I: Idx, T> IdxSliceIndex<I, T> for core::ops::RangeFull {
@@ -134,6 +136,7 @@ impl<I: Idx> private_range_bounds::Sealed for core::ops::RangeTo<I> {}
 impl<I: Idx> private_range_bounds::Sealed for core::ops::RangeInclusive<I> {}
 impl<I: Idx> private_range_bounds::Sealed for core::ops::RangeToInclusive<I> {}
 impl private_range_bounds::Sealed for core::ops::RangeFull {}
+impl<I: Idx> private_range_bounds::Sealed for IdxRange<I> {}
 
 impl<I: Idx> IdxRangeBounds<I> for core::ops::Range<I> {
     type Range = core::ops::Range<usize>;
@@ -189,6 +192,15 @@ impl<I: Idx> IdxRangeBounds<I> for core::ops::RangeToInclusive<I> {
     }
 }
 
+impl<I: Idx> IdxRangeBounds<I> for IdxRange<I> {
+    type Range = core::ops::Range<usize>;
+
+    #[inline]
+    fn into_range(self) -> Self::Range {
+        self.start.index()..self.end.index()
+    }
+}
+
 impl<I, R, T> core::ops::Index<R> for IndexSlice<I, [T]>
 where
     I: Idx,
@@ -0,0 +1,139 @@
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+use std::collections::HashMap;
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// Deduplicates values, handing out an index per distinct value.
+///
+/// Values are stored in insertion order in an `IndexVec`, so looking one up
+/// by index is a plain array access, and a `HashMap` maps each value back to
+/// its index. Each value is kept in both, so `T` should be cheap to clone
+/// (e.g. a `Copy` type, or an `Rc<str>`).
+///
+/// ```rust
+/// use oxc_index::Interner;
+///
+/// oxc_index::define_index_type! {
+///     pub struct Atom = u32;
+/// }
+///
+/// let mut atoms = Interner::<Atom, &str>::new();
+/// let foo = atoms.intern("foo");
+/// let bar = atoms.intern("bar");
+/// assert_eq!(atoms.intern("foo"), foo);
+/// assert_eq!(atoms[bar], "bar");
+/// assert_eq!(atoms.lookup("bar"), Some(bar));
+/// assert_eq!(atoms.lookup("baz"), None);
+/// ```
+#[derive(Clone)]
+pub struct Interner<I: Idx, T, S = std::hash::RandomState> {
+    values: IndexVec<I, T>,
+    indices: HashMap<T, I, S>,
+}
+
+impl<I: Idx, T> Interner<I, T> {
+    /// Construct an empty `Interner`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<I: Idx, T, S: BuildHasher> Interner<I, T, S> {
+    /// Construct an empty `Interner` using `hasher` to hash the values.
+    #[inline]
+    pub fn with_hasher(hasher: S) -> Self {
+        Self { values: IndexVec::new(), indices: HashMap::with_hasher(hasher) }
+    }
+
+    /// Returns the number of distinct values.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if nothing has been interned.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Get the value for `idx`, or `None` if `idx` is out of bounds.
+    #[inline]
+    pub fn get(&self, idx: I) -> Option<&T> {
+        self.values.get(idx)
+    }
+
+    /// The interned values, in the order they were first interned.
+    #[inline]
+    pub fn values(&self) -> &IndexSlice<I, [T]> {
+        &self.values
+    }
+
+    /// Returns the index of `value`, if it has been interned.
+    #[inline]
+    pub fn lookup<Q>(&self, value: &Q) -> Option<I>
+    where
+        T: Borrow<Q> + Hash + Eq,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.indices.get(value).copied()
+    }
+
+    /// Returns the index of `value`, interning it first if it's new.
+    pub fn intern(&mut self, value: T) -> I
+    where
+        T: Clone + Hash + Eq,
+    {
+        *self.indices.entry(value).or_insert_with_key(|value| self.values.push(value.clone()))
+    }
+
+    /// Unwrap into the interned values, in the order they were first interned.
+    #[inline]
+    pub fn into_values(self) -> IndexVec<I, T> {
+        self.values
+    }
+}
+
+impl<I: Idx, T, S: Default> Default for Interner<I, T, S> {
+    #[inline]
+    fn default() -> Self {
+        Self { values: IndexVec::new(), indices: HashMap::default() }
+    }
+}
+
+impl<I: Idx, T, S> core::ops::Index<I> for Interner<I, T, S> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, idx: I) -> &T {
+        &self.values[idx]
+    }
+}
+
+impl<I: Idx, T: Clone + Hash + Eq, S: BuildHasher> Extend<T> for Interner<I, T, S> {
+    /// Intern every value yielded by `iter`.
+    fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
+        for value in iter {
+            self.intern(value);
+        }
+    }
+}
+
+impl<I: Idx, T: Clone + Hash + Eq, S: BuildHasher + Default> FromIterator<T> for Interner<I, T, S> {
+    fn from_iter<J: IntoIterator<Item = T>>(iter: J) -> Self {
+        let mut interner = Self::default();
+        interner.extend(iter);
+        interner
+    }
+}
+
+impl<I: Idx, T: fmt::Debug, S> fmt::Debug for Interner<I, T, S> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.values.fmt(fmt)
+    }
+}
@@ -0,0 +1,155 @@
+use alloc::vec::Vec;
+use core::{fmt, ops::Range};
+
+use crate::Idx;
+
+/// A map from non-overlapping ranges of an index type to values.
+///
+/// Entries are kept sorted by start, so finding the range which contains an
+/// index is a binary search. This replaces the sorted `Vec` plus hand written
+/// `partition_point` that span → scope or offset → file lookups tend to grow.
+///
+/// ```rust
+/// use oxc_index::IntervalMap;
+///
+/// oxc_index::define_index_type! {
+///     pub struct ByteIdx = u32;
+/// }
+///
+/// let mut files = IntervalMap::new();
+/// files.insert(ByteIdx::new(0)..ByteIdx::new(100), "a.js").unwrap();
+/// files.insert(ByteIdx::new(200)..ByteIdx::new(300), "b.js").unwrap();
+/// assert!(files.insert(ByteIdx::new(50)..ByteIdx::new(150), "c.js").is_err());
+///
+/// assert_eq!(files.get(ByteIdx::new(99)), Some(&"a.js"));
+/// assert_eq!(files.get(ByteIdx::new(150)), None);
+/// assert_eq!(files.get(ByteIdx::new(200)), Some(&"b.js"));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalMap<I: Idx, T> {
+    entries: Vec<(Range<I>, T)>,
+}
+
+impl<I: Idx, T> IntervalMap<I, T> {
+    /// Construct an empty map.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Returns the number of ranges in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the map has no ranges.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Insert `value` for every index in `range`, which can be a `Range<I>` or
+    /// an [`IdxRange<I>`](crate::IdxRange).
+    ///
+    /// Returns `Err(value)` without modifying the map if `range` is empty, or
+    /// overlaps a range already in the map.
+    pub fn insert<R: Into<Range<I>>>(&mut self, range: R, value: T) -> Result<(), T> {
+        let range = range.into();
+        if range.start >= range.end {
+            return Err(value);
+        }
+        let pos = self.entries.partition_point(|(r, _)| r.start < range.start);
+        let overlaps_prev = pos > 0 && self.entries[pos - 1].0.end > range.start;
+        let overlaps_next = self.entries.get(pos).is_some_and(|(r, _)| r.start < range.end);
+        if overlaps_prev || overlaps_next {
+            return Err(value);
+        }
+        self.entries.insert(pos, (range, value));
+        Ok(())
+    }
+
+    #[inline]
+    fn find(&self, idx: I) -> Option<usize> {
+        let pos = self.entries.partition_point(|(r, _)| r.start <= idx);
+        let pos = pos.checked_sub(1)?;
+        (idx < self.entries[pos].0.end).then_some(pos)
+    }
+
+    /// Get a ref to the value of the range containing `idx`, if any.
+    #[inline]
+    pub fn get(&self, idx: I) -> Option<&T> {
+        self.find(idx).map(|pos| &self.entries[pos].1)
+    }
+
+    /// Get a mut ref to the value of the range containing `idx`, if any.
+    #[inline]
+    pub fn get_mut(&mut self, idx: I) -> Option<&mut T> {
+        self.find(idx).map(|pos| &mut self.entries[pos].1)
+    }
+
+    /// Get the range containing `idx` along with its value, if any.
+    #[inline]
+    pub fn get_entry(&self, idx: I) -> Option<(Range<I>, &T)> {
+        self.find(idx).map(|pos| {
+            let (range, value) = &self.entries[pos];
+            (range.clone(), value)
+        })
+    }
+
+    /// Returns true if some range contains `idx`.
+    #[inline]
+    pub fn contains(&self, idx: I) -> bool {
+        self.find(idx).is_some()
+    }
+
+    /// Remove the range containing `idx`, returning it along with its value.
+    #[inline]
+    pub fn remove(&mut self, idx: I) -> Option<(Range<I>, T)> {
+        self.find(idx).map(|pos| self.entries.remove(pos))
+    }
+
+    /// Remove every range.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Iterate over the ranges and their values, in order.
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Range<I>, &T)> + ExactSizeIterator {
+        self.entries.iter().map(|(range, value)| (range.clone(), value))
+    }
+
+    /// Iterate over the ranges and mut refs to their values, in order.
+    #[inline]
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = (Range<I>, &mut T)> + ExactSizeIterator {
+        self.entries.iter_mut().map(|(range, value)| (range.clone(), value))
+    }
+}
+
+impl<I: Idx, T> Default for IntervalMap<I, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, T: fmt::Debug> fmt::Debug for IntervalMap<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<I: Idx, T: zeroize::Zeroize> zeroize::Zeroize for IntervalMap<I, T> {
+    /// Zeroes every value, leaving an empty map.
+    fn zeroize(&mut self) {
+        for (_, value) in &mut self.entries {
+            value.zeroize();
+        }
+        self.entries.clear();
+    }
+}
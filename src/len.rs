@@ -0,0 +1,151 @@
+use core::{fmt, marker::PhantomData, ops::Range};
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// A length token for the index domain `I`.
+///
+/// A `Len<I>` is minted from a primary table with [`IndexSlice::len_token`],
+/// and handed to sibling tables which are keyed by the same `I`. Those can be
+/// built from it ([`IndexVec::new_for`]), resized to it
+/// ([`IndexVec::resize_for`]), or checked against it
+/// ([`IndexSlice::assert_len_for`]). This catches the classic "this side table
+/// was never resized after the arena grew" bug at the point where the tables
+/// are combined, instead of as an out of bounds access somewhere far away.
+///
+/// ```rust
+/// use oxc_index::{IndexVec, index_vec};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let mut nodes: IndexVec<NodeIdx, &str> = index_vec!["a", "b"];
+/// let mut depth = IndexVec::<NodeIdx, u32>::new_for(nodes.len_token());
+/// assert_eq!(depth.len(), 2);
+///
+/// nodes.push("c");
+/// assert!(!depth.matches_len(nodes.len_token()));
+/// depth.resize_for(nodes.len_token());
+/// depth.assert_len_for(nodes.len_token());
+/// ```
+pub struct Len<I: Idx> {
+    len: usize,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx> Len<I> {
+    /// Construct a length token directly. Prefer [`IndexSlice::len_token`],
+    /// which ties the token to an actual table.
+    #[inline]
+    pub const fn new(len: usize) -> Self {
+        Self { len, _marker: PhantomData }
+    }
+
+    /// The length as a `usize`.
+    #[inline(always)]
+    pub const fn get(self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the length is zero.
+    #[inline(always)]
+    pub const fn is_empty(self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if `idx` is in bounds for a table of this length.
+    #[inline]
+    pub fn contains(self, idx: I) -> bool {
+        idx.index() < self.len
+    }
+
+    /// Get an iterator over all the indices in bounds for this length.
+    #[inline]
+    pub fn indices(self) -> core::iter::Map<Range<usize>, fn(usize) -> I> {
+        (0..self.len).map(I::from_usize)
+    }
+}
+
+impl<I: Idx> Clone for Len<I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I: Idx> Copy for Len<I> {}
+
+impl<I: Idx> PartialEq for Len<I> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+    }
+}
+
+impl<I: Idx> Eq for Len<I> {}
+
+impl<I: Idx> fmt::Debug for Len<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "Len({})", self.len)
+    }
+}
+
+impl<I: Idx, T> IndexSlice<I, [T]> {
+    /// Mint a [`Len`] token for our current length, which sibling tables keyed
+    /// by `I` can be built from or checked against.
+    #[inline]
+    pub const fn len_token(&self) -> Len<I> {
+        Len::new(self.len())
+    }
+
+    /// Returns true if our length is the one recorded in `len`.
+    #[inline]
+    pub const fn matches_len(&self, len: Len<I>) -> bool {
+        self.len() == len.get()
+    }
+
+    /// Assert that our length is the one recorded in `len`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lengths differ.
+    #[inline]
+    #[track_caller]
+    pub fn assert_len_for(&self, len: Len<I>) {
+        assert!(
+            self.matches_len(len),
+            "index_vec length mismatch: table has {} elements, expected {}",
+            self.len(),
+            len.get()
+        );
+    }
+}
+
+impl<I: Idx, T> IndexVec<I, T> {
+    /// Construct a new table with one default element for every index in
+    /// `len`.
+    #[inline]
+    pub fn new_for(len: Len<I>) -> Self
+    where
+        T: Default,
+    {
+        Self::new_for_with(len, |_| T::default())
+    }
+
+    /// Construct a new table for every index in `len`, calling `f` to produce
+    /// each element.
+    #[inline]
+    pub fn new_for_with<F: FnMut(I) -> T>(len: Len<I>, f: F) -> Self {
+        len.indices().map(f).collect()
+    }
+
+    /// Resize ourselves to the length recorded in `len`, filling any new
+    /// elements with `T::default()`.
+    #[inline]
+    pub fn resize_for(&mut self, len: Len<I>)
+    where
+        T: Default,
+    {
+        self.raw.resize_with(len.get(), T::default);
+    }
+}
@@ -106,11 +106,19 @@
 //!
 //! #### Does it support no_std?
 //!
-//! Yes, although it uses `extern crate alloc;`, of course.
+//! Yes, although it uses `extern crate alloc;`, of course. The index-type
+//! macros, `IndexSlice`, `IndexVec` and most other containers only need
+//! `alloc`. The few std-only bits (the hash map aliases, the interners, and
+//! `ConcurrentIndexVec`) are behind the `std` feature, which also turns on
+//! the `std` features of the optional `serde`, `nonmax` and `zeroize`
+//! dependencies.
 //!
 //! #### Does it support serde?
 //!
-//! Yes, but only if you turn on the `serialize` feature.
+//! Yes, but only if you turn on the `serialize` feature. An `IndexVec` is
+//! written as a sequence by default; use the `serde_map` module (via
+//! `#[serde(with = "oxc_index::serde_map")]`) to write it as a map from index
+//! to value instead.
 //!
 //! #### Does it support NonMaxU32?
 //!
@@ -124,6 +132,19 @@
 //! }
 //! ```
 //!
+//! Other widths are available through `define_nonmax_index_type!`, e.g.
+//! `pub struct MyIndex = u16;` for one backed by `NonMaxU16`.
+//!
+//! #### Why does `assert_eq!(v.as_slice(), &[])` stop compiling with `rkyv` or `schemars`?
+//!
+//! Some dependencies implement `PartialEq` between the integer types and
+//! their own: rkyv's `rend` for the archived forms (e.g. `u32: PartialEq<u32_le>`),
+//! and schemars' `serde_json` for `Value`. Once one is in the build,
+//! comparing a table of integers with an untyped empty literal is ambiguous,
+//! so it needs an annotation like `&[0_u32; 0]`. This isn't
+//! specific to `IndexVec`: `Vec<u32>` and `&[u32]` are affected the same way,
+//! and there's nothing our impls can do about it.
+//!
 //! #### What features are planned?
 //!
 //! Planned is a bit strong but here are the things I would find useful.
@@ -143,10 +164,13 @@
 #![allow(clippy::partialeq_ne_impl)]
 #![no_std]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 use alloc::{
     borrow::{Cow, ToOwned},
     boxed::Box,
+    collections::TryReserveError,
     vec,
     vec::Vec,
 };
@@ -160,18 +184,192 @@ use core::{
     ops::Range,
     slice,
 };
+mod allocator;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+#[cfg(feature = "arc-swap")]
+mod arc_vec;
+#[cfg(feature = "bumpalo")]
+mod arena_vec;
+mod array;
+mod array_vec;
+mod atomic_bitset;
+mod atomic_idx;
+#[cfg(feature = "bincode")]
+mod bincode_impl;
+mod bit_matrix;
+mod bitset;
+mod branded;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impl;
+mod chunked_bitset;
+#[cfg(feature = "dashmap")]
+mod concurrent_map;
+#[cfg(feature = "std")]
+mod concurrent_vec;
+mod counter;
+mod cow_vec;
+mod deque;
+mod diff;
+mod dirty;
+mod frozen;
+mod generational;
+mod hash;
 mod idxslice;
+mod index_map;
 mod indexing;
+#[cfg(feature = "std")]
+mod interner;
+mod interval;
+mod len;
+mod multimap;
+mod once_vec;
+mod option_idx;
+mod packed_option;
+mod parse;
+mod parts;
+mod priority_queue;
+mod range;
+mod range_set;
+#[cfg(feature = "rkyv")]
+mod rkyv_impl;
+mod rle;
+mod rope;
+#[cfg(feature = "schemars")]
+mod schemars_impl;
+#[cfg(feature = "serde")]
+mod serde_bits;
+#[cfg(feature = "serde")]
+pub mod serde_map;
+mod slab;
+#[cfg(feature = "smallvec")]
+mod small_vec;
+#[cfg(feature = "hashbrown")]
+mod sparse_map;
+#[cfg(feature = "speedy")]
+mod speedy_impl;
+mod stable_hash;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "std")]
+mod string_interner;
+#[cfg(feature = "thin-vec")]
+mod thin_vec_impl;
+#[cfg(feature = "ts-rs")]
+mod ts_rs_impl;
+mod union_find;
+mod watched;
+pub use allocator::IdxAllocator;
+#[cfg(feature = "arbitrary")]
+pub use arbitrary;
+#[cfg(feature = "arc-swap")]
+pub use arc_swap;
+#[cfg(feature = "arc-swap")]
+pub use arc_vec::ArcIndexVec;
+#[cfg(feature = "bumpalo")]
+pub use arena_vec::ArenaIndexVec;
+pub use array::IndexArray;
+pub use array_vec::IndexArrayVec;
+pub use atomic_bitset::AtomicIndexBitSet;
+pub use atomic_idx::{__RawAtomicFor, AtomicIdx, HasAtomic, RawAtomic};
+#[cfg(feature = "bincode")]
+pub use bincode;
+pub use bit_matrix::BitMatrix;
+pub use bitset::IndexBitSet;
+pub use branded::{BrandedIdx, BrandedIndexVec};
+#[cfg(feature = "bumpalo")]
+pub use bumpalo;
+#[cfg(feature = "bytemuck")]
+pub use bytemuck;
+pub use chunked_bitset::ChunkedIndexBitSet;
+#[cfg(feature = "dashmap")]
+pub use concurrent_map::ConcurrentIndexMap;
+#[cfg(feature = "std")]
+pub use concurrent_vec::ConcurrentIndexVec;
+pub use counter::IndexCounter;
+pub use cow_vec::CowIndexVec;
+#[cfg(feature = "dashmap")]
+pub use dashmap;
+#[cfg(feature = "defmt")]
+pub use defmt;
+pub use deque::IndexVecDeque;
+pub use diff::{DiffDebug, IndexDiff};
+pub use dirty::DirtyIndexVec;
+pub use frozen::FrozenIndexVec;
+pub use generational::{GenerationalIndexVec, GenerationalKey};
+pub use hash::{BuildIdxHasher, IdxHasher};
+#[cfg(feature = "std")]
+pub use hash::{IdxHashMap, IdxHashSet};
+#[cfg(all(feature = "std", feature = "nohash-hasher"))]
+pub use hash::{IndexHashMap, IndexHashSet};
+#[cfg(feature = "hashbrown")]
+pub use hashbrown;
 pub use idxslice::{IndexBox, IndexSlice};
+pub use index_map::IndexMap;
 pub use indexing::{IdxRangeBounds, IdxSliceIndex};
+#[cfg(feature = "std")]
+pub use interner::Interner;
+pub use interval::IntervalMap;
+pub use len::Len;
+pub use multimap::IndexMultiMap;
+#[cfg(feature = "napi")]
+pub use napi;
+#[cfg(feature = "nohash-hasher")]
+pub use nohash_hasher;
 #[cfg(feature = "nonmax")]
 pub use nonmax;
+pub use once_vec::OnceIndexVec;
+#[cfg(feature = "std")]
+pub use once_vec::SyncOnceIndexVec;
+pub use option_idx::OptionIdx;
+#[cfg(feature = "derive")]
+pub use oxc_index_derive::Idx;
+pub use packed_option::PackedOption;
+pub use parse::{ParseIdxError, TryFromIdxError};
+pub use parts::IndexVecPart;
+pub use priority_queue::IndexPriorityQueue;
+#[cfg(feature = "proptest")]
+pub use proptest;
+pub use range::IdxRange;
+pub use range_set::IndexRangeSet;
 #[cfg(feature = "rayon")]
 pub use rayon_impl::*;
+#[cfg(feature = "rkyv")]
+pub use rkyv;
+#[cfg(feature = "rkyv")]
+pub use rkyv_impl::{ArchivedIdx, ArchivedRaw};
+pub use rle::RleIndexVec;
+pub use rope::IndexRope;
+#[cfg(feature = "schemars")]
+pub use schemars;
 #[cfg(feature = "serde")]
 pub use serde;
+pub use slab::IndexSlab;
+#[cfg(feature = "smallvec")]
+pub use small_vec::IndexSmallVec;
+#[cfg(feature = "smallvec")]
+pub use smallvec;
+#[cfg(feature = "hashbrown")]
+pub use sparse_map::SparseIndexMap;
+#[cfg(feature = "speedy")]
+pub use speedy;
+pub use stable_hash::{StableHasher, stable_hash};
+#[cfg(feature = "std")]
+pub use string_interner::StringInterner;
+#[cfg(feature = "thin-vec")]
+pub use thin_vec;
+#[cfg(feature = "thin-vec")]
+pub use thin_vec_impl::IndexThinVec;
+#[cfg(feature = "ts-rs")]
+pub use ts_rs;
+pub use union_find::UnionFind;
+pub use watched::WatchedIndexVec;
+#[cfg(feature = "zeroize")]
+pub use zeroize;
 #[cfg(feature = "rayon")]
 mod rayon_impl;
+#[cfg(feature = "zeroize")]
+mod zeroize_impl;
 
 #[macro_use]
 mod macros;
@@ -218,6 +416,17 @@ pub trait Idx: Copy + 'static + Ord + Debug + Hash {
         // SAFETY: We checked `idx` is valid
         unsafe { Self::from_usize_unchecked(idx) }
     }
+
+    /// Construct an Index from a `usize`, or return `None` if `idx` is greater
+    /// than `Self::MAX`. This never panics, and checks `idx` even if checking
+    /// has been disabled for `from_usize`.
+    ///
+    /// Note that index types don't implement `TryFrom<usize>`, as their
+    /// `From<usize>` impl already comes with a (panicking) blanket one.
+    fn try_from_usize(idx: usize) -> Option<Self> {
+        // SAFETY: We checked `idx` is valid
+        (idx <= Self::MAX).then(|| unsafe { Self::from_usize_unchecked(idx) })
+    }
 }
 
 /// A macro equivalent to the stdlib's `vec![]`, but producing an `IndexVec`.
@@ -237,6 +446,54 @@ macro_rules! index_box {
     }
 }
 
+/// Asserts that two `IndexVec`s (or `IndexSlice`s) are equal, like
+/// `assert_eq!`.
+///
+/// On failure, only the differing entries are printed, along with their
+/// typed indices, instead of two full `Debug` dumps. See
+/// [`IndexSlice::diff_debug`].
+///
+/// ```rust,should_panic
+/// use oxc_index::{IndexVec, assert_index_vec_eq, index_vec};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+///     DEBUG_FORMAT = "Node({})";
+/// }
+///
+/// let a: IndexVec<NodeIdx, u32> = index_vec![1, 2, 3];
+/// let b: IndexVec<NodeIdx, u32> = index_vec![1, 5, 3];
+/// // Panics with "[Node(1)] left: 2, right: 5".
+/// assert_index_vec_eq!(a, b);
+/// ```
+#[macro_export]
+macro_rules! assert_index_vec_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left[..], &$right[..]) {
+            (left, right) => {
+                let diff = $crate::IndexSlice::diff_debug(left, right);
+                if !diff.is_empty() {
+                    panic!("assertion `left == right` failed\n{}", diff);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left[..], &$right[..]) {
+            (left, right) => {
+                let diff = $crate::IndexSlice::diff_debug(left, right);
+                if !diff.is_empty() {
+                    panic!(
+                        "assertion `left == right` failed: {}\n{}",
+                        format_args!($($arg)+),
+                        diff
+                    );
+                }
+            }
+        }
+    };
+}
+
 /// A Vec that only accepts indices of a specific type.
 ///
 /// This is a thin wrapper around `Vec`, to the point where the backing vec is a
@@ -289,6 +546,49 @@ impl<I: Idx, T> IndexVec<I, T> {
         IndexVec { raw: vec, _marker: PhantomData }
     }
 
+    /// Create an IndexVec from its pointer, length and capacity, taking
+    /// ownership of the buffer.
+    ///
+    /// Panics if `length` is too large for our index type.
+    ///
+    /// # SAFETY
+    ///
+    /// This is equivalent to [`Vec::from_raw_parts`] and has the same safety
+    /// caveats. In particular, the parts returned by
+    /// [`IndexVec::into_raw_parts`] (or a `Vec<T>`'s) are always valid.
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: *mut T, length: usize, capacity: usize) -> Self {
+        // SAFETY: the caller upholds `Vec::from_raw_parts`'s requirements.
+        Self::from_vec(unsafe { Vec::from_raw_parts(ptr, length, capacity) })
+    }
+
+    /// Decompose the vector into its pointer, length and capacity, without
+    /// dropping or copying the elements.
+    ///
+    /// The buffer is exactly the backing `Vec<T>`'s, so these can be passed to
+    /// [`IndexVec::from_raw_parts`] or [`Vec::from_raw_parts`] to take
+    /// ownership again; otherwise the memory is leaked.
+    #[inline]
+    #[must_use = "losing the pointer will leak memory"]
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        let mut raw = core::mem::ManuallyDrop::new(self.raw);
+        (raw.as_mut_ptr(), raw.len(), raw.capacity())
+    }
+
+    /// Re-key this table with the sibling index type `J`, without touching the
+    /// elements.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(self)` unchanged if `len()` exceeds `J::MAX`.
+    #[inline]
+    pub fn try_convert_indices<J: Idx>(self) -> Result<IndexVec<J, T>, Self> {
+        if self.len() > J::MAX {
+            return Err(self);
+        }
+        Ok(IndexVec { raw: self.raw, _marker: PhantomData })
+    }
+
     /// Construct an IndexVec that can hold at least `capacity` items before
     /// reallocating. See [`Vec::with_capacity`].
     #[inline]
@@ -296,6 +596,21 @@ impl<I: Idx, T> IndexVec<I, T> {
         IndexVec { raw: Vec::with_capacity(capacity), _marker: PhantomData }
     }
 
+    /// Construct an IndexVec that can hold at least `capacity` items before
+    /// reallocating, or return an error if the allocation fails. See
+    /// [`Vec::try_reserve_exact`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the capacity overflows, or the allocator reports a
+    /// failure.
+    #[inline]
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        let mut raw = Vec::new();
+        raw.try_reserve_exact(capacity)?;
+        Ok(IndexVec { raw, _marker: PhantomData })
+    }
+
     /// Similar to `self.into_iter().enumerate()` but with indices of `I` and
     /// not `usize`.
     #[inline(always)]
@@ -303,6 +618,13 @@ impl<I: Idx, T> IndexVec<I, T> {
         self.raw.into_iter().enumerate().map(|(i, t)| (I::from_usize(i), t))
     }
 
+    /// Consume the vector, calling `f` with each element and its index to
+    /// produce a new table keyed by the same index type.
+    #[inline]
+    pub fn into_map_enumerated<U, F: FnMut(I, T) -> U>(self, mut f: F) -> IndexVec<I, U> {
+        self.into_iter_enumerated().map(|(i, t)| f(i, t)).collect()
+    }
+
     /// Creates a splicing iterator that replaces the specified range in the
     /// vector with the given `replace_with` iterator and yields the removed
     /// items. See [`Vec::splice`]
@@ -379,8 +701,10 @@ impl<I: Idx, T> IndexVec<I, T> {
         self.raw.pop()
     }
 
-    /// Converts the vector into an owned [`IndexSlice`], dropping excess capacity.
+    /// Converts the vector into an owned [`IndexSlice`] (an [`IndexBox`]),
+    /// dropping excess capacity.
     #[inline]
+    #[doc(alias = "into_boxed_index_slice")]
     pub fn into_boxed_slice(self) -> Box<IndexSlice<I, [T]>> {
         let b = self.raw.into_boxed_slice();
         // SAFETY: `IndexSlice` is a thin wrapper around `[T]` with the added marker for the index.
@@ -416,6 +740,18 @@ impl<I: Idx, T> IndexVec<I, T> {
         self.raw.truncate(a);
     }
 
+    /// Drop elements off the end of the vector for as long as `f` returns true
+    /// for them, e.g. to trim trailing `None`s off a sparse table. Returns the
+    /// new length.
+    ///
+    /// Follow up with [`IndexVec::shrink_to_fit`] to release the memory.
+    #[inline]
+    pub fn truncate_trailing<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> I {
+        let len = self.raw.iter().rposition(|t| !f(t)).map_or(0, |i| i + 1);
+        self.raw.truncate(len);
+        I::from_usize(len)
+    }
+
     /// Clear our vector. See [`Vec::clear`].
     #[inline]
     pub fn clear(&mut self) {
@@ -428,6 +764,37 @@ impl<I: Idx, T> IndexVec<I, T> {
         self.raw.reserve(c);
     }
 
+    /// Reserve capacity for exactly `c` more elements. See
+    /// [`Vec::reserve_exact`]
+    #[inline]
+    pub fn reserve_exact(&mut self, c: usize) {
+        self.raw.reserve_exact(c);
+    }
+
+    /// Try to reserve capacity for `c` more elements. See
+    /// [`Vec::try_reserve`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the capacity overflows, or the allocator reports a
+    /// failure.
+    #[inline]
+    pub fn try_reserve(&mut self, c: usize) -> Result<(), TryReserveError> {
+        self.raw.try_reserve(c)
+    }
+
+    /// Try to reserve capacity for exactly `c` more elements. See
+    /// [`Vec::try_reserve_exact`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the capacity overflows, or the allocator reports a
+    /// failure.
+    #[inline]
+    pub fn try_reserve_exact(&mut self, c: usize) -> Result<(), TryReserveError> {
+        self.raw.try_reserve_exact(c)
+    }
+
     /// Get a ref to the item at the provided index, or None for out of bounds.
     #[inline]
     pub fn get<J: IdxSliceIndex<I, T>>(&self, index: J) -> Option<&J::Output> {
@@ -628,6 +995,27 @@ where
     }
 }
 
+impl<'a, I: Idx, T: Clone> From<&'a IndexSlice<I, [T]>> for Cow<'a, IndexSlice<I, [T]>> {
+    #[inline]
+    fn from(s: &'a IndexSlice<I, [T]>) -> Self {
+        Cow::Borrowed(s)
+    }
+}
+
+impl<'a, I: Idx, T: Clone> From<&'a IndexVec<I, T>> for Cow<'a, IndexSlice<I, [T]>> {
+    #[inline]
+    fn from(v: &'a IndexVec<I, T>) -> Self {
+        Cow::Borrowed(v.as_slice())
+    }
+}
+
+impl<I: Idx, T: Clone> From<IndexVec<I, T>> for Cow<'_, IndexSlice<I, [T]>> {
+    #[inline]
+    fn from(v: IndexVec<I, T>) -> Self {
+        Cow::Owned(v)
+    }
+}
+
 impl<'a, I: Idx, T: Clone> From<&'a IndexSlice<I, [T]>> for IndexVec<I, T> {
     #[inline]
     fn from(src: &'a IndexSlice<I, [T]>) -> Self {
@@ -793,6 +1181,13 @@ array_impls! {
     30 31 32
 }
 
+#[cfg(feature = "schemars")]
+#[doc(hidden)]
+pub use alloc::borrow::Cow as __Cow;
+#[cfg(feature = "ts-rs")]
+#[doc(hidden)]
+pub use std::{path::PathBuf as __PathBuf, string::String as __String};
+
 #[inline(never)]
 #[cold]
 #[doc(hidden)]
@@ -821,8 +1216,9 @@ impl<'de, I: Idx, T: crate::serde::de::Deserialize<'de>> crate::serde::de::Deser
     }
 }
 
+// This also covers `IndexBox`, through serde's impl for `Box<T: ?Sized>`.
 #[cfg(feature = "serde")]
-impl<I: Idx, T: crate::serde::ser::Serialize> crate::serde::ser::Serialize for IndexBox<I, T> {
+impl<I: Idx, T: crate::serde::ser::Serialize> crate::serde::ser::Serialize for IndexSlice<I, [T]> {
     fn serialize<S: crate::serde::ser::Serializer>(
         &self,
         serializer: S,
@@ -882,7 +1278,8 @@ mod test {
 
         v.clear();
         assert_eq!(v.len(), 0);
-        assert_eq!(v.as_slice(), &[]);
+        // Typed, see the FAQ on comparing with `&[]`.
+        assert_eq!(v.as_slice(), &[0_u32; 0]);
         assert_eq!(v, IndexVec::<TestIdx, u32>::new());
     }
 }
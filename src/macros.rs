@@ -152,10 +152,222 @@
 /// assert_eq!(as_u32, 5);
 /// # }
 /// ```
+///
+/// #### `IMPL_STEP = true;`
+///
+/// Implement the unstable `core::iter::Step` trait, so that ranges of the
+/// index type can be iterated directly. This needs a nightly compiler, with
+/// `#![feature(step_trait)]` enabled in the crate invoking the macro. On
+/// stable, use [`IdxRange`](crate::IdxRange) instead.
+///
+/// ```rust,ignore
+/// #![feature(step_trait)]
+///
+/// oxc_index::define_index_type! {
+///     struct FooIdx = u32;
+///     IMPL_STEP = true;
+/// }
+///
+/// for idx in FooIdx::new(0)..FooIdx::new(10) {
+///     // ...
+/// }
+/// ```
+///
+/// #### `NO_ARITHMETIC = true;`
+///
+/// Don't implement the arithmetic operators (`Add`, `Sub`, `Rem` and their
+/// `*Assign` variants), for opaque IDs where doing math on an index is always
+/// a bug. The `checked_*` and `saturating_*` methods are still available.
+///
+/// ```rust,compile_fail
+/// oxc_index::define_index_type! {
+///     struct SymbolId = u32;
+///     NO_ARITHMETIC = true;
+/// }
+///
+/// # fn main() {
+/// let next = SymbolId::new(0) + 1;
+/// # }
+/// ```
+///
+/// #### `SENTINEL = <expr producing usize>;`
+///
+/// Reserve a value as "no index", like a null pointer, for when an
+/// `Option<MyIdx>` would be too big. This adds a `NONE` constant holding it,
+/// `is_some`/`is_none` to test for it, and `get`, which turns it into an
+/// actual `None`. It's also printed as `None` by `Debug`, and the
+/// serialization features (serde, bincode, speedy, napi) accept it back when
+/// decoding.
+///
+/// The sentinel has to be above `MAX_INDEX`, so that `new` and friends can't
+/// produce it by accident; this is checked at compile time.
+///
+/// ```rust
+/// oxc_index::define_index_type! {
+///     pub struct SymbolId = u32;
+///     MAX_INDEX = (u32::MAX - 1) as usize;
+///     SENTINEL = u32::MAX as usize;
+/// }
+///
+/// # fn main() {
+/// assert!(SymbolId::NONE.is_none());
+/// assert_eq!(SymbolId::new(3).get(), Some(SymbolId::new(3)));
+/// assert_eq!(format!("{:?}", SymbolId::NONE), "None");
+/// # }
+/// ```
+///
+/// #### `TS_BRANDED = true;`
+///
+/// With the `ts-rs` feature, declare the index type as a branded alias,
+/// `type MyIdx = number & { readonly __brand: "MyIdx" };`, instead of a plain
+/// `number`, so that TypeScript catches mixed up indices too. This has no
+/// effect without the feature.
+///
+/// #### `IMPL_FROM_STR = true;`
+///
+/// Implement `FromStr` and `TryFrom<&str>`, parsing the index as a decimal
+/// integer. Values above `MAX_INDEX` are rejected with
+/// [`ParseIdxError::TooLarge`](crate::ParseIdxError::TooLarge), even if
+/// `DISABLE_MAX_INDEX_CHECK` is set.
+///
+/// ```rust
+/// oxc_index::define_index_type! {
+///     struct FooIdx = u8;
+///     IMPL_FROM_STR = true;
+/// }
+///
+/// # fn main() {
+/// assert_eq!("12".parse::<FooIdx>(), Ok(FooIdx::new(12)));
+/// assert!(FooIdx::try_from("256").is_err());
+/// # }
+/// ```
+///
+/// #### `#[cfg(...)]` on options
+///
+/// Any option can be prefixed with a `#[cfg(...)]` attribute, in which case it
+/// only applies when the predicate holds. This lets one invocation serve
+/// targets which need different settings, e.g. a smaller `MAX_INDEX` on 32-bit
+/// wasm.
+///
+/// ```rust
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+///     #[cfg(target_pointer_width = "32")]
+///     MAX_INDEX = 0x00ff_ffff;
+///     #[cfg(not(target_pointer_width = "32"))]
+///     MAX_INDEX = 0x7fff_ffff;
+///     DEBUG_FORMAT = "Node({})";
+/// }
+///
+/// # fn main() {
+/// # #[cfg(target_pointer_width = "64")]
+/// assert_eq!(NodeIdx::MAX_INDEX, 0x7fff_ffff);
+/// # }
+/// ```
+///
+/// #### `CONSTANTS = { <name> = <expr producing usize>, ... };`
+///
+/// Declare well-known indices of the domain as associated constants of the
+/// type. Unlike the other options, this has to come directly after the struct
+/// declaration. The values are checked against `MAX_INDEX` at compile time.
+///
+/// ```rust
+/// oxc_index::define_index_type! {
+///     pub struct Local = u32;
+///     CONSTANTS = { RETURN_PLACE = 0, FIRST_ARG = 1 };
+///     DEBUG_FORMAT = "_{}";
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Local::RETURN_PLACE.index(), 0);
+/// assert_eq!(format!("{:?}", Local::FIRST_ARG), "_1");
+/// # }
+/// ```
+///
+/// #### `DERIVES = [<derive>, ...];`
+///
+/// Replace the derives on the generated struct, which default to `Copy`,
+/// `Clone`, `PartialEq`, `Eq`, `Hash`, `PartialOrd` and `Ord`. Any of those
+/// which are left out have to be implemented by hand, as `Idx` requires all
+/// of them. Like `CONSTANTS`, this has to come directly after the struct
+/// declaration (or after `CONSTANTS`, if both are used).
+///
+/// ```rust
+/// oxc_index::define_index_type! {
+///     pub struct ScopeId = u32;
+///     DERIVES = [Copy, Clone, PartialEq, Eq, PartialOrd, Ord];
+/// }
+///
+/// // E.g. to hash just the low bits.
+/// impl core::hash::Hash for ScopeId {
+///     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+///         state.write_u16(self.raw() as u16);
+///     }
+/// }
+/// ```
 #[macro_export]
 macro_rules! define_index_type {
+    // named constants, forwarded to the arms below
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = $raw:ident;
+        CONSTANTS = { $($CONST_NAME:ident = $const_value:expr_2021),* $(,)? };
+        $($rest:tt)*
+    ) => {
+        $crate::define_index_type! {
+            $(#[$attrs])*
+            $v struct $type = $raw;
+            $($rest)*
+        }
+        impl $type {
+            $($v const $CONST_NAME: Self = Self::new($const_value);)*
+        }
+    };
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = $raw:ty;
+        CONSTANTS = { $($CONST_NAME:ident = $const_value:expr_2021),* $(,)? };
+        $($rest:tt)*
+    ) => {
+        $crate::define_index_type! {
+            $(#[$attrs])*
+            $v struct $type = $raw;
+            $($rest)*
+        }
+        impl $type {
+            $($v const $CONST_NAME: Self = Self::new($const_value);)*
+        }
+    };
+    // custom derive list, forwarded to the arms below
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = $raw:ident;
+        DERIVES = [$($derive:path),* $(,)?];
+        $($rest:tt)*
+    ) => {
+        $crate::define_index_type! {
+            @derives [$($derive),*]
+            $(#[$attrs])*
+            $v struct $type = $raw;
+            $($rest)*
+        }
+    };
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = $raw:ty;
+        DERIVES = [$($derive:path),* $(,)?];
+        $($rest:tt)*
+    ) => {
+        $crate::define_index_type! {
+            @derives [$($derive),*]
+            $(#[$attrs])*
+            $v struct $type = $raw;
+            $($rest)*
+        }
+    };
     // public api for primitive types (u8, u16, u32, usize, etc.)
     (
+        @derives [$($derive:path),*]
         $(#[$attrs:meta])*
         $v:vis struct $type:ident = $raw:ident;
         $($CONFIG_NAME:ident = $value:expr_2021;)* $(;)?
@@ -163,15 +375,19 @@ macro_rules! define_index_type {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
             @attrs [$(#[$attrs])*]
-            @derives [#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]]
+            @derives [#[derive($($derive),*)]]
             @decl [$v struct $type ($raw)]
             @debug_fmt ["{}"]
             @max [(<$raw>::max_value() as usize)]
             @no_check_max [false]
+            @arith [true]
+            @sentinel []
+            @ts_branded [false]
         }
     };
     // public api for complex types (NonMaxU32, etc.) - requires explicit MAX_INDEX
     (
+        @derives [$($derive:path),*]
         $(#[$attrs:meta])*
         $v:vis struct $type:ident = $raw:ty;
         $($CONFIG_NAME:ident = $value:expr_2021;)+ $(;)?
@@ -179,13 +395,99 @@ macro_rules! define_index_type {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
             @attrs [$(#[$attrs])*]
-            @derives [#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]]
+            @derives [#[derive($($derive),*)]]
             @decl [$v struct $type ($raw)]
             @debug_fmt ["{}"]
             @max [(usize::MAX)]
             @no_check_max [false]
+            @arith [true]
+            @sentinel []
+            @ts_branded [false]
+        }
+    };
+    // `#[cfg]`-gated options, resolved by `__define_index_type_cfg`
+    (
+        @derives [$($derive:path),*]
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = $raw:ident;
+        $($rest:tt)*
+    ) => {
+        $crate::__define_index_type_cfg!{
+            @head [@derives [$($derive),*] $(#[$attrs])* $v struct $type = $raw;]
+            @configs []
+            $($rest)*
+        }
+    };
+    (
+        @derives [$($derive:path),*]
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = $raw:ty;
+        $($rest:tt)*
+    ) => {
+        $crate::__define_index_type_cfg!{
+            @head [@derives [$($derive),*] $(#[$attrs])* $v struct $type = $raw;]
+            @configs []
+            $($rest)*
+        }
+    };
+    // default derive list
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $($rest:tt)*
+    ) => {
+        $crate::define_index_type! {
+            @derives [Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord]
+            $(#[$attrs])*
+            $v struct $($rest)*
+        }
+    };
+}
+
+/// Walks the options of a `define_index_type!` invocation. Every
+/// `#[cfg(pred)]`-gated option splits the expansion in two, one with the
+/// option kept under `#[cfg(pred)]` and one without it under
+/// `#[cfg(not(pred))]`, so the compiler picks the right one per target.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __define_index_type_cfg {
+    (
+        @head [$($head:tt)*]
+        @configs [$($configs:tt)*]
+        #[cfg($pred:meta)] $CONFIG_NAME:ident = $value:expr_2021;
+        $($rest:tt)*
+    ) => {
+        #[cfg($pred)]
+        $crate::__define_index_type_cfg!{
+            @head [$($head)*]
+            @configs [$($configs)* $CONFIG_NAME = $value;]
+            $($rest)*
+        }
+        #[cfg(not($pred))]
+        $crate::__define_index_type_cfg!{
+            @head [$($head)*]
+            @configs [$($configs)*]
+            $($rest)*
+        }
+    };
+    (
+        @head [$($head:tt)*]
+        @configs [$($configs:tt)*]
+        $CONFIG_NAME:ident = $value:expr_2021;
+        $($rest:tt)*
+    ) => {
+        $crate::__define_index_type_cfg!{
+            @head [$($head)*]
+            @configs [$($configs)* $CONFIG_NAME = $value;]
+            $($rest)*
         }
     };
+    (
+        @head [$($head:tt)*]
+        @configs [$($configs:tt)*]
+        $(;)?
+    ) => {
+        $crate::define_index_type!{ $($head)* $($configs)* }
+    };
 }
 
 #[macro_export]
@@ -242,54 +544,305 @@ macro_rules! define_nonmax_u32_index_type {
         $v:vis struct $type:ident;
         $($CONFIG_NAME:ident = $value:expr_2021;)* $(;)?
     ) => {
-        #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-        $(#[$attrs])*
-        $v struct $type($crate::nonmax::NonMaxU32);
-
-        impl $type {
-            /// The maximum representable index value.
-            $v const MAX_INDEX: usize = (u32::MAX - 1) as usize;
-
-            /// Whether this index type performs bounds checking.
-            $v const CHECKS_MAX_INDEX: bool = true;
+        $crate::__define_niche_index_type! {
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [$crate::nonmax::NonMaxU32, u32, 0]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [concat!(stringify!($type), "({})")]
+            @max [(<u32>::MAX - 1) as usize]
+            @no_check_max [false]
+            @ts_branded [false]
+        }
+    };
+}
 
-            /// Create a new index from a `usize` value.
-            ///
-            /// # Panics
-            /// Panics if `value > MAX_INDEX`.
-            #[inline(always)]
-            $v const fn new(value: usize) -> Self {
-                Self::from_usize(value)
-            }
+/// The shared body of the index types backed by a type with a niche. The raw
+/// value stores the index plus `$offset`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __define_niche_index_type {
+    // MAX_INDEX
+    (
+        @configs [(MAX_INDEX; $new_max:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @raw [$wrapper:ty, $prim:ident, $offset:literal]
+        @attrs [$(#[$attrs:meta])*]
+        @decl [$v:vis struct $type:ident]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$_old_max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
+        $crate::__define_niche_index_type!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [$wrapper, $prim, $offset]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [$dbg]
+            @max [$new_max]
+            @no_check_max [$no_check_max]
+            @ts_branded [$ts_branded]
+        }
+    };
+    // DISABLE_MAX_INDEX_CHECK
+    (
+        @configs [(DISABLE_MAX_INDEX_CHECK; $new_no_check_max:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @raw [$wrapper:ty, $prim:ident, $offset:literal]
+        @attrs [$(#[$attrs:meta])*]
+        @decl [$v:vis struct $type:ident]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$_old_no_check_max:expr_2021]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
+        $crate::__define_niche_index_type!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [$wrapper, $prim, $offset]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [$dbg]
+            @max [$max]
+            @no_check_max [$new_no_check_max]
+            @ts_branded [$ts_branded]
+        }
+    };
+    // TS_BRANDED
+    (
+        @configs [(TS_BRANDED; $new_ts_branded:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @raw [$wrapper:ty, $prim:ident, $offset:literal]
+        @attrs [$(#[$attrs:meta])*]
+        @decl [$v:vis struct $type:ident]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @ts_branded [$_old_ts_branded:expr_2021]
+    ) => {
+        $crate::__define_niche_index_type!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [$wrapper, $prim, $offset]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [$dbg]
+            @max [$max]
+            @no_check_max [$no_check_max]
+            @ts_branded [$new_ts_branded]
+        }
+    };
+    // DEBUG_FORMAT
+    (
+        @configs [(DEBUG_FORMAT; $new_dbg:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @raw [$wrapper:ty, $prim:ident, $offset:literal]
+        @attrs [$(#[$attrs:meta])*]
+        @decl [$v:vis struct $type:ident]
+        @debug_fmt [$_old_debug_fmt:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
+        $crate::__define_niche_index_type!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [$wrapper, $prim, $offset]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [$new_dbg]
+            @max [$max]
+            @no_check_max [$no_check_max]
+            @ts_branded [$ts_branded]
+        }
+    };
+    // DISPLAY_FORMAT
+    (
+        @configs [(DISPLAY_FORMAT; $format:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @raw [$wrapper:ty, $prim:ident, $offset:literal]
+        @attrs [$(#[$attrs:meta])*]
+        @decl [$v:vis struct $type:ident]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
+        $crate::__define_niche_index_type!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [$wrapper, $prim, $offset]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [$dbg]
+            @max [$max]
+            @no_check_max [$no_check_max]
+            @ts_branded [$ts_branded]
+        }
 
-            /// Create an index from a raw `NonMaxU32` value.
-            #[inline(always)]
-            $v const fn from_raw(value: $crate::nonmax::NonMaxU32) -> Self {
-                Self(value)
+        impl core::fmt::Display for $type {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, $format, self.index())
             }
+        }
+    };
+    // DEFAULT
+    (
+        @configs [(DEFAULT; $default_expr:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @raw [$wrapper:ty, $prim:ident, $offset:literal]
+        @attrs [$(#[$attrs:meta])*]
+        @decl [$v:vis struct $type:ident]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
+        $crate::__define_niche_index_type!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [$wrapper, $prim, $offset]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [$dbg]
+            @max [$max]
+            @no_check_max [$no_check_max]
+            @ts_branded [$ts_branded]
+        }
 
-            /// Convert an index from another index type.
-            #[inline(always)]
-            $v fn from_foreign<F: $crate::Idx>(value: F) -> Self {
-                Self::from_usize(value.index())
+        impl Default for $type {
+            #[inline]
+            fn default() -> Self {
+                $default_expr
             }
-
-            /// Create an index from a `usize` without bounds checking.
-            ///
-            /// # SAFETY
-            /// The caller must ensure `value < (u32::MAX as usize)`.
-            #[inline(always)]
+        }
+    };
+    // IMPL_RAW_CONVERSIONS
+    (
+        @configs [(IMPL_RAW_CONVERSIONS; $val:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @raw [$wrapper:ty, $prim:ident, $offset:literal]
+        @attrs [$(#[$attrs:meta])*]
+        @decl [$v:vis struct $type:ident]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
+        $crate::__define_niche_index_type!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [$wrapper, $prim, $offset]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [$dbg]
+            @max [$max]
+            @no_check_max [$no_check_max]
+            @ts_branded [$ts_branded]
+        }
+        // Ensure they passed in true, like for `define_index_type!`.
+        const _: [(); 1] = [(); $val as usize];
+
+        impl From<$wrapper> for $type {
+            #[inline]
+            fn from(value: $wrapper) -> Self {
+                Self::from_raw(value)
+            }
+        }
+
+        impl From<$type> for $wrapper {
+            #[inline]
+            fn from(v: $type) -> $wrapper {
+                v.raw()
+            }
+        }
+
+        impl From<$type> for $prim {
+            #[inline]
+            fn from(v: $type) -> $prim {
+                v.index() as $prim
+            }
+        }
+
+        impl TryFrom<$prim> for $type {
+            type Error = $crate::TryFromIdxError;
+
+            #[inline]
+            fn try_from(value: $prim) -> Result<Self, Self::Error> {
+                usize::try_from(value)
+                    .ok()
+                    .and_then(Self::try_from_usize)
+                    .ok_or($crate::TryFromIdxError)
+            }
+        }
+    };
+    // Try to make rust emit a decent error message...
+    (
+        @configs [($other:ident; $format:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @raw [$wrapper:ty, $prim:ident, $offset:literal]
+        @attrs [$(#[$attrs:meta])*]
+        @decl [$v:vis struct $type:ident]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
+        $crate::unknown_define_index_type_option!($other);
+    };
+    // finish
+    (
+        @configs []
+        @raw [$wrapper:ty, $prim:ident, $offset:literal]
+        @attrs [$(#[$attrs:meta])*]
+        @decl [$v:vis struct $type:ident]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
+        #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        $(#[$attrs])*
+        $v struct $type($wrapper);
+
+        // The unchecked constructors rely on this, so it holds even if
+        // `DISABLE_MAX_INDEX_CHECK` is set.
+        const _: () = assert!(
+            $type::MAX_INDEX <= ($prim::MAX - 1) as usize,
+            "MAX_INDEX doesn't fit in the niche-optimized raw type",
+        );
+
+        impl $type {
+            /// The maximum representable index value.
+            $v const MAX_INDEX: usize = $max;
+
+            /// Whether this index type performs bounds checking.
+            $v const CHECKS_MAX_INDEX: bool = !$no_check_max;
+
+            /// Create a new index from a `usize` value.
+            ///
+            /// # Panics
+            /// Panics if `value > MAX_INDEX`.
+            #[inline(always)]
+            $v const fn new(value: usize) -> Self {
+                Self::from_usize(value)
+            }
+
+            /// Create an index from the raw wrapped value.
+            #[inline(always)]
+            $v const fn from_raw(value: $wrapper) -> Self {
+                Self(value)
+            }
+
+            /// Convert an index from another index type.
+            #[inline(always)]
+            $v fn from_foreign<F: $crate::Idx>(value: F) -> Self {
+                Self::from_usize(value.index())
+            }
+
+            /// Create an index from a `usize` without bounds checking.
+            ///
+            /// # SAFETY
+            /// The caller must ensure `value <= MAX_INDEX`.
+            #[inline(always)]
             $v const unsafe fn from_usize_unchecked(value: usize) -> Self {
-                Self(unsafe { $crate::nonmax::NonMaxU32::new_unchecked(value as u32) })
+                Self(unsafe { <$wrapper>::new_unchecked(value as $prim + $offset) })
             }
 
-            /// Create an index from a raw `u32` without bounds checking.
+            /// Create an index from a raw integer without bounds checking.
             ///
             /// # SAFETY
-            /// The caller must ensure the value is not `u32::MAX`.
+            /// The caller must ensure `raw` is a valid value of the wrapped type.
             #[inline(always)]
-            $v const unsafe fn from_raw_unchecked(raw: u32) -> Self {
-                Self(unsafe { $crate::nonmax::NonMaxU32::new_unchecked(raw) })
+            $v const unsafe fn from_raw_unchecked(raw: $prim) -> Self {
+                Self(unsafe { <$wrapper>::new_unchecked(raw) })
             }
 
             /// Create an index from a `usize` with bounds checking.
@@ -299,24 +852,76 @@ macro_rules! define_nonmax_u32_index_type {
             #[inline]
             $v const fn from_usize(value: usize) -> Self {
                 Self::check_index(value);
-                match $crate::nonmax::NonMaxU32::new(value as u32) {
+                match <$wrapper>::new((value as $prim).wrapping_add($offset)) {
                     Some(raw) => Self(raw),
                     None => panic!("index_vec index overflow"),
                 }
             }
 
+            /// Create an index from a `usize`, or return `None` if
+            /// `value > MAX_INDEX`.
+            #[inline]
+            $v const fn try_from_usize(value: usize) -> Option<Self> {
+                if value <= Self::MAX_INDEX {
+                    // SAFETY: `MAX_INDEX` is below the raw type's `MAX`.
+                    Some(unsafe { Self::from_usize_unchecked(value) })
+                } else {
+                    None
+                }
+            }
+
             /// Get the index value as a `usize`.
             #[inline(always)]
             $v const fn index(self) -> usize {
-                self.0.get() as usize
+                (self.0.get() - $offset) as usize
+            }
+
+            /// Add `n` to this index, or return `None` if the result would be
+            /// above `MAX_INDEX`.
+            #[inline]
+            $v const fn checked_add(self, n: usize) -> Option<Self> {
+                match self.index().checked_add(n) {
+                    Some(value) => Self::try_from_usize(value),
+                    None => None,
+                }
+            }
+
+            /// Subtract `n` from this index, or return `None` if the result
+            /// would be below zero.
+            #[inline]
+            $v const fn checked_sub(self, n: usize) -> Option<Self> {
+                match self.index().checked_sub(n) {
+                    Some(value) => Self::try_from_usize(value),
+                    None => None,
+                }
             }
 
-            /// Get the raw `NonMaxU32` value.
+            /// Add `n` to this index, clamping the result to `MAX_INDEX`.
+            #[inline]
+            $v const fn saturating_add(self, n: usize) -> Self {
+                let value = self.index().saturating_add(n);
+                Self::from_usize(if value > Self::MAX_INDEX { Self::MAX_INDEX } else { value })
+            }
+
+            /// Subtract `n` from this index, clamping the result to zero.
+            #[inline]
+            $v const fn saturating_sub(self, n: usize) -> Self {
+                Self::from_usize(self.index().saturating_sub(n))
+            }
+
+            /// Get the raw wrapped value.
             #[inline(always)]
-            $v const fn raw(self) -> $crate::nonmax::NonMaxU32 {
+            $v const fn raw(self) -> $wrapper {
                 self.0
             }
 
+            /// Get an iterator over every valid index of this type, from `0` up
+            /// to and including `MAX_INDEX`.
+            #[inline]
+            $v fn all() -> impl DoubleEndedIterator<Item = Self> {
+                (0..=Self::MAX_INDEX).map(|i| unsafe { Self::from_usize_unchecked(i) })
+            }
+
             #[doc(hidden)]
             #[inline]
             $v const fn check_index(v: usize) {
@@ -328,7 +933,7 @@ macro_rules! define_nonmax_u32_index_type {
 
         impl core::fmt::Debug for $type {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                write!(f, "{}({})", stringify!($type), self.index())
+                write!(f, $dbg, self.index())
             }
         }
 
@@ -472,38 +1077,656 @@ macro_rules! define_nonmax_u32_index_type {
             }
         }
 
-        $crate::__internal_maybe_index_impl_serde!($type);
+        impl $crate::HasAtomic for $type {
+            type Atomic = <$prim as $crate::__RawAtomicFor>::Atomic;
+        }
+
+        $crate::__internal_maybe_index_impl_serde!($type);
+        $crate::__internal_maybe_index_impl_defmt!($type);
+        $crate::__internal_maybe_index_impl_nohash!($type);
+        $crate::__internal_maybe_index_impl_rkyv!($type, $prim);
+        $crate::__internal_maybe_index_impl_bincode!($type, $prim);
+        $crate::__internal_maybe_index_impl_speedy!($type, $prim);
+        $crate::__internal_maybe_index_impl_schemars!($type);
+        $crate::__internal_maybe_index_impl_arbitrary!($type);
+        $crate::__internal_maybe_index_impl_ts!($type, $ts_branded);
+        $crate::__internal_maybe_index_impl_napi!($type);
+    };
+}
+
+/// Generate the boilerplate for a newtyped index struct backed by one of the
+/// `NonMax*` types from the `nonmax` crate, which have the same size as the
+/// integer they wrap but leave a niche for `Option<Index>`. The raw type can be
+/// `u8`, `u16`, `u32` or `u64`, and valid indices range from `0` to its
+/// `MAX - 1`.
+///
+/// ```rust,ignore
+/// oxc_index::define_nonmax_index_type! {
+///     pub struct ScopeFlagsId = u16;
+/// }
+///
+/// assert_eq!(size_of::<Option<ScopeFlagsId>>(), 2);
+/// ```
+///
+/// Other than the raw type, this behaves like
+/// [`define_nonmax_u32_index_type!`], which is a shorthand for `= u32`.
+///
+/// Both take the `MAX_INDEX`, `DISABLE_MAX_INDEX_CHECK`, `DEBUG_FORMAT`,
+/// `DISPLAY_FORMAT` and `DEFAULT` options of [`define_index_type!`].
+/// `MAX_INDEX` can only lower the limit, and `Debug` defaults to printing the
+/// type name, e.g. `MyIndex(3)`.
+///
+/// `IMPL_RAW_CONVERSIONS = true;` implements `From` between the index type
+/// and its `NonMax*` type, `From<MyIndex>` for the integer type, and
+/// `TryFrom` the integer type, failing with
+/// [`TryFromIdxError`](crate::TryFromIdxError) above `MAX_INDEX`.
+#[cfg(feature = "nonmax")]
+#[macro_export]
+macro_rules! define_nonmax_index_type {
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = u8;
+        $($CONFIG_NAME:ident = $value:expr_2021;)* $(;)?
+    ) => {
+        $crate::__define_niche_index_type! {
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [$crate::nonmax::NonMaxU8, u8, 0]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [concat!(stringify!($type), "({})")]
+            @max [(<u8>::MAX - 1) as usize]
+            @no_check_max [false]
+            @ts_branded [false]
+        }
+    };
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = u16;
+        $($CONFIG_NAME:ident = $value:expr_2021;)* $(;)?
+    ) => {
+        $crate::__define_niche_index_type! {
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [$crate::nonmax::NonMaxU16, u16, 0]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [concat!(stringify!($type), "({})")]
+            @max [(<u16>::MAX - 1) as usize]
+            @no_check_max [false]
+            @ts_branded [false]
+        }
+    };
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = u32;
+        $($CONFIG_NAME:ident = $value:expr_2021;)* $(;)?
+    ) => {
+        $crate::__define_niche_index_type! {
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [$crate::nonmax::NonMaxU32, u32, 0]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [concat!(stringify!($type), "({})")]
+            @max [(<u32>::MAX - 1) as usize]
+            @no_check_max [false]
+            @ts_branded [false]
+        }
+    };
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = u64;
+        $($CONFIG_NAME:ident = $value:expr_2021;)* $(;)?
+    ) => {
+        $crate::__define_niche_index_type! {
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [$crate::nonmax::NonMaxU64, u64, 0]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [concat!(stringify!($type), "({})")]
+            @max [(<u64>::MAX - 1) as usize]
+            @no_check_max [false]
+            @ts_branded [false]
+        }
+    };
+}
+
+/// Generate the boilerplate for a newtyped index struct backed by one of the
+/// `core::num::NonZero*` types, storing `index + 1`. Like the
+/// `define_nonmax_*` macros this makes `Option<Index>` as small as the index
+/// itself, but without the `nonmax` dependency, at the cost of an increment
+/// and a decrement when converting from and to `usize`. The raw type can be
+/// `u8`, `u16`, `u32` or `u64`, and valid indices range from `0` to its
+/// `MAX - 1`.
+///
+/// ```rust
+/// oxc_index::define_nonzero_index_type! {
+///     pub struct NodeId = u32;
+/// }
+///
+/// # fn main() {
+/// assert_eq!(size_of::<Option<NodeId>>(), 4);
+/// assert_eq!(NodeId::new(0).raw().get(), 1);
+/// assert_eq!(NodeId::new(41).index(), 41);
+/// # }
+/// ```
+///
+/// It takes the same options as `define_nonmax_index_type!`. A `MAX_INDEX`
+/// which doesn't leave room for the offset is rejected at compile time:
+///
+/// ```rust,compile_fail
+/// oxc_index::define_nonzero_index_type! {
+///     pub struct TooBig = u8;
+///     MAX_INDEX = 255;
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_nonzero_index_type {
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = u8;
+        $($CONFIG_NAME:ident = $value:expr_2021;)* $(;)?
+    ) => {
+        $crate::__define_niche_index_type! {
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [core::num::NonZeroU8, u8, 1]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [concat!(stringify!($type), "({})")]
+            @max [(<u8>::MAX - 1) as usize]
+            @no_check_max [false]
+            @ts_branded [false]
+        }
+    };
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = u16;
+        $($CONFIG_NAME:ident = $value:expr_2021;)* $(;)?
+    ) => {
+        $crate::__define_niche_index_type! {
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [core::num::NonZeroU16, u16, 1]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [concat!(stringify!($type), "({})")]
+            @max [(<u16>::MAX - 1) as usize]
+            @no_check_max [false]
+            @ts_branded [false]
+        }
+    };
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = u32;
+        $($CONFIG_NAME:ident = $value:expr_2021;)* $(;)?
+    ) => {
+        $crate::__define_niche_index_type! {
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [core::num::NonZeroU32, u32, 1]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [concat!(stringify!($type), "({})")]
+            @max [(<u32>::MAX - 1) as usize]
+            @no_check_max [false]
+            @ts_branded [false]
+        }
+    };
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = u64;
+        $($CONFIG_NAME:ident = $value:expr_2021;)* $(;)?
+    ) => {
+        $crate::__define_niche_index_type! {
+            @configs [$(($CONFIG_NAME; $value))*]
+            @raw [core::num::NonZeroU64, u64, 1]
+            @attrs [$(#[$attrs])*]
+            @decl [$v struct $type]
+            @debug_fmt [concat!(stringify!($type), "({})")]
+            @max [(<u64>::MAX - 1) as usize]
+            @no_check_max [false]
+            @ts_branded [false]
+        }
+    };
+}
+
+#[cfg(feature = "serde")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_serde {
+    ($type:ident $(, $sentinel:expr_2021)?) => {
+        impl $crate::serde::ser::Serialize for $type {
+            fn serialize<S: $crate::serde::ser::Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                self.index().serialize(serializer)
+            }
+        }
+
+        impl<'de> $crate::serde::de::Deserialize<'de> for $type {
+            fn deserialize<D: $crate::serde::de::Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                let value = usize::deserialize(deserializer)?;
+                $(
+                    if value == $sentinel {
+                        return Ok(Self::NONE);
+                    }
+                )?
+                Ok(Self::from_usize(value))
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_serde {
+    ($type:ident $(, $sentinel:expr_2021)?) => {};
+}
+
+#[cfg(feature = "defmt")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_defmt {
+    ($type:ident) => {
+        impl $crate::defmt::Format for $type {
+            fn format(&self, fmt: $crate::defmt::Formatter<'_>) {
+                $crate::defmt::Format::format(&self.index(), fmt)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "defmt"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_defmt {
+    ($type:ident) => {};
+}
+
+// Only used for `define_index_type!`, whose struct is an integer wrapper. It
+// gets `repr(transparent)` here, only with the feature, so that without it a
+// user's own `repr` attribute still works. The niche types aren't `Pod`.
+#[cfg(feature = "bytemuck")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_bytemuck {
+    ($(#[$attrs:meta])* $v:vis struct $type:ident($raw:ty);) => {
+        $(#[$attrs])*
+        #[repr(transparent)]
+        $v struct $type($raw);
+
+        // SAFETY: `$type` is a `repr(transparent)` wrapper around an integer.
+        unsafe impl $crate::bytemuck::Zeroable for $type {}
+        // SAFETY: as above. Values above `MAX_INDEX` aren't rejected, but
+        // nothing relies on them being absent for soundness.
+        unsafe impl $crate::bytemuck::Pod for $type {}
+    };
+}
+
+#[cfg(not(feature = "bytemuck"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_bytemuck {
+    ($(#[$attrs:meta])* $v:vis struct $type:ident($raw:ty);) => {
+        $(#[$attrs])*
+        $v struct $type($raw);
+    };
+}
+
+// Archived as the integer type the index wraps, e.g. `u32_le` for a `u32`
+// index, through `ArchivedIdx`.
+#[cfg(feature = "rkyv")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_rkyv {
+    ($type:ident, $int:ty) => {
+        impl $crate::rkyv::Archive for $type {
+            type Archived = $crate::ArchivedIdx<$type, $crate::rkyv::Archived<$int>>;
+            type Resolver = ();
+
+            #[inline]
+            fn resolve(&self, _: (), out: $crate::rkyv::Place<Self::Archived>) {
+                $crate::ArchivedIdx::resolve_from(&(self.index() as $int), out);
+            }
+        }
+
+        impl<S: $crate::rkyv::rancor::Fallible + ?Sized> $crate::rkyv::Serialize<S> for $type {
+            #[inline]
+            fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "rkyv"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_rkyv {
+    ($type:ident, $int:ty) => {};
+}
+
+// Encoded as the integer type the index wraps, rather than as a `usize`, so
+// the encoding doesn't depend on the platform's pointer width.
+#[cfg(feature = "bincode")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_bincode {
+    ($type:ident, $int:ty $(, $sentinel:expr_2021)?) => {
+        impl $crate::bincode::Encode for $type {
+            #[inline]
+            fn encode<E: $crate::bincode::enc::Encoder>(
+                &self,
+                encoder: &mut E,
+            ) -> Result<(), $crate::bincode::error::EncodeError> {
+                $crate::bincode::Encode::encode(&(self.index() as $int), encoder)
+            }
+        }
+
+        impl<Context> $crate::bincode::Decode<Context> for $type {
+            #[inline]
+            fn decode<D: $crate::bincode::de::Decoder<Context = Context>>(
+                decoder: &mut D,
+            ) -> Result<Self, $crate::bincode::error::DecodeError> {
+                let raw = <$int as $crate::bincode::Decode<Context>>::decode(decoder)?;
+                let value = usize::try_from(raw).ok();
+                $(
+                    if value == Some($sentinel) {
+                        return Ok(Self::NONE);
+                    }
+                )?
+                value.and_then(<Self as $crate::Idx>::try_from_usize).ok_or(
+                    $crate::bincode::error::DecodeError::Other("index out of range for its type"),
+                )
+            }
+        }
+
+        $crate::bincode::impl_borrow_decode!($type);
+    };
+}
+
+#[cfg(not(feature = "bincode"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_bincode {
+    ($type:ident, $int:ty $(, $sentinel:expr_2021)?) => {};
+}
+
+// Written as the integer type the index wraps, like the bincode impl.
+#[cfg(feature = "speedy")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_speedy {
+    ($type:ident, $int:ty $(, $sentinel:expr_2021)?) => {
+        impl<C: $crate::speedy::Context> $crate::speedy::Writable<C> for $type {
+            #[inline]
+            fn write_to<W: ?Sized + $crate::speedy::Writer<C>>(
+                &self,
+                writer: &mut W,
+            ) -> Result<(), C::Error> {
+                writer.write_value(&(self.index() as $int))
+            }
+
+            #[inline]
+            fn bytes_needed(&self) -> Result<usize, C::Error> {
+                Ok(core::mem::size_of::<$int>())
+            }
+        }
+
+        impl<'a, C: $crate::speedy::Context> $crate::speedy::Readable<'a, C> for $type {
+            #[inline]
+            fn read_from<R: $crate::speedy::Reader<'a, C>>(
+                reader: &mut R,
+            ) -> Result<Self, C::Error> {
+                let raw: $int = reader.read_value()?;
+                let value = usize::try_from(raw).ok();
+                $(
+                    if value == Some($sentinel) {
+                        return Ok(Self::NONE);
+                    }
+                )?
+                value
+                    .and_then(<Self as $crate::Idx>::try_from_usize)
+                    .ok_or_else(|| {
+                        $crate::speedy::Error::custom("index out of range for its type").into()
+                    })
+            }
+
+            #[inline]
+            fn minimum_bytes_needed() -> usize {
+                core::mem::size_of::<$int>()
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "speedy"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_speedy {
+    ($type:ident, $int:ty $(, $sentinel:expr_2021)?) => {};
+}
+
+// A non-negative integer no greater than `MAX_INDEX`, matching the serde
+// impl.
+#[cfg(feature = "schemars")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_schemars {
+    ($type:ident) => {
+        impl $crate::schemars::JsonSchema for $type {
+            #[inline]
+            fn inline_schema() -> bool {
+                true
+            }
+
+            #[inline]
+            fn schema_name() -> $crate::__Cow<'static, str> {
+                $crate::__Cow::Borrowed(stringify!($type))
+            }
+
+            #[inline]
+            fn schema_id() -> $crate::__Cow<'static, str> {
+                $crate::__Cow::Borrowed(concat!(module_path!(), "::", stringify!($type)))
+            }
+
+            fn json_schema(_: &mut $crate::schemars::SchemaGenerator) -> $crate::schemars::Schema {
+                $crate::schemars::json_schema!({
+                    "type": "integer",
+                    "format": "uint",
+                    "minimum": 0,
+                    "maximum": $type::MAX_INDEX,
+                })
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "schemars"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_schemars {
+    ($type:ident) => {};
+}
+
+// Only ever produces valid indices, so never a sentinel or a niche type's
+// forbidden value.
+#[cfg(feature = "arbitrary")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_arbitrary {
+    ($type:ident) => {
+        impl<'a> $crate::arbitrary::Arbitrary<'a> for $type {
+            #[inline]
+            fn arbitrary(
+                u: &mut $crate::arbitrary::Unstructured<'a>,
+            ) -> $crate::arbitrary::Result<Self> {
+                u.int_in_range(0..=Self::MAX_INDEX).map(Self::from_usize)
+            }
+
+            #[inline]
+            fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+                (0, Some(core::mem::size_of::<usize>()))
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "arbitrary"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_arbitrary {
+    ($type:ident) => {};
+}
+
+// A plain `number`, like the integer types, unless `TS_BRANDED` is set. Then
+// it's a named alias of `number`, branded so that indices of different types
+// can't be mixed up on the TypeScript side either.
+#[cfg(feature = "ts-rs")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_ts {
+    ($type:ident, $branded:expr_2021) => {
+        impl $crate::ts_rs::TS for $type {
+            type WithoutGenerics = Self;
+            type OptionInnerType = Self;
+
+            fn name() -> $crate::__String {
+                if $branded { stringify!($type) } else { "number" }.into()
+            }
+
+            fn inline() -> $crate::__String {
+                if $branded {
+                    concat!("number & { readonly __brand: \"", stringify!($type), "\" }").into()
+                } else {
+                    <Self as $crate::ts_rs::TS>::name()
+                }
+            }
+
+            fn inline_flattened() -> $crate::__String {
+                panic!(concat!(stringify!($type), " cannot be flattened"))
+            }
+
+            fn decl() -> $crate::__String {
+                assert!($branded, concat!(stringify!($type), " cannot be declared"));
+                concat!(
+                    "type ",
+                    stringify!($type),
+                    " = number & { readonly __brand: \"",
+                    stringify!($type),
+                    "\" };"
+                )
+                .into()
+            }
+
+            fn decl_concrete() -> $crate::__String {
+                <Self as $crate::ts_rs::TS>::decl()
+            }
+
+            fn output_path() -> Option<$crate::__PathBuf> {
+                if $branded {
+                    Some($crate::__PathBuf::from(concat!(stringify!($type), ".ts")))
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "ts-rs"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_ts {
+    ($type:ident, $branded:expr_2021) => {};
+}
+
+// Passed as a JS number, with `NONE` as its sentinel value. Numbers which
+// aren't a valid index or the sentinel (fractional, negative or above
+// `MAX_INDEX`) are rejected with an `InvalidArg` error rather than a panic.
+#[cfg(feature = "napi")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_napi {
+    ($type:ident $(, $sentinel:expr_2021)?) => {
+        impl $crate::napi::bindgen_prelude::TypeName for $type {
+            fn type_name() -> &'static str {
+                stringify!($type)
+            }
+
+            fn value_type() -> $crate::napi::ValueType {
+                $crate::napi::ValueType::Number
+            }
+        }
+
+        impl $crate::napi::bindgen_prelude::ValidateNapiValue for $type {}
+
+        impl $crate::napi::bindgen_prelude::ToNapiValue for $type {
+            unsafe fn to_napi_value(
+                env: $crate::napi::sys::napi_env,
+                val: Self,
+            ) -> $crate::napi::Result<$crate::napi::sys::napi_value> {
+                // SAFETY: forwarded from our caller.
+                unsafe {
+                    $crate::napi::bindgen_prelude::ToNapiValue::to_napi_value(
+                        env,
+                        val.index() as f64,
+                    )
+                }
+            }
+        }
+
+        impl $crate::napi::bindgen_prelude::FromNapiValue for $type {
+            unsafe fn from_napi_value(
+                env: $crate::napi::sys::napi_env,
+                napi_val: $crate::napi::sys::napi_value,
+            ) -> $crate::napi::Result<Self> {
+                // SAFETY: forwarded from our caller.
+                let value: f64 = unsafe {
+                    $crate::napi::bindgen_prelude::FromNapiValue::from_napi_value(env, napi_val)?
+                };
+                // The cast saturates, and turns NaN into 0, so this only
+                // accepts whole numbers in range.
+                let idx = value as usize;
+                if idx as f64 == value {
+                    $(
+                        if idx == $sentinel {
+                            return Ok(Self::NONE);
+                        }
+                    )?
+                    if idx <= Self::MAX_INDEX {
+                        return Ok(Self::from_usize(idx));
+                    }
+                }
+                Err($crate::napi::Error::new(
+                    $crate::napi::Status::InvalidArg,
+                    concat!("expected a valid ", stringify!($type)),
+                ))
+            }
+        }
     };
 }
 
-#[cfg(feature = "serde")]
+#[cfg(not(feature = "napi"))]
 #[macro_export]
 #[doc(hidden)]
-macro_rules! __internal_maybe_index_impl_serde {
-    ($type:ident) => {
-        impl $crate::serde::ser::Serialize for $type {
-            fn serialize<S: $crate::serde::ser::Serializer>(
-                &self,
-                serializer: S,
-            ) -> Result<S::Ok, S::Error> {
-                self.index().serialize(serializer)
-            }
-        }
+macro_rules! __internal_maybe_index_impl_napi {
+    ($type:ident $(, $sentinel:expr_2021)?) => {};
+}
 
-        impl<'de> $crate::serde::de::Deserialize<'de> for $type {
-            fn deserialize<D: $crate::serde::de::Deserializer<'de>>(
-                deserializer: D,
-            ) -> Result<Self, D::Error> {
-                usize::deserialize(deserializer).map(Self::from_usize)
-            }
-        }
+#[cfg(feature = "nohash-hasher")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __internal_maybe_index_impl_nohash {
+    ($type:ident) => {
+        impl $crate::nohash_hasher::IsEnabled for $type {}
     };
 }
 
-#[cfg(not(feature = "serde"))]
+#[cfg(not(feature = "nohash-hasher"))]
 #[macro_export]
 #[doc(hidden)]
-macro_rules! __internal_maybe_index_impl_serde {
+macro_rules! __internal_maybe_index_impl_nohash {
     ($type:ident) => {};
 }
 
@@ -519,6 +1742,9 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr_2021]
         @max [$max:expr_2021]
         @no_check_max [$_old_no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
     ) => {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
@@ -528,6 +1754,9 @@ macro_rules! __define_index_type_inner {
             @debug_fmt [$dbg]
             @max [$max]
             @no_check_max [$no_check_max]
+            @arith [$arith]
+            @sentinel [$($sentinel)?]
+            @ts_branded [$ts_branded]
         }
     };
 
@@ -540,6 +1769,9 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr_2021]
         @max [$max:expr_2021]
         @no_check_max [$cm:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
     ) => {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
@@ -549,6 +1781,9 @@ macro_rules! __define_index_type_inner {
             @debug_fmt [$dbg]
             @max [$new_max]
             @no_check_max [$cm]
+            @arith [$arith]
+            @sentinel [$($sentinel)?]
+            @ts_branded [$ts_branded]
         }
     };
 
@@ -561,6 +1796,9 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr_2021]
         @max [$max:expr_2021]
         @no_check_max [$no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
     ) => {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
@@ -570,6 +1808,9 @@ macro_rules! __define_index_type_inner {
             @debug_fmt [$dbg]
             @max [$max]
             @no_check_max [$no_check_max]
+            @arith [$arith]
+            @sentinel [$($sentinel)?]
+            @ts_branded [$ts_branded]
         }
         impl Default for $type {
             #[inline]
@@ -588,6 +1829,9 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$old_dbg:expr_2021]
         @max [$max:expr_2021]
         @no_check_max [$no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
     ) => {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
@@ -597,6 +1841,9 @@ macro_rules! __define_index_type_inner {
             @debug_fmt [$dbg]
             @max [$max]
             @no_check_max [$no_check_max]
+            @arith [$arith]
+            @sentinel [$($sentinel)?]
+            @ts_branded [$ts_branded]
         }
     };
 
@@ -609,6 +1856,9 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr_2021]
         @max [$max:expr_2021]
         @no_check_max [$no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
     ) => {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
@@ -618,6 +1868,9 @@ macro_rules! __define_index_type_inner {
             @debug_fmt [$dbg]
             @max [$max]
             @no_check_max [$no_check_max]
+            @arith [$arith]
+            @sentinel [$($sentinel)?]
+            @ts_branded [$ts_branded]
         }
 
         impl core::fmt::Display for $type {
@@ -636,6 +1889,9 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr_2021]
         @max [$max:expr_2021]
         @no_check_max [$no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
     ) => {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
@@ -645,6 +1901,9 @@ macro_rules! __define_index_type_inner {
             @debug_fmt [$dbg]
             @max [$max]
             @no_check_max [$no_check_max]
+            @arith [$arith]
+            @sentinel [$($sentinel)?]
+            @ts_branded [$ts_branded]
         }
         // Ensure they passed in true. This is... cludgey.
         const _: [(); 1] = [(); $val as usize];
@@ -663,6 +1922,178 @@ macro_rules! __define_index_type_inner {
             }
         }
     };
+    // IMPL_STEP
+    (
+        @configs [(IMPL_STEP; $val:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @attrs [$(#[$attrs:meta])*]
+        @derives [$(#[$derive:meta])*]
+        @decl [$v:vis struct $type:ident ($raw:ty)]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
+        $crate::__define_index_type_inner!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @attrs [$(#[$attrs])*]
+            @derives [$(#[$derive])*]
+            @decl [$v struct $type ($raw)]
+            @debug_fmt [$dbg]
+            @max [$max]
+            @no_check_max [$no_check_max]
+            @arith [$arith]
+            @sentinel [$($sentinel)?]
+            @ts_branded [$ts_branded]
+        }
+        // Ensure they passed in true, like IMPL_RAW_CONVERSIONS.
+        const _: [(); 1] = [(); $val as usize];
+
+        impl core::iter::Step for $type {
+            #[inline]
+            fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+                <usize as core::iter::Step>::steps_between(&start.index(), &end.index())
+            }
+
+            #[inline]
+            fn forward_checked(start: Self, n: usize) -> Option<Self> {
+                start.index().checked_add(n).filter(|&i| i <= Self::MAX_INDEX).map(Self::from_usize)
+            }
+
+            #[inline]
+            fn backward_checked(start: Self, n: usize) -> Option<Self> {
+                start.index().checked_sub(n).map(Self::from_usize)
+            }
+        }
+    };
+    // NO_ARITHMETIC
+    (
+        @configs [(NO_ARITHMETIC; $val:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @attrs [$(#[$attrs:meta])*]
+        @derives [$(#[$derive:meta])*]
+        @decl [$v:vis struct $type:ident ($raw:ty)]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
+        $crate::__define_index_type_inner!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @attrs [$(#[$attrs])*]
+            @derives [$(#[$derive])*]
+            @decl [$v struct $type ($raw)]
+            @debug_fmt [$dbg]
+            @max [$max]
+            @no_check_max [$no_check_max]
+            @arith [false]
+            @sentinel [$($sentinel)?]
+            @ts_branded [$ts_branded]
+        }
+        // Ensure they passed in true, like IMPL_RAW_CONVERSIONS.
+        const _: [(); 1] = [(); $val as usize];
+    };
+    // SENTINEL
+    (
+        @configs [(SENTINEL; $new_sentinel:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @attrs [$(#[$attrs:meta])*]
+        @derives [$(#[$derive:meta])*]
+        @decl [$v:vis struct $type:ident ($raw:ty)]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($_old_sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
+        $crate::__define_index_type_inner!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @attrs [$(#[$attrs])*]
+            @derives [$(#[$derive])*]
+            @decl [$v struct $type ($raw)]
+            @debug_fmt [$dbg]
+            @max [$max]
+            @no_check_max [$no_check_max]
+            @arith [$arith]
+            @sentinel [$new_sentinel]
+            @ts_branded [$ts_branded]
+        }
+    };
+    // TS_BRANDED
+    (
+        @configs [(TS_BRANDED; $new_ts_branded:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @attrs [$(#[$attrs:meta])*]
+        @derives [$(#[$derive:meta])*]
+        @decl [$v:vis struct $type:ident ($raw:ty)]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$_old_ts_branded:expr_2021]
+    ) => {
+        $crate::__define_index_type_inner!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @attrs [$(#[$attrs])*]
+            @derives [$(#[$derive])*]
+            @decl [$v struct $type ($raw)]
+            @debug_fmt [$dbg]
+            @max [$max]
+            @no_check_max [$no_check_max]
+            @arith [$arith]
+            @sentinel [$($sentinel)?]
+            @ts_branded [$new_ts_branded]
+        }
+    };
+    // IMPL_FROM_STR
+    (
+        @configs [(IMPL_FROM_STR; $val:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
+        @attrs [$(#[$attrs:meta])*]
+        @derives [$(#[$derive:meta])*]
+        @decl [$v:vis struct $type:ident ($raw:ty)]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
+        $crate::__define_index_type_inner!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @attrs [$(#[$attrs])*]
+            @derives [$(#[$derive])*]
+            @decl [$v struct $type ($raw)]
+            @debug_fmt [$dbg]
+            @max [$max]
+            @no_check_max [$no_check_max]
+            @arith [$arith]
+            @sentinel [$($sentinel)?]
+            @ts_branded [$ts_branded]
+        }
+        // Ensure they passed in true, like IMPL_RAW_CONVERSIONS.
+        const _: [(); 1] = [(); $val as usize];
+
+        impl core::str::FromStr for $type {
+            type Err = $crate::ParseIdxError;
+
+            #[inline]
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let value: usize = s.parse().map_err($crate::ParseIdxError::Invalid)?;
+                Self::try_from_usize(value).ok_or($crate::ParseIdxError::TooLarge)
+            }
+        }
+
+        impl TryFrom<&str> for $type {
+            type Error = $crate::ParseIdxError;
+
+            #[inline]
+            fn try_from(s: &str) -> Result<Self, Self::Error> {
+                s.parse()
+            }
+        }
+    };
     // Try to make rust emit a decent error message...
     (
         @configs [($other:ident; $format:expr_2021) $(($CONFIG_NAME:ident; $value:expr_2021))*]
@@ -672,6 +2103,9 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr_2021]
         @max [$max:expr_2021]
         @no_check_max [$no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
     ) => {
         $crate::unknown_define_index_type_option!($other);
     };
@@ -684,13 +2118,66 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr_2021]
         @max [$max:expr_2021]
         @no_check_max [$no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
     ) => {
+        $crate::__internal_maybe_index_impl_bytemuck! {
+            $(#[$derive])*
+            $(#[$attrs])*
+            $v struct $type($raw);
+        }
 
-        $(#[$derive])*
-        $(#[$attrs])*
-        $v struct $type($raw);
+        $crate::__define_index_type_body!{
+            @decl [$v struct $type ($raw)]
+            @debug_fmt [$dbg]
+            @max [$max]
+            @no_check_max [$no_check_max]
+            @arith [$arith]
+            @sentinel [$($sentinel)?]
+            @ts_branded [$ts_branded]
+        }
+    };
+}
 
+/// Everything `define_index_type!` generates besides the struct itself. Also
+/// used by `#[derive(Idx)]`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __define_index_type_body {
+    (
+        @decl [$v:vis struct $type:ident ($raw:ty)]
+        @debug_fmt [$dbg:expr_2021]
+        @max [$max:expr_2021]
+        @no_check_max [$no_check_max:expr_2021]
+        @arith [$arith:tt]
+        @sentinel [$($sentinel:expr_2021)?]
+        @ts_branded [$ts_branded:expr_2021]
+    ) => {
         impl $type {
+            $(
+            /// The value reserved to mean "no index", see `SENTINEL`.
+            $v const NONE: Self = Self::from_usize_unchecked($sentinel);
+
+            /// Returns true unless this is [`Self::NONE`].
+            #[inline]
+            $v const fn is_some(self) -> bool {
+                self.index() != $sentinel
+            }
+
+            /// Returns true if this is [`Self::NONE`].
+            #[inline]
+            $v const fn is_none(self) -> bool {
+                !self.is_some()
+            }
+
+            /// Returns `None` for [`Self::NONE`], and the index otherwise.
+            #[inline]
+            $v const fn get(self) -> Option<Self> {
+                if self.is_some() { Some(self) } else { None }
+            }
+            )?
+
             /// If `Self::CHECKS_MAX_INDEX` is true, we'll assert if trying to
             /// produce a value larger than this in any of the ctors that don't
             /// have `unchecked` in their name.
@@ -739,18 +2226,69 @@ macro_rules! __define_index_type_inner {
                 Self(value as $raw)
             }
 
+            /// Construct this index type from a usize, or return `None` if
+            /// `value > MAX_INDEX`. This checks `value` even if
+            /// `CHECKS_MAX_INDEX` is false.
+            #[inline]
+            $v const fn try_from_usize(value: usize) -> Option<Self> {
+                if value <= Self::MAX_INDEX { Some(Self::from_usize_unchecked(value)) } else { None }
+            }
+
             /// Get the wrapped index as a usize.
             #[inline(always)]
             $v const fn index(self) -> usize {
                 self.0 as usize
             }
 
+            /// Add `n` to this index, or return `None` if the result would be
+            /// above `MAX_INDEX`.
+            #[inline]
+            $v const fn checked_add(self, n: usize) -> Option<Self> {
+                match self.index().checked_add(n) {
+                    Some(value) => Self::try_from_usize(value),
+                    None => None,
+                }
+            }
+
+            /// Subtract `n` from this index, or return `None` if the result
+            /// would be below zero.
+            #[inline]
+            $v const fn checked_sub(self, n: usize) -> Option<Self> {
+                match self.index().checked_sub(n) {
+                    Some(value) => Self::try_from_usize(value),
+                    None => None,
+                }
+            }
+
+            /// Add `n` to this index, clamping the result to `MAX_INDEX`.
+            #[inline]
+            $v const fn saturating_add(self, n: usize) -> Self {
+                let value = self.index().saturating_add(n);
+                Self::from_usize(if value > Self::MAX_INDEX { Self::MAX_INDEX } else { value })
+            }
+
+            /// Subtract `n` from this index, clamping the result to zero.
+            #[inline]
+            $v const fn saturating_sub(self, n: usize) -> Self {
+                Self::from_usize(self.index().saturating_sub(n))
+            }
+
             /// Get the wrapped index.
             #[inline(always)]
             $v const fn raw(self) -> $raw {
                 self.0
             }
 
+            /// Get an iterator over every valid index of this type, from `0` up
+            /// to and including `MAX_INDEX`.
+            ///
+            /// Mostly useful for types with a small `MAX_INDEX`, e.g. those used
+            /// in place of a dense enum.
+            #[inline]
+            $v fn all() -> impl DoubleEndedIterator<Item = Self> {
+                (0..=Self::MAX_INDEX).map(Self::from_usize_unchecked)
+            }
+
             #[doc(hidden)]
             #[inline]
             $v const fn check_index(v: usize) {
@@ -760,8 +2298,20 @@ macro_rules! __define_index_type_inner {
             }
         }
 
+        $(
+            const _: () = assert!(
+                $sentinel > $type::MAX_INDEX && $type::NONE.index() == $sentinel,
+                "SENTINEL has to be above MAX_INDEX, and fit in the raw type",
+            );
+        )?
+
         impl core::fmt::Debug for $type {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                $(
+                    if self.index() == $sentinel {
+                        return f.write_str("None");
+                    }
+                )?
                 write!(f, $dbg, self.index())
             }
         }
@@ -794,6 +2344,58 @@ macro_rules! __define_index_type_inner {
             }
         }
 
+        $crate::__define_index_type_arith!($arith $type);
+
+        impl $crate::Idx for $type {
+            const MAX: usize = Self::MAX_INDEX;
+
+            #[inline]
+            unsafe fn from_usize_unchecked(idx: usize) -> Self {
+                Self::from_usize_unchecked(idx)
+            }
+
+            #[inline]
+            fn index(self) -> usize {
+                usize::from(self)
+            }
+        }
+
+        impl From<$type> for usize {
+            #[inline]
+            fn from(v: $type) -> usize {
+                v.index()
+            }
+        }
+
+        impl From<usize> for $type {
+            #[inline]
+            fn from(value: usize) -> Self {
+                $type::from_usize(value)
+            }
+        }
+
+        impl $crate::HasAtomic for $type {
+            type Atomic = <$raw as $crate::__RawAtomicFor>::Atomic;
+        }
+
+        $crate::__internal_maybe_index_impl_serde!($type $(, $sentinel)?);
+        $crate::__internal_maybe_index_impl_defmt!($type);
+        $crate::__internal_maybe_index_impl_nohash!($type);
+        $crate::__internal_maybe_index_impl_rkyv!($type, $raw);
+        $crate::__internal_maybe_index_impl_bincode!($type, $raw $(, $sentinel)?);
+        $crate::__internal_maybe_index_impl_speedy!($type, $raw $(, $sentinel)?);
+        $crate::__internal_maybe_index_impl_schemars!($type);
+        $crate::__internal_maybe_index_impl_arbitrary!($type);
+        $crate::__internal_maybe_index_impl_ts!($type, $ts_branded);
+        $crate::__internal_maybe_index_impl_napi!($type $(, $sentinel)?);
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __define_index_type_arith {
+    (false $type:ident) => {};
+    (true $type:ident) => {
         impl core::ops::Add<usize> for $type {
             type Output = Self;
             #[inline]
@@ -883,35 +2485,5 @@ macro_rules! __define_index_type_inner {
                 *self = *self - other;
             }
         }
-
-        impl $crate::Idx for $type {
-            const MAX: usize = Self::MAX_INDEX;
-
-            #[inline]
-            unsafe fn from_usize_unchecked(idx: usize) -> Self {
-                Self::from_usize_unchecked(idx)
-            }
-
-            #[inline]
-            fn index(self) -> usize {
-                usize::from(self)
-            }
-        }
-
-        impl From<$type> for usize {
-            #[inline]
-            fn from(v: $type) -> usize {
-                v.index()
-            }
-        }
-
-        impl From<usize> for $type {
-            #[inline]
-            fn from(value: usize) -> Self {
-                $type::from_usize(value)
-            }
-        }
-
-        $crate::__internal_maybe_index_impl_serde!($type);
     };
 }
@@ -0,0 +1,195 @@
+use alloc::vec::Vec;
+use core::{fmt, ops::Range};
+
+use crate::{Idx, IndexSlice, IndexVec, Len, idxslice::permute_by_key};
+
+/// A compressed one-to-many map from an index type to values.
+///
+/// All the values are stored in one contiguous `Vec`, grouped by key, along
+/// with a table of where each key's group starts (the "compressed sparse row"
+/// layout). Compared to an `IndexVec<K, Vec<V>>`, this uses two allocations in
+/// total rather than one per key, at the cost of being immutable once built.
+///
+/// ```rust
+/// use oxc_index::{IndexMultiMap, Len};
+///
+/// oxc_index::define_index_type! {
+///     pub struct ScopeIdx = u32;
+/// }
+///
+/// let symbols = [("a", 1), ("b", 0), ("c", 1)];
+/// let by_scope = IndexMultiMap::group_by_key(Len::<ScopeIdx>::new(3), symbols, |&(_, s)| {
+///     ScopeIdx::new(s)
+/// });
+/// assert_eq!(by_scope[ScopeIdx::new(0)], [("b", 0)]);
+/// assert_eq!(by_scope[ScopeIdx::new(1)], [("a", 1), ("c", 1)]);
+/// assert!(by_scope[ScopeIdx::new(2)].is_empty());
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IndexMultiMap<K: Idx, V> {
+    starts: IndexVec<K, usize>,
+    values: Vec<V>,
+}
+
+impl<K: Idx, V> IndexMultiMap<K, V> {
+    /// Distribute `items` into one group per key in `len`, keyed by `key`. Each
+    /// group keeps its items in iteration order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` returns an index outside `len`.
+    pub fn group_by_key<J, F>(len: Len<K>, items: J, mut key: F) -> Self
+    where
+        J: IntoIterator<Item = V>,
+        F: FnMut(&V) -> K,
+    {
+        let mut values: Vec<V> = items.into_iter().collect();
+        let keys: Vec<usize> = values.iter().map(|v| key(v).index()).collect();
+
+        let mut counts = IndexVec::<K, usize>::new_for(len);
+        for &k in &keys {
+            assert!(k < len.get(), "key {} out of bounds for {}", k, len.get());
+            counts.raw[k] += 1;
+        }
+        let (starts, _) = counts.prefix_sum();
+        permute_by_key(&mut values, &keys, starts.raw.clone());
+        Self { starts, values }
+    }
+
+    /// Build from `(key, value)` pairs, with one group per key in `len`.
+    /// Each group keeps its values in iteration order.
+    ///
+    /// ```rust
+    /// use oxc_index::{IndexMultiMap, Len};
+    ///
+    /// oxc_index::define_index_type! {
+    ///     pub struct NodeIdx = u32;
+    /// }
+    ///
+    /// let edges = [(NodeIdx::new(0), "b"), (NodeIdx::new(2), "c"), (NodeIdx::new(0), "d")];
+    /// let children = IndexMultiMap::from_pairs(Len::<NodeIdx>::new(3), edges);
+    /// assert_eq!(children[NodeIdx::new(0)], ["b", "d"]);
+    /// assert_eq!(children[NodeIdx::new(2)], ["c"]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a key is outside `len`.
+    pub fn from_pairs<J: IntoIterator<Item = (K, V)>>(len: Len<K>, pairs: J) -> Self {
+        let IndexMultiMap { starts, values } = IndexMultiMap::group_by_key(len, pairs, |&(k, _)| k);
+        Self { starts, values: values.into_iter().map(|(_, v)| v).collect() }
+    }
+
+    #[inline]
+    fn range(&self, key: K) -> Range<usize> {
+        let k = key.index();
+        let end = self.starts.raw.get(k + 1).copied().unwrap_or(self.values.len());
+        self.starts.raw[k]..end
+    }
+
+    /// Get the values for `key`, or `None` if `key` is out of bounds.
+    #[inline]
+    pub fn get(&self, key: K) -> Option<&[V]> {
+        if key.index() < self.starts.len() { Some(&self.values[self.range(key)]) } else { None }
+    }
+
+    /// Get the values for `key` mutably, or `None` if `key` is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, key: K) -> Option<&mut [V]> {
+        if key.index() < self.starts.len() {
+            let range = self.range(key);
+            Some(&mut self.values[range])
+        } else {
+            None
+        }
+    }
+
+    /// The number of keys (including those without any values).
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Returns true if there are no keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// All values, grouped by key.
+    #[inline]
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    /// The offset into [`IndexMultiMap::values`] at which each key's group
+    /// starts.
+    #[inline]
+    pub fn starts(&self) -> &IndexSlice<K, [usize]> {
+        &self.starts
+    }
+
+    /// Iterate over every key along with its values.
+    #[inline]
+    pub fn iter_enumerated(&self) -> impl Iterator<Item = (K, &[V])> + '_ {
+        self.starts.indices().map(|k| (k, &self.values[self.range(k)]))
+    }
+}
+
+impl<K: Idx, V> core::ops::Index<K> for IndexMultiMap<K, V> {
+    type Output = [V];
+
+    #[inline]
+    fn index(&self, key: K) -> &[V] {
+        assert!(key.index() < self.len(), "key {} out of bounds for {}", key.index(), self.len());
+        &self.values[self.range(key)]
+    }
+}
+
+impl<K: Idx, V> FromIterator<(K, V)> for IndexMultiMap<K, V> {
+    /// Collect `(key, value)` pairs into a map whose keys end just past the
+    /// largest one. See [`IndexMultiMap::from_pairs`] to choose the keys.
+    fn from_iter<J: IntoIterator<Item = (K, V)>>(iter: J) -> Self {
+        let pairs: Vec<(K, V)> = iter.into_iter().collect();
+        let len = pairs.iter().map(|(k, _)| k.index() + 1).max().unwrap_or(0);
+        Self::from_pairs(Len::new(len), pairs)
+    }
+}
+
+impl<K: Idx, V: fmt::Debug> fmt::Debug for IndexMultiMap<K, V> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.iter_enumerated()).finish()
+    }
+}
+
+impl<K: Idx, T> IndexVec<K, Vec<T>> {
+    /// Distribute `items` into one `Vec` per key in `len`, keyed by `key`.
+    /// Each bucket keeps its items in iteration order.
+    ///
+    /// See [`IndexMultiMap::group_by_key`] for a version which stores every
+    /// item in a single allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` returns an index outside `len`.
+    pub fn group_by_key<J, F>(len: Len<K>, items: J, mut key: F) -> Self
+    where
+        J: IntoIterator<Item = T>,
+        F: FnMut(&T) -> K,
+    {
+        let mut buckets = Self::new_for(len);
+        for item in items {
+            buckets[key(&item)].push(item);
+        }
+        buckets
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<K: Idx, V: zeroize::Zeroize> zeroize::Zeroize for IndexMultiMap<K, V> {
+    /// Zeroes every value, leaving an empty map.
+    fn zeroize(&mut self) {
+        self.values.zeroize();
+        self.starts.zeroize();
+    }
+}
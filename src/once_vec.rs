@@ -0,0 +1,119 @@
+use core::cell::OnceCell;
+
+use crate::{Idx, IndexVec, Len};
+
+macro_rules! once_index_vec {
+    ($(#[$attrs:meta])* $name:ident, $cell:ty) => {
+        $(#[$attrs])*
+        pub struct $name<I: Idx, T> {
+            raw: IndexVec<I, $cell>,
+        }
+
+        impl<I: Idx, T> $name<I, T> {
+            /// Construct a table of `len` uninitialized slots.
+            #[inline]
+            pub fn new(len: usize) -> Self {
+                Self { raw: (0..len).map(|_| <$cell>::new()).collect() }
+            }
+
+            /// Construct a table with an uninitialized slot for every index in
+            /// `len`.
+            #[inline]
+            pub fn new_for(len: Len<I>) -> Self {
+                Self::new(len.get())
+            }
+
+            /// Returns the number of slots.
+            #[inline]
+            pub fn len(&self) -> usize {
+                self.raw.len()
+            }
+
+            /// Returns true if there are no slots.
+            #[inline]
+            pub fn is_empty(&self) -> bool {
+                self.raw.is_empty()
+            }
+
+            /// Get the value at `idx`, if it has been initialized.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `idx` is out of bounds.
+            #[inline]
+            pub fn get(&self, idx: I) -> Option<&T> {
+                self.raw[idx].get()
+            }
+
+            /// Get the value at `idx`, initializing it with `f` if it hasn't
+            /// been already. `f` is called at most once per slot.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `idx` is out of bounds.
+            #[inline]
+            pub fn get_or_init<F: FnOnce() -> T>(&self, idx: I, f: F) -> &T {
+                self.raw[idx].get_or_init(f)
+            }
+
+            /// Initialize the slot at `idx` with `value`.
+            ///
+            /// # Errors
+            ///
+            /// Gives `value` back if the slot was already initialized.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `idx` is out of bounds.
+            #[inline]
+            pub fn set(&self, idx: I, value: T) -> Result<(), T> {
+                self.raw[idx].set(value)
+            }
+
+            /// Consume the table, returning `Some` for every initialized slot.
+            #[inline]
+            pub fn into_index_vec(self) -> IndexVec<I, Option<T>> {
+                self.raw.into_iter().map(<$cell>::into_inner).collect()
+            }
+        }
+
+        impl<I: Idx, T: core::fmt::Debug> core::fmt::Debug for $name<I, T> {
+            fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Debug::fmt(&self.raw, fmt)
+            }
+        }
+    };
+}
+
+once_index_vec! {
+    /// A fixed-size table of lazily initialized slots keyed by an index type.
+    ///
+    /// Each slot is computed at most once, the first time it's asked for with
+    /// [`OnceIndexVec::get_or_init`]. This suits demand-driven analyses over a
+    /// known index space.
+    #[cfg_attr(feature = "std", doc = "See [`SyncOnceIndexVec`] for a thread-safe version.")]
+    ///
+    /// ```rust
+    /// use oxc_index::OnceIndexVec;
+    ///
+    /// oxc_index::define_index_type! {
+    ///     pub struct NodeIdx = u32;
+    /// }
+    ///
+    /// let depths = OnceIndexVec::<NodeIdx, u32>::new(4);
+    /// assert_eq!(depths.get(NodeIdx::new(2)), None);
+    /// assert_eq!(*depths.get_or_init(NodeIdx::new(2), || 7), 7);
+    /// assert_eq!(*depths.get_or_init(NodeIdx::new(2), || 8), 7);
+    /// ```
+    OnceIndexVec, OnceCell<T>
+}
+
+#[cfg(feature = "std")]
+once_index_vec! {
+    /// A thread-safe version of [`OnceIndexVec`], backed by
+    /// [`OnceLock`](std::sync::OnceLock). Requires the `std` feature.
+    ///
+    /// If several threads race to initialize the same slot, only one of them
+    /// runs its initializer; the others block until it is done.
+    SyncOnceIndexVec, std::sync::OnceLock<T>
+}
@@ -0,0 +1,135 @@
+use core::{fmt, mem::size_of};
+
+use crate::Idx;
+
+/// An `Option<I>` for index types with a niche, such as those defined with
+/// `define_nonmax_index_type!` or `define_nonzero_index_type!`, which is
+/// checked at compile time to be the same size as `I`.
+///
+/// Plain `Option<I>` is already that small for such types, but nothing stops
+/// a table of them from silently doubling in size when the index type is
+/// changed to one without a niche. Using an `OptionIdx<I>` for an `I` which
+/// doesn't have one is a compile error instead; use
+/// [`PackedOption`](crate::PackedOption) for those.
+///
+/// ```rust
+/// use oxc_index::{IndexVec, OptionIdx};
+///
+/// oxc_index::define_nonzero_index_type! {
+///     pub struct ScopeId = u32;
+/// }
+///
+/// let parents: IndexVec<ScopeId, OptionIdx<ScopeId>> =
+///     [OptionIdx::none(), OptionIdx::some(ScopeId::new(0))].into_iter().collect();
+/// assert_eq!(size_of_val(&parents.raw[0]), 4);
+/// assert_eq!(parents[ScopeId::new(1)].unwrap_or(ScopeId::new(9)), ScopeId::new(0));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct OptionIdx<I: Idx>(Option<I>);
+
+impl<I: Idx> OptionIdx<I> {
+    const HAS_NICHE: () = assert!(
+        size_of::<Option<I>>() == size_of::<I>(),
+        "`OptionIdx` needs an index type with a niche, use `PackedOption` instead",
+    );
+
+    /// Construct an empty `OptionIdx`.
+    #[inline]
+    pub const fn none() -> Self {
+        let () = Self::HAS_NICHE;
+        Self(None)
+    }
+
+    /// Construct an `OptionIdx` holding `idx`.
+    #[inline]
+    pub const fn some(idx: I) -> Self {
+        let () = Self::HAS_NICHE;
+        Self(Some(idx))
+    }
+
+    /// Returns true if this doesn't hold an index.
+    #[inline]
+    pub const fn is_none(self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns true if this holds an index.
+    #[inline]
+    pub const fn is_some(self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Unpack into a regular `Option`.
+    #[inline]
+    pub const fn expand(self) -> Option<I> {
+        self.0
+    }
+
+    /// Take the index out, leaving `none` in its place.
+    #[inline]
+    pub fn take(&mut self) -> Option<I> {
+        self.0.take()
+    }
+
+    /// Returns the index, or `default` if there is none.
+    #[inline]
+    pub fn unwrap_or(self, default: I) -> I {
+        self.0.unwrap_or(default)
+    }
+
+    /// Returns the index, or panics if there is none.
+    #[inline]
+    #[track_caller]
+    pub fn unwrap(self) -> I {
+        self.0.unwrap()
+    }
+
+    /// Returns the index, or panics with `msg` if there is none.
+    #[inline]
+    #[track_caller]
+    pub fn expect(self, msg: &str) -> I {
+        self.0.expect(msg)
+    }
+
+    /// Map the index, like `Option::map`.
+    #[inline]
+    pub fn map<U, F: FnOnce(I) -> U>(self, f: F) -> Option<U> {
+        self.0.map(f)
+    }
+}
+
+impl<I: Idx> Default for OptionIdx<I> {
+    #[inline]
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl<I: Idx> From<I> for OptionIdx<I> {
+    #[inline]
+    fn from(idx: I) -> Self {
+        Self::some(idx)
+    }
+}
+
+impl<I: Idx> From<Option<I>> for OptionIdx<I> {
+    #[inline]
+    fn from(idx: Option<I>) -> Self {
+        let () = Self::HAS_NICHE;
+        Self(idx)
+    }
+}
+
+impl<I: Idx> From<OptionIdx<I>> for Option<I> {
+    #[inline]
+    fn from(idx: OptionIdx<I>) -> Self {
+        idx.0
+    }
+}
+
+impl<I: Idx> fmt::Debug for OptionIdx<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(fmt)
+    }
+}
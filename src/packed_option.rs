@@ -0,0 +1,131 @@
+use core::fmt;
+
+use crate::Idx;
+
+/// An `Option<I>` which takes up the space of a single `I`, by reserving
+/// `I::MAX` to mean `None`.
+///
+/// `Option<u32>` is twice the size of a `u32`, so tables of optional indices
+/// built from ordinary `u32`-backed index types pay for the discriminant on
+/// every element. This packs the "none" case into the index instead, at the
+/// cost of `I::MAX` no longer being usable as an index. Storing it panics.
+///
+/// ```rust
+/// use oxc_index::PackedOption;
+///
+/// oxc_index::define_index_type! {
+///     pub struct ScopeId = u32;
+/// }
+///
+/// let mut parent = PackedOption::from(ScopeId::new(3));
+/// assert_eq!(size_of_val(&parent), 4);
+/// assert_eq!(parent.expand(), Some(ScopeId::new(3)));
+/// assert_eq!(parent.take(), Some(ScopeId::new(3)));
+/// assert!(parent.is_none());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PackedOption<I: Idx>(I);
+
+impl<I: Idx> PackedOption<I> {
+    /// Construct an empty `PackedOption`.
+    #[inline]
+    pub fn none() -> Self {
+        // SAFETY: `I::MAX` is in bounds by definition.
+        Self(unsafe { I::from_usize_unchecked(I::MAX) })
+    }
+
+    /// Construct a `PackedOption` holding `idx`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is `I::MAX`, which is reserved.
+    #[inline]
+    pub fn some(idx: I) -> Self {
+        assert!(idx.index() != I::MAX, "`I::MAX` is reserved for `PackedOption::none`");
+        Self(idx)
+    }
+
+    /// Returns true if this doesn't hold an index.
+    #[inline]
+    pub fn is_none(self) -> bool {
+        self.0.index() == I::MAX
+    }
+
+    /// Returns true if this holds an index.
+    #[inline]
+    pub fn is_some(self) -> bool {
+        !self.is_none()
+    }
+
+    /// Unpack into a regular `Option`.
+    #[inline]
+    pub fn expand(self) -> Option<I> {
+        if self.is_none() { None } else { Some(self.0) }
+    }
+
+    /// Take the index out, leaving `none` in its place.
+    #[inline]
+    pub fn take(&mut self) -> Option<I> {
+        core::mem::take(self).expand()
+    }
+
+    /// Replace the index with `idx`, returning the previous one.
+    #[inline]
+    pub fn replace(&mut self, idx: I) -> Option<I> {
+        core::mem::replace(self, Self::some(idx)).expand()
+    }
+
+    /// Returns the index, or panics with `msg` if there is none.
+    #[inline]
+    #[track_caller]
+    pub fn expect(self, msg: &str) -> I {
+        self.expand().expect(msg)
+    }
+
+    /// Returns the index, or panics if there is none.
+    #[inline]
+    #[track_caller]
+    pub fn unwrap(self) -> I {
+        self.expand().unwrap()
+    }
+
+    /// Map the index, like `Option::map`.
+    #[inline]
+    pub fn map<U, F: FnOnce(I) -> U>(self, f: F) -> Option<U> {
+        self.expand().map(f)
+    }
+}
+
+impl<I: Idx> Default for PackedOption<I> {
+    #[inline]
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl<I: Idx> From<I> for PackedOption<I> {
+    #[inline]
+    fn from(idx: I) -> Self {
+        Self::some(idx)
+    }
+}
+
+impl<I: Idx> From<Option<I>> for PackedOption<I> {
+    #[inline]
+    fn from(idx: Option<I>) -> Self {
+        idx.map_or_else(Self::none, Self::some)
+    }
+}
+
+impl<I: Idx> From<PackedOption<I>> for Option<I> {
+    #[inline]
+    fn from(packed: PackedOption<I>) -> Self {
+        packed.expand()
+    }
+}
+
+impl<I: Idx> fmt::Debug for PackedOption<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.expand().fmt(fmt)
+    }
+}
@@ -0,0 +1,42 @@
+use core::{fmt, num::ParseIntError};
+
+/// The error returned when parsing an index type generated with
+/// `IMPL_FROM_STR = true;` fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseIdxError {
+    /// The string isn't a valid unsigned integer.
+    Invalid(ParseIntError),
+    /// The value is above the type's `MAX_INDEX`.
+    TooLarge,
+}
+
+impl fmt::Display for ParseIdxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(err) => write!(f, "invalid index: {err}"),
+            Self::TooLarge => f.write_str("index is out of range for the index type"),
+        }
+    }
+}
+
+impl core::error::Error for ParseIdxError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Invalid(err) => Some(err),
+            Self::TooLarge => None,
+        }
+    }
+}
+
+/// The error returned by the `TryFrom` conversions from a raw integer, when
+/// the value is above the index type's `MAX_INDEX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromIdxError;
+
+impl fmt::Display for TryFromIdxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("index is out of range for the index type")
+    }
+}
+
+impl core::error::Error for TryFromIdxError {}
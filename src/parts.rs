@@ -0,0 +1,227 @@
+use alloc::vec::Vec;
+use core::{fmt, ops::Range};
+
+use crate::{Idx, IndexVec};
+
+/// An owned, contiguous region of an [`IndexVec`], which remembers the index
+/// it started at.
+///
+/// Produced by [`IndexVec::into_parts`] and [`IndexVec::split_at_owned`], so
+/// that regions of a table can be moved to worker threads (instead of
+/// borrowing shards for a scoped section), and reassembled afterwards with
+/// [`IndexVec::from_parts`]. A part is indexed with the indices of the
+/// original table.
+///
+/// ```rust
+/// use oxc_index::{IndexVec, index_vec};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let v: IndexVec<NodeIdx, u32> = index_vec![0, 1, 2, 3, 4];
+/// let handles: Vec<_> = v
+///     .into_parts(2)
+///     .into_iter()
+///     .map(|mut part| {
+///         std::thread::spawn(move || {
+///             for (idx, x) in part.iter_mut_enumerated() {
+///                 *x += idx.raw() * 10;
+///             }
+///             part
+///         })
+///     })
+///     .collect();
+/// let v = IndexVec::from_parts(handles.into_iter().map(|h| h.join().unwrap()));
+/// assert_eq!(v, [0, 11, 22, 33, 44]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IndexVecPart<I: Idx, T> {
+    start: I,
+    raw: Vec<T>,
+}
+
+impl<I: Idx, T> IndexVecPart<I, T> {
+    /// The index of our first element in the original table.
+    #[inline]
+    pub fn start(&self) -> I {
+        self.start
+    }
+
+    /// The index just past our last element in the original table.
+    #[inline]
+    pub fn end(&self) -> I {
+        I::from_usize(self.start.index() + self.raw.len())
+    }
+
+    /// The range of original indices this part covers.
+    #[inline]
+    pub fn range(&self) -> Range<I> {
+        self.start..self.end()
+    }
+
+    /// Returns the number of elements in this part.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.raw.len()
+    }
+
+    /// Returns true if this part has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+
+    #[inline]
+    fn offset(&self, idx: I) -> Option<usize> {
+        idx.index().checked_sub(self.start.index()).filter(|&i| i < self.raw.len())
+    }
+
+    /// Get a ref to the element at `idx` of the original table, or `None` if
+    /// it isn't in this part.
+    #[inline]
+    pub fn get(&self, idx: I) -> Option<&T> {
+        self.offset(idx).map(|i| &self.raw[i])
+    }
+
+    /// Get a mut ref to the element at `idx` of the original table, or `None`
+    /// if it isn't in this part.
+    #[inline]
+    pub fn get_mut(&mut self, idx: I) -> Option<&mut T> {
+        self.offset(idx).map(|i| &mut self.raw[i])
+    }
+
+    /// Iterate over our elements along with their original indices.
+    #[inline]
+    pub fn iter_enumerated(&self) -> impl DoubleEndedIterator<Item = (I, &T)> + ExactSizeIterator {
+        let start = self.start.index();
+        self.raw.iter().enumerate().map(move |(i, t)| (I::from_usize(start + i), t))
+    }
+
+    /// Iterate over mut refs to our elements along with their original
+    /// indices.
+    #[inline]
+    pub fn iter_mut_enumerated(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = (I, &mut T)> + ExactSizeIterator {
+        let start = self.start.index();
+        self.raw.iter_mut().enumerate().map(move |(i, t)| (I::from_usize(start + i), t))
+    }
+
+    /// Returns the underlying elements.
+    #[inline(always)]
+    pub fn as_raw_slice(&self) -> &[T] {
+        &self.raw
+    }
+
+    /// Returns the underlying elements.
+    #[inline(always)]
+    pub fn as_raw_slice_mut(&mut self) -> &mut [T] {
+        &mut self.raw
+    }
+
+    /// Unwrap the elements, forgetting where they came from.
+    #[inline]
+    pub fn into_raw(self) -> Vec<T> {
+        self.raw
+    }
+}
+
+impl<I: Idx, T> core::ops::Index<I> for IndexVecPart<I, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, idx: I) -> &T {
+        match self.get(idx) {
+            Some(value) => value,
+            None => panic!("index {} out of bounds for part {:?}", idx.index(), self.range()),
+        }
+    }
+}
+
+impl<I: Idx, T> core::ops::IndexMut<I> for IndexVecPart<I, T> {
+    #[inline]
+    fn index_mut(&mut self, idx: I) -> &mut T {
+        let range = self.range();
+        match self.get_mut(idx) {
+            Some(value) => value,
+            None => panic!("index {} out of bounds for part {:?}", idx.index(), range),
+        }
+    }
+}
+
+impl<I: Idx, T: fmt::Debug> fmt::Debug for IndexVecPart<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("IndexVecPart")
+            .field("start", &self.start)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<I: Idx, T: zeroize::Zeroize> zeroize::Zeroize for IndexVecPart<I, T> {
+    #[inline]
+    fn zeroize(&mut self) {
+        self.raw.zeroize();
+    }
+}
+
+impl<I: Idx, T> IndexVec<I, T> {
+    /// Split into `n` owned parts of (nearly) equal length, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn into_parts(mut self, n: usize) -> Vec<IndexVecPart<I, T>> {
+        assert!(n > 0, "can't split into zero parts");
+        let (base, extra) = (self.len() / n, self.len() % n);
+        let mut parts = Vec::with_capacity(n);
+        // Split from the back, so each element is only moved once.
+        for i in (0..n).rev() {
+            let start = i * base + i.min(extra);
+            let raw = self.raw.split_off(start);
+            parts.push(IndexVecPart { start: I::from_usize(start), raw });
+        }
+        parts.reverse();
+        parts
+    }
+
+    /// Split into two owned parts, the first holding the indices before `idx`
+    /// and the second the rest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx > len`.
+    #[inline]
+    pub fn split_at_owned(mut self, idx: I) -> (IndexVecPart<I, T>, IndexVecPart<I, T>) {
+        let second = self.raw.split_off(idx.index());
+        (
+            IndexVecPart { start: I::from_usize(0), raw: self.raw },
+            IndexVecPart { start: idx, raw: second },
+        )
+    }
+
+    /// Reassemble a table from parts produced by [`IndexVec::into_parts`] or
+    /// [`IndexVec::split_at_owned`]. The parts may be given in any order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parts don't exactly cover `0..len` between them.
+    pub fn from_parts<It: IntoIterator<Item = IndexVecPart<I, T>>>(parts: It) -> Self {
+        let mut parts: Vec<_> = parts.into_iter().collect();
+        parts.sort_by_key(|part| part.start);
+        let mut vec = Self::with_capacity(parts.iter().map(IndexVecPart::len).sum());
+        for part in parts {
+            assert_eq!(
+                part.start.index(),
+                vec.len(),
+                "parts don't cover the table contiguously: expected a part at {}, found one at {}",
+                vec.len(),
+                part.start.index()
+            );
+            vec.raw.extend(part.raw);
+        }
+        vec
+    }
+}
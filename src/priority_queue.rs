@@ -0,0 +1,207 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Idx, IndexVec};
+
+/// A max-priority queue of indices, which can change the priority of an index
+/// already in the queue.
+///
+/// This is a binary heap plus an `IndexVec` recording where each index sits
+/// in the heap, so [`IndexPriorityQueue::change_priority`] and
+/// [`IndexPriorityQueue::remove`] are O(log n) instead of a linear search.
+/// Each index is in the queue at most once. Indices with equal priority are
+/// popped in an unspecified order.
+///
+/// ```rust
+/// use oxc_index::IndexPriorityQueue;
+///
+/// oxc_index::define_index_type! {
+///     pub struct BlockIdx = u32;
+/// }
+///
+/// let mut worklist = IndexPriorityQueue::new();
+/// worklist.push(BlockIdx::new(0), 1);
+/// worklist.push(BlockIdx::new(1), 5);
+/// worklist.push(BlockIdx::new(2), 3);
+/// worklist.change_priority(BlockIdx::new(0), 9);
+/// assert_eq!(worklist.pop(), Some((BlockIdx::new(0), 9)));
+/// assert_eq!(worklist.pop(), Some((BlockIdx::new(1), 5)));
+/// ```
+#[derive(Clone)]
+pub struct IndexPriorityQueue<I: Idx, P: Ord> {
+    heap: Vec<(I, P)>,
+    positions: IndexVec<I, Option<usize>>,
+}
+
+impl<I: Idx, P: Ord> IndexPriorityQueue<I, P> {
+    /// Construct an empty queue.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { heap: Vec::new(), positions: IndexVec::new() }
+    }
+
+    /// Returns the number of indices in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if the queue is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    #[inline]
+    fn position(&self, idx: I) -> Option<usize> {
+        self.positions.get(idx).copied().flatten()
+    }
+
+    /// Returns true if `idx` is in the queue.
+    #[inline]
+    pub fn contains(&self, idx: I) -> bool {
+        self.position(idx).is_some()
+    }
+
+    /// Get the priority of `idx`, if it's in the queue.
+    #[inline]
+    pub fn priority(&self, idx: I) -> Option<&P> {
+        self.position(idx).map(|pos| &self.heap[pos].1)
+    }
+
+    /// Get the index with the highest priority, without removing it.
+    #[inline]
+    pub fn peek(&self) -> Option<(I, &P)> {
+        self.heap.first().map(|(idx, priority)| (*idx, priority))
+    }
+
+    /// Add `idx` with `priority`. If `idx` is already in the queue, its
+    /// priority is changed instead, and the old one returned.
+    pub fn push(&mut self, idx: I, priority: P) -> Option<P> {
+        if self.contains(idx) {
+            return self.change_priority(idx, priority);
+        }
+        if idx.index() >= self.positions.len() {
+            self.positions.resize(idx.index() + 1, None);
+        }
+        let pos = self.heap.len();
+        self.heap.push((idx, priority));
+        self.positions[idx] = Some(pos);
+        self.sift_up(pos);
+        None
+    }
+
+    /// Remove and return the index with the highest priority.
+    pub fn pop(&mut self) -> Option<(I, P)> {
+        self.remove_at(0)
+    }
+
+    /// Change the priority of `idx`, returning the old one. Returns `None`
+    /// and does nothing if `idx` isn't in the queue.
+    pub fn change_priority(&mut self, idx: I, priority: P) -> Option<P> {
+        let pos = self.position(idx)?;
+        let old = core::mem::replace(&mut self.heap[pos].1, priority);
+        if self.heap[pos].1 > old {
+            self.sift_up(pos);
+        } else {
+            self.sift_down(pos);
+        }
+        Some(old)
+    }
+
+    /// Remove `idx` from the queue, returning its priority.
+    pub fn remove(&mut self, idx: I) -> Option<P> {
+        let pos = self.position(idx)?;
+        self.remove_at(pos).map(|(_, priority)| priority)
+    }
+
+    /// Remove every index.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.positions.clear();
+    }
+
+    fn remove_at(&mut self, pos: usize) -> Option<(I, P)> {
+        if pos >= self.heap.len() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(pos, last);
+        let (idx, priority) = self.heap.pop()?;
+        self.positions[idx] = None;
+        if pos < self.heap.len() {
+            // The element moved into `pos` came from the bottom, but `pos`
+            // may be in another subtree, so it could need to go either way.
+            self.sift_up(pos);
+            self.sift_down(pos);
+        }
+        Some((idx, priority))
+    }
+
+    #[inline]
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions[self.heap[a].0] = Some(a);
+        self.positions[self.heap[b].0] = Some(b);
+    }
+
+    fn sift_up(&mut self, mut pos: usize) {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if self.heap[pos].1 <= self.heap[parent].1 {
+                break;
+            }
+            self.swap(pos, parent);
+            pos = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut pos: usize) {
+        loop {
+            let (left, right) = (2 * pos + 1, 2 * pos + 2);
+            let mut largest = pos;
+            if left < self.heap.len() && self.heap[left].1 > self.heap[largest].1 {
+                largest = left;
+            }
+            if right < self.heap.len() && self.heap[right].1 > self.heap[largest].1 {
+                largest = right;
+            }
+            if largest == pos {
+                break;
+            }
+            self.swap(pos, largest);
+            pos = largest;
+        }
+    }
+}
+
+impl<I: Idx, P: Ord> Default for IndexPriorityQueue<I, P> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, P: Ord> Extend<(I, P)> for IndexPriorityQueue<I, P> {
+    /// Push every `(index, priority)` pair yielded by `iter`.
+    fn extend<J: IntoIterator<Item = (I, P)>>(&mut self, iter: J) {
+        for (idx, priority) in iter {
+            self.push(idx, priority);
+        }
+    }
+}
+
+impl<I: Idx, P: Ord> FromIterator<(I, P)> for IndexPriorityQueue<I, P> {
+    fn from_iter<J: IntoIterator<Item = (I, P)>>(iter: J) -> Self {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<I: Idx, P: Ord + fmt::Debug> fmt::Debug for IndexPriorityQueue<I, P> {
+    /// Formats the queue's contents in heap order.
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.heap.iter().map(|(idx, priority)| (idx, priority))).finish()
+    }
+}
@@ -0,0 +1,131 @@
+use core::{fmt, iter::FusedIterator, ops::Range};
+
+use crate::Idx;
+
+/// A half-open range of indices, `start..end`, which iterates as `I`.
+///
+/// `core::ops::Range<I>` can't be iterated, since `Step` is unstable, so
+/// walking a block of typed indices means going through `usize` and
+/// re-wrapping each one. `IdxRange` does that for you. It can also be used to
+/// slice an [`IndexSlice`](crate::IndexSlice) or [`IndexVec`](crate::IndexVec).
+///
+/// ```rust
+/// use oxc_index::{IdxRange, IndexVec, index_vec};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let nodes: IndexVec<NodeIdx, &str> = index_vec!["a", "b", "c", "d"];
+/// let children = IdxRange::new(NodeIdx::new(1), NodeIdx::new(3));
+/// assert_eq!(children.len(), 2);
+/// assert!(children.contains(NodeIdx::new(2)));
+/// assert_eq!(nodes[children.clone()], ["b", "c"]);
+/// for id in children {
+///     assert_ne!(nodes[id], "a");
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IdxRange<I: Idx> {
+    /// The first index in the range.
+    pub start: I,
+    /// The index just past the end of the range.
+    pub end: I,
+}
+
+impl<I: Idx> IdxRange<I> {
+    /// Construct the range `start..end`.
+    #[inline]
+    pub const fn new(start: I, end: I) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the number of indices in the range.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.end.index().saturating_sub(self.start.index())
+    }
+
+    /// Returns true if the range contains no indices.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Returns true if `idx` is in the range.
+    #[inline]
+    pub fn contains(&self, idx: I) -> bool {
+        self.start <= idx && idx < self.end
+    }
+}
+
+impl<I: Idx> Iterator for IdxRange<I> {
+    type Item = I;
+
+    #[inline]
+    fn next(&mut self) -> Option<I> {
+        if self.start >= self.end {
+            return None;
+        }
+        let idx = self.start;
+        self.start = I::from_usize(idx.index() + 1);
+        Some(idx)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<I> {
+        if n >= self.len() {
+            self.start = self.end;
+            return None;
+        }
+        self.start = I::from_usize(self.start.index() + n);
+        self.next()
+    }
+}
+
+impl<I: Idx> DoubleEndedIterator for IdxRange<I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<I> {
+        if self.start >= self.end {
+            return None;
+        }
+        self.end = I::from_usize(self.end.index() - 1);
+        Some(self.end)
+    }
+}
+
+impl<I: Idx> ExactSizeIterator for IdxRange<I> {}
+
+impl<I: Idx> FusedIterator for IdxRange<I> {}
+
+impl<I: Idx> From<Range<I>> for IdxRange<I> {
+    #[inline]
+    fn from(range: Range<I>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl<I: Idx> From<IdxRange<I>> for Range<I> {
+    #[inline]
+    fn from(range: IdxRange<I>) -> Self {
+        range.start..range.end
+    }
+}
+
+impl<I: Idx> fmt::Debug for IdxRange<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{:?}..{:?}", self.start, self.end)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<I: Idx + defmt::Format> defmt::Format for IdxRange<I> {
+    fn format(&self, fmt: defmt::Formatter<'_>) {
+        defmt::write!(fmt, "{}..{}", self.start, self.end);
+    }
+}
@@ -0,0 +1,240 @@
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    marker::PhantomData,
+    ops::{Range, RangeInclusive},
+};
+
+use crate::Idx;
+
+/// A set of indices stored as sorted, coalesced ranges.
+///
+/// Overlapping and adjacent ranges are merged as they're inserted, so the set
+/// is as small as its number of contiguous runs. For domains made up of long
+/// runs of indices (e.g. the nodes of a subtree, numbered in pre-order) that
+/// is much more compact than a bitset.
+///
+/// ```rust
+/// use oxc_index::IndexRangeSet;
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let mut set = IndexRangeSet::new();
+/// set.insert_range(NodeIdx::new(0)..NodeIdx::new(10));
+/// set.insert_range(NodeIdx::new(10)..NodeIdx::new(20));
+/// set.insert_range(NodeIdx::new(30)..NodeIdx::new(40));
+/// assert!(set.contains(NodeIdx::new(15)));
+/// assert!(!set.contains(NodeIdx::new(25)));
+/// assert_eq!(
+///     set.iter_ranges().collect::<Vec<_>>(),
+///     [NodeIdx::new(0)..=NodeIdx::new(19), NodeIdx::new(30)..=NodeIdx::new(39)],
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IndexRangeSet<I: Idx> {
+    /// Sorted, non-empty, and neither overlapping nor adjacent. Stored as
+    /// `usize` so that a range containing the maximum index can end past it.
+    ranges: Vec<Range<usize>>,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx> IndexRangeSet<I> {
+    /// Construct an empty set.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { ranges: Vec::new(), _marker: PhantomData }
+    }
+
+    /// Returns the number of disjoint ranges in the set.
+    #[inline]
+    pub fn num_ranges(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Count the indices in the set.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+
+    /// Returns true if the set has no indices.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns true if `idx` is in the set.
+    #[inline]
+    pub fn contains(&self, idx: I) -> bool {
+        let idx = idx.index();
+        let pos = self.ranges.partition_point(|r| r.end <= idx);
+        self.ranges.get(pos).is_some_and(|r| r.start <= idx)
+    }
+
+    /// Insert `idx`, returning true if it was not already present.
+    #[inline]
+    pub fn insert(&mut self, idx: I) -> bool {
+        self.insert_usize(idx.index()..idx.index() + 1)
+    }
+
+    /// Insert every index in `range`, returning true if any was not already
+    /// present.
+    #[inline]
+    pub fn insert_range(&mut self, range: Range<I>) -> bool {
+        self.insert_usize(range.start.index()..range.end.index())
+    }
+
+    fn insert_usize(&mut self, range: Range<usize>) -> bool {
+        if range.start >= range.end {
+            return false;
+        }
+        // Everything from `first` up to `last` overlaps or touches `range`.
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        if first < last {
+            let (start, end) = (self.ranges[first].start, self.ranges[last - 1].end);
+            if first + 1 == last && start <= range.start && range.end <= end {
+                return false;
+            }
+            let merged = start.min(range.start)..end.max(range.end);
+            self.ranges.splice(first..last, [merged]);
+        } else {
+            self.ranges.insert(first, range);
+        }
+        true
+    }
+
+    /// Remove `idx`, returning true if it was present.
+    #[inline]
+    pub fn remove(&mut self, idx: I) -> bool {
+        self.remove_usize(idx.index()..idx.index() + 1)
+    }
+
+    /// Remove every index in `range`, returning true if any was present.
+    #[inline]
+    pub fn remove_range(&mut self, range: Range<I>) -> bool {
+        self.remove_usize(range.start.index()..range.end.index())
+    }
+
+    fn remove_usize(&mut self, range: Range<usize>) -> bool {
+        if range.start >= range.end {
+            return false;
+        }
+        // Everything from `first` up to `last` overlaps `range`.
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first >= last {
+            return false;
+        }
+        let (start, end) = (self.ranges[first].start, self.ranges[last - 1].end);
+        let left = (start < range.start).then_some(start..range.start);
+        let right = (range.end < end).then_some(range.end..end);
+        self.ranges.splice(first..last, left.into_iter().chain(right));
+        true
+    }
+
+    /// Remove every index.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    /// Iterate over the disjoint ranges making up the set, in ascending order.
+    ///
+    /// The ranges are inclusive, so that one containing the maximum index of
+    /// `I` can still be represented.
+    #[inline]
+    pub fn iter_ranges(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = RangeInclusive<I>> + ExactSizeIterator + '_ {
+        self.ranges.iter().map(|r| I::from_usize(r.start)..=I::from_usize(r.end - 1))
+    }
+
+    /// Iterate over the indices in the set, in ascending order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = I> + '_ {
+        self.ranges.iter().flat_map(|r| r.clone().map(I::from_usize))
+    }
+
+    fn combine(&mut self, other: &Self, op: impl Fn(bool, bool) -> bool) -> bool {
+        let mut points: Vec<usize> =
+            self.ranges.iter().chain(&other.ranges).flat_map(|r| [r.start, r.end]).collect();
+        points.sort_unstable();
+        points.dedup();
+
+        // Every range boundary is in `points`, so each segment between two
+        // consecutive points is either entirely in a set or entirely out.
+        let (mut a, mut b) = (0, 0);
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for segment in points.windows(2) {
+            let (start, end) = (segment[0], segment[1]);
+            while self.ranges.get(a).is_some_and(|r| r.end <= start) {
+                a += 1;
+            }
+            while other.ranges.get(b).is_some_and(|r| r.end <= start) {
+                b += 1;
+            }
+            let in_a = self.ranges.get(a).is_some_and(|r| r.start <= start);
+            let in_b = other.ranges.get(b).is_some_and(|r| r.start <= start);
+            if op(in_a, in_b) {
+                match ranges.last_mut() {
+                    Some(last) if last.end == start => last.end = end,
+                    _ => ranges.push(start..end),
+                }
+            }
+        }
+        let changed = ranges != self.ranges;
+        self.ranges = ranges;
+        changed
+    }
+
+    /// Add every index in `other`, returning true if `self` changed.
+    #[inline]
+    pub fn union(&mut self, other: &Self) -> bool {
+        self.combine(other, |a, b| a || b)
+    }
+
+    /// Remove every index not in `other`, returning true if `self` changed.
+    #[inline]
+    pub fn intersect(&mut self, other: &Self) -> bool {
+        self.combine(other, |a, b| a && b)
+    }
+
+    /// Remove every index in `other`, returning true if `self` changed.
+    #[inline]
+    pub fn difference(&mut self, other: &Self) -> bool {
+        self.combine(other, |a, b| a && !b)
+    }
+}
+
+impl<I: Idx> Default for IndexRangeSet<I> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx> Extend<Range<I>> for IndexRangeSet<I> {
+    /// Insert every range yielded by `iter`.
+    fn extend<J: IntoIterator<Item = Range<I>>>(&mut self, iter: J) {
+        for range in iter {
+            self.insert_range(range);
+        }
+    }
+}
+
+impl<I: Idx> FromIterator<Range<I>> for IndexRangeSet<I> {
+    fn from_iter<J: IntoIterator<Item = Range<I>>>(iter: J) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<I: Idx> fmt::Debug for IndexRangeSet<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_set().entries(self.ranges.iter()).finish()
+    }
+}
@@ -17,7 +17,7 @@ use rayon::{
     slice::{Iter, IterMut, ParallelSlice, ParallelSliceMut},
 };
 
-use crate::{Idx, IndexVec};
+use crate::{Idx, IndexSlice, IndexVec};
 
 impl<'data, I: Idx, T: Sync + 'data> IntoParallelIterator for &'data IndexVec<I, T> {
     type Item = &'data T;
@@ -37,6 +37,103 @@ impl<'data, I: Idx, T: Send + 'data> IntoParallelIterator for &'data mut IndexVe
     }
 }
 
+impl<'data, I: Idx, T: Sync + 'data> IntoParallelIterator for &'data IndexSlice<I, [T]> {
+    type Item = &'data T;
+    type Iter = Iter<'data, T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        <&[T]>::into_par_iter(&self.raw)
+    }
+}
+
+impl<'data, I: Idx, T: Send + 'data> IntoParallelIterator for &'data mut IndexSlice<I, [T]> {
+    type Item = &'data mut T;
+    type Iter = IterMut<'data, T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        <&mut [T]>::into_par_iter(&mut self.raw)
+    }
+}
+
+impl<I: Idx + Send, T: Sync> IndexSlice<I, [T]> {
+    /// Parallel version of [`IndexSlice::iter_enumerated`], yielding `(I, &T)`.
+    #[inline]
+    pub fn par_iter_enumerated(&self) -> impl IndexedParallelIterator<Item = (I, &T)> {
+        <&[T]>::into_par_iter(&self.raw).enumerate().map(|(i, t)| (I::from_usize(i), t))
+    }
+}
+
+impl<I: Idx + Send, T: Send> IndexSlice<I, [T]> {
+    /// Parallel version of [`IndexSlice::iter_mut_enumerated`], yielding
+    /// `(I, &mut T)`.
+    #[inline]
+    pub fn par_iter_mut_enumerated(&mut self) -> impl IndexedParallelIterator<Item = (I, &mut T)> {
+        <&mut [T]>::into_par_iter(&mut self.raw).enumerate().map(|(i, t)| (I::from_usize(i), t))
+    }
+}
+
+impl<I: Idx, T: Send> IndexVec<I, T> {
+    /// Construct a vector of `len` elements, calling `f` with each index in
+    /// parallel. The results are assembled in index order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is too large for `I`.
+    pub fn par_from_fn<F>(len: usize, f: F) -> Self
+    where
+        F: Fn(I) -> T + Sync + Send,
+    {
+        let _ = I::from_usize(len);
+        let raw: Vec<T> = (0..len).into_par_iter().map(|i| f(I::from_usize(i))).collect();
+        IndexVec::from_vec(raw)
+    }
+
+    /// Parallel version of [`IndexVec::into_iter_enumerated`], yielding
+    /// `(I, T)`.
+    #[inline]
+    pub fn into_par_iter_enumerated(self) -> impl IndexedParallelIterator<Item = (I, T)>
+    where
+        I: Send,
+    {
+        self.into_par_iter().enumerate().map(|(i, t)| (I::from_usize(i), t))
+    }
+}
+
+impl<I: Idx> IndexSlice<I, [usize]> {
+    /// Parallel version of [`IndexSlice::prefix_sum`].
+    ///
+    /// The counts are split into one chunk per thread, each chunk is summed in
+    /// parallel, and then the offsets of each chunk are filled in parallel.
+    pub fn par_prefix_sum(&self) -> (IndexVec<I, usize>, usize) {
+        let chunk_size = self.len().div_ceil(rayon::current_num_threads()).max(1);
+        let chunk_totals: Vec<usize> =
+            self.raw.par_chunks(chunk_size).map(|chunk| chunk.iter().sum()).collect();
+
+        let mut total = 0;
+        let chunk_starts: Vec<usize> = chunk_totals
+            .iter()
+            .map(|&n| {
+                let start = total;
+                total += n;
+                start
+            })
+            .collect();
+
+        let mut offsets = alloc::vec![0; self.len()];
+        offsets
+            .par_chunks_mut(chunk_size)
+            .zip(self.raw.par_chunks(chunk_size))
+            .zip(chunk_starts)
+            .for_each(|((out, counts), mut start)| {
+                for (out, &n) in out.iter_mut().zip(counts) {
+                    *out = start;
+                    start += n;
+                }
+            });
+        (IndexVec::from_vec(offsets), total)
+    }
+}
+
 /// Parallel iterator that moves out of a vector.
 #[derive(Debug, Clone)]
 pub struct IntoIter<T: Send> {
@@ -0,0 +1,154 @@
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use rkyv::{
+    Archive, Deserialize, Place, Portable, Serialize,
+    primitive::{ArchivedU16, ArchivedU32, ArchivedU64},
+    rancor::{Fallible, Source},
+    ser::{Allocator, Writer},
+    vec::{ArchivedVec, VecResolver},
+};
+
+use crate::{Idx, IndexVec, TryFromIdxError};
+
+/// The archived form of an index type: the archived integer it wraps (e.g. a
+/// `u32_le` for a `u32` index), with the index type attached.
+///
+/// ```rust
+/// use oxc_index::rkyv::{self, Archived, rancor::Error};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let bytes = rkyv::to_bytes::<Error>(&NodeIdx::new(7)).unwrap();
+/// assert_eq!(bytes.len(), 4);
+/// // SAFETY: `bytes` was just produced by `to_bytes` for this type.
+/// let archived = unsafe { rkyv::access_unchecked::<Archived<NodeIdx>>(&bytes) };
+/// assert_eq!(archived.get(), NodeIdx::new(7));
+/// ```
+#[repr(transparent)]
+pub struct ArchivedIdx<I, A> {
+    raw: A,
+    _marker: PhantomData<fn(&I)>,
+}
+
+// SAFETY: `ArchivedIdx` is a `repr(transparent)` wrapper around `A`.
+unsafe impl<I, A: Portable> Portable for ArchivedIdx<I, A> {}
+
+/// An archived unsigned integer, which an [`ArchivedIdx`] can wrap.
+#[doc(hidden)]
+pub trait ArchivedRaw: Portable {
+    fn to_usize(&self) -> usize;
+}
+
+impl ArchivedRaw for u8 {
+    #[inline]
+    fn to_usize(&self) -> usize {
+        usize::from(*self)
+    }
+}
+
+macro_rules! impl_archived_raw {
+    ($($archived:ty),*) => {$(
+        impl ArchivedRaw for $archived {
+            #[inline]
+            fn to_usize(&self) -> usize {
+                self.to_native() as usize
+            }
+        }
+    )*};
+}
+
+impl_archived_raw!(ArchivedU16, ArchivedU32, ArchivedU64);
+
+impl<I: Idx, A: ArchivedRaw> ArchivedIdx<I, A> {
+    /// Get the index.
+    ///
+    /// Panics if the archived value is too large for the index type. See
+    /// [`ArchivedIdx::try_get`] for a fallible version.
+    #[inline]
+    pub fn get(&self) -> I {
+        I::from_usize(self.raw.to_usize())
+    }
+
+    /// Get the index, or `None` if the archived value is too large for the
+    /// index type.
+    #[inline]
+    pub fn try_get(&self) -> Option<I> {
+        I::try_from_usize(self.raw.to_usize())
+    }
+
+    /// Get the archived integer.
+    #[inline]
+    pub fn raw(&self) -> &A {
+        &self.raw
+    }
+
+    /// Used by `define_index_type!` to resolve an index from its raw value.
+    #[doc(hidden)]
+    #[inline]
+    pub fn resolve_from<R: Archive<Archived = A, Resolver = ()>>(raw: &R, out: Place<Self>) {
+        // SAFETY: `ArchivedIdx` is a `repr(transparent)` wrapper around `A`.
+        raw.resolve((), unsafe { out.cast_unchecked() });
+    }
+}
+
+/// Out of range values, e.g. from a corrupt archive, are an error rather
+/// than a panic.
+impl<I: Idx, A: ArchivedRaw, D: Fallible + ?Sized> Deserialize<I, D> for ArchivedIdx<I, A>
+where
+    D::Error: Source,
+{
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<I, D::Error> {
+        self.try_get().ok_or_else(|| D::Error::new(TryFromIdxError))
+    }
+}
+
+impl<I, A: PartialEq> PartialEq for ArchivedIdx<I, A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw
+    }
+}
+
+impl<I, A: Eq> Eq for ArchivedIdx<I, A> {}
+
+impl<I: Idx, A: ArchivedRaw> fmt::Debug for ArchivedIdx<I, A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.get(), fmt)
+    }
+}
+
+/// Archived exactly like a `Vec<T>`, so an archived table is an
+/// `ArchivedVec` of the archived elements.
+impl<I: Idx, T: Archive> Archive for IndexVec<I, T> {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedVec::resolve_from_slice(self.raw.as_slice(), resolver, out);
+    }
+}
+
+impl<I: Idx, T: Serialize<S>, S: Fallible + Allocator + Writer + ?Sized> Serialize<S>
+    for IndexVec<I, T>
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedVec::<T::Archived>::serialize_from_slice(self.raw.as_slice(), serializer)
+    }
+}
+
+impl<I: Idx, T: Archive, D: Fallible + ?Sized> Deserialize<IndexVec<I, T>, D>
+    for ArchivedVec<T::Archived>
+where
+    ArchivedVec<T::Archived>: Deserialize<Vec<T>, D>,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<IndexVec<I, T>, D::Error> {
+        Deserialize::<Vec<T>, D>::deserialize(self, deserializer).map(IndexVec::from_vec)
+    }
+}
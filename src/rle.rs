@@ -0,0 +1,208 @@
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    marker::PhantomData,
+    ops::{Bound, Range, RangeBounds},
+};
+
+use crate::{Idx, IdxRangeBounds, IndexVec};
+
+/// A run-length encoded table keyed by an index type.
+///
+/// Consecutive equal values are stored once, along with the index where their
+/// run ends. Lookups binary search the runs, so they're `O(log r)` for `r`
+/// runs. This suits huge, mostly-constant tables (e.g. mapping every byte of
+/// a file to some ID) where dense storage would waste memory.
+///
+/// ```rust
+/// use oxc_index::RleIndexVec;
+///
+/// oxc_index::define_index_type! {
+///     pub struct ByteIdx = u32;
+/// }
+///
+/// let mut file_ids = RleIndexVec::<ByteIdx, u8>::new();
+/// file_ids.extend_with(1000, 0);
+/// file_ids.extend_with(500, 1);
+/// assert_eq!(file_ids.len(), 1500);
+/// assert_eq!(file_ids.run_count(), 2);
+/// assert_eq!(file_ids.get(ByteIdx::new(999)), Some(&0));
+/// assert_eq!(file_ids.get(ByteIdx::new(1000)), Some(&1));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RleIndexVec<I: Idx, T: Eq> {
+    /// The exclusive end of each run, strictly increasing.
+    ends: Vec<usize>,
+    values: Vec<T>,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx, T: Eq> RleIndexVec<I, T> {
+    /// Construct an empty table.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { ends: Vec::new(), values: Vec::new(), _marker: PhantomData }
+    }
+
+    /// Returns the number of elements (not runs).
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ends.last().copied().unwrap_or(0)
+    }
+
+    /// Returns true if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Returns the number of runs.
+    #[inline]
+    pub fn run_count(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Gives the next index that will be assigned when `push` is called.
+    #[inline]
+    pub fn next_idx(&self) -> I {
+        I::from_usize(self.len())
+    }
+
+    /// Append `count` copies of `value`, extending the last run if it holds an
+    /// equal value.
+    pub fn extend_with(&mut self, count: usize, value: T) {
+        if count == 0 {
+            return;
+        }
+        let end = self.len() + count;
+        let _ = I::from_usize(end);
+        if self.values.last() == Some(&value) {
+            *self.ends.last_mut().unwrap() = end;
+        } else {
+            self.ends.push(end);
+            self.values.push(value);
+        }
+    }
+
+    /// Append a value, returning its index.
+    #[inline]
+    pub fn push(&mut self, value: T) -> I {
+        let idx = self.next_idx();
+        self.extend_with(1, value);
+        idx
+    }
+
+    #[inline]
+    fn run_of(&self, idx: usize) -> usize {
+        self.ends.partition_point(|&end| end <= idx)
+    }
+
+    /// Get a ref to the value at `idx`, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, idx: I) -> Option<&T> {
+        self.values.get(self.run_of(idx.index()))
+    }
+
+    /// Iterate over the runs as `(range, value)` pairs.
+    #[inline]
+    pub fn runs(&self) -> impl Iterator<Item = (Range<I>, &T)> + '_ {
+        self.ends.iter().zip(&self.values).scan(0, |start, (&end, value)| {
+            let range = I::from_usize(*start)..I::from_usize(end);
+            *start = end;
+            Some((range, value))
+        })
+    }
+
+    /// Iterate over the runs overlapping `range`, clipped to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` goes past the end of the table.
+    pub fn runs_in<R: IdxRangeBounds<I>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = (Range<I>, &T)> + '_ {
+        let range = range.into_range();
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i + 1,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len(),
+        };
+        assert!(start <= end && end <= self.len(), "range out of bounds for {}", self.len());
+        let first = self.run_of(start);
+        let mut run_start = start;
+        self.ends[first..].iter().zip(&self.values[first..]).map_while(move |(&run_end, value)| {
+            if run_start >= end {
+                return None;
+            }
+            let clipped = I::from_usize(run_start)..I::from_usize(run_end.min(end));
+            run_start = run_end;
+            Some((clipped, value))
+        })
+    }
+
+    /// Iterate over every element, expanding the runs.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.runs().flat_map(|(range, value)| {
+            core::iter::repeat_n(value, range.end.index() - range.start.index())
+        })
+    }
+
+    /// Expand into a dense [`IndexVec`].
+    #[inline]
+    pub fn to_index_vec(&self) -> IndexVec<I, T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<I: Idx, T: Eq> Default for RleIndexVec<I, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, T: Eq> core::ops::Index<I> for RleIndexVec<I, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, idx: I) -> &T {
+        match self.get(idx) {
+            Some(value) => value,
+            None => panic!("index {} out of bounds for {}", idx.index(), self.len()),
+        }
+    }
+}
+
+impl<I: Idx, T: Eq> Extend<T> for RleIndexVec<I, T> {
+    #[inline]
+    fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<I: Idx, T: Eq> FromIterator<T> for RleIndexVec<I, T> {
+    #[inline]
+    fn from_iter<J: IntoIterator<Item = T>>(iter: J) -> Self {
+        let mut rle = Self::new();
+        rle.extend(iter);
+        rle
+    }
+}
+
+impl<I: Idx, T: Eq + fmt::Debug> fmt::Debug for RleIndexVec<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_list().entries(self.runs()).finish()
+    }
+}
@@ -0,0 +1,324 @@
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use crate::{Idx, IndexVec};
+
+const NIL: usize = usize::MAX;
+
+struct Node<T> {
+    value: Option<T>,
+    left: usize,
+    right: usize,
+    size: usize,
+    priority: u64,
+}
+
+/// A sequence addressed by an index type, with `O(log n)` insertion and
+/// removal anywhere in the sequence.
+///
+/// Where [`IndexVec::insert`] and [`IndexVec::remove`] have to shift every
+/// element after the edit, `IndexRope` stores its elements in a balanced tree
+/// (an implicit treap) so that edits in the middle stay cheap. The price is
+/// that reads are `O(log n)` too, and the elements aren't contiguous. This
+/// suits edit-heavy workloads such as incremental reparsing; for anything
+/// else, prefer `IndexVec`.
+///
+/// Like with a `Vec`, inserting or removing shifts the index of every later
+/// element by one.
+///
+/// ```rust
+/// use oxc_index::IndexRope;
+///
+/// oxc_index::define_index_type! {
+///     pub struct TokenIdx = u32;
+/// }
+///
+/// let mut tokens: IndexRope<TokenIdx, &str> = ["let", "=", "1"].into_iter().collect();
+/// tokens.insert(TokenIdx::new(1), "x");
+/// assert_eq!(tokens[TokenIdx::new(1)], "x");
+/// assert_eq!(tokens.remove(TokenIdx::new(0)), "let");
+/// assert_eq!(tokens.iter().copied().collect::<Vec<_>>(), ["x", "=", "1"]);
+/// ```
+pub struct IndexRope<I: Idx, T> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    root: usize,
+    seed: u64,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx, T> IndexRope<I, T> {
+    /// Construct an empty rope.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: NIL,
+            seed: 0x2545_f491_4f6c_dd1d,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    /// Returns true if there are no elements.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.root == NIL
+    }
+
+    /// Gives the index the next call to [`IndexRope::push`] will return.
+    #[inline]
+    pub fn next_idx(&self) -> I {
+        I::from_usize(self.len())
+    }
+
+    /// Get a ref to the element at `idx`, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, idx: I) -> Option<&T> {
+        let node = self.find(idx.index())?;
+        self.nodes[node].value.as_ref()
+    }
+
+    /// Get a mut ref to the element at `idx`, or `None` if out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, idx: I) -> Option<&mut T> {
+        let node = self.find(idx.index())?;
+        self.nodes[node].value.as_mut()
+    }
+
+    /// Append an element, returning its index.
+    #[inline]
+    pub fn push(&mut self, value: T) -> I {
+        let idx = self.next_idx();
+        let node = self.alloc(value);
+        self.root = self.merge(self.root, node);
+        idx
+    }
+
+    /// Insert an element at `idx`, shifting all the elements after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx > len`.
+    pub fn insert(&mut self, idx: I, value: T) {
+        let pos = idx.index();
+        assert!(pos <= self.len(), "insertion index {} out of bounds for {}", pos, self.len());
+        let (left, right) = self.split(self.root, pos);
+        let node = self.alloc(value);
+        let left = self.merge(left, node);
+        self.root = self.merge(left, right);
+    }
+
+    /// Remove and return the element at `idx`, shifting all the elements after
+    /// it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn remove(&mut self, idx: I) -> T {
+        let pos = idx.index();
+        assert!(pos < self.len(), "removal index {} out of bounds for {}", pos, self.len());
+        let (left, rest) = self.split(self.root, pos);
+        let (node, right) = self.split(rest, 1);
+        self.root = self.merge(left, right);
+        self.free.push(node);
+        self.nodes[node].value.take().unwrap()
+    }
+
+    /// Remove and return the last element, if any.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 { None } else { Some(self.remove(I::from_usize(len - 1))) }
+    }
+
+    /// Remove every element.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.root = NIL;
+    }
+
+    /// Iterate over the elements in order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        let mut stack = Vec::new();
+        let mut cur = self.root;
+        core::iter::from_fn(move || {
+            while cur != NIL {
+                stack.push(cur);
+                cur = self.nodes[cur].left;
+            }
+            let node = stack.pop()?;
+            cur = self.nodes[node].right;
+            self.nodes[node].value.as_ref()
+        })
+    }
+
+    /// Similar to `self.iter().enumerate()` but with indices of `I` and not
+    /// `usize`.
+    #[inline]
+    pub fn iter_enumerated(&self) -> impl Iterator<Item = (I, &T)> + '_ {
+        self.iter().enumerate().map(|(i, t)| (I::from_usize(i), t))
+    }
+
+    /// Copy the elements into a contiguous [`IndexVec`].
+    #[inline]
+    pub fn to_index_vec(&self) -> IndexVec<I, T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    #[inline]
+    fn size(&self, node: usize) -> usize {
+        if node == NIL { 0 } else { self.nodes[node].size }
+    }
+
+    #[inline]
+    fn update(&mut self, node: usize) {
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        self.nodes[node].size = 1 + self.size(left) + self.size(right);
+    }
+
+    fn alloc(&mut self, value: T) -> usize {
+        // xorshift64*, only used to keep the tree balanced in expectation.
+        self.seed ^= self.seed >> 12;
+        self.seed ^= self.seed << 25;
+        self.seed ^= self.seed >> 27;
+        let priority = self.seed.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        let node = Node { value: Some(value), left: NIL, right: NIL, size: 1, priority };
+        if let Some(i) = self.free.pop() {
+            self.nodes[i] = node;
+            i
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    fn find(&self, mut pos: usize) -> Option<usize> {
+        let mut cur = self.root;
+        while cur != NIL {
+            let left = self.nodes[cur].left;
+            let left_size = self.size(left);
+            if pos < left_size {
+                cur = left;
+            } else if pos == left_size {
+                return Some(cur);
+            } else {
+                pos -= left_size + 1;
+                cur = self.nodes[cur].right;
+            }
+        }
+        None
+    }
+
+    /// Split `node` into the first `pos` elements and the rest.
+    fn split(&mut self, node: usize, pos: usize) -> (usize, usize) {
+        if node == NIL {
+            return (NIL, NIL);
+        }
+        let left_size = self.size(self.nodes[node].left);
+        if pos <= left_size {
+            let (l, r) = self.split(self.nodes[node].left, pos);
+            self.nodes[node].left = r;
+            self.update(node);
+            (l, node)
+        } else {
+            let (l, r) = self.split(self.nodes[node].right, pos - left_size - 1);
+            self.nodes[node].right = l;
+            self.update(node);
+            (node, r)
+        }
+    }
+
+    /// Concatenate `left` and `right`.
+    fn merge(&mut self, left: usize, right: usize) -> usize {
+        if left == NIL {
+            return right;
+        }
+        if right == NIL {
+            return left;
+        }
+        if self.nodes[left].priority > self.nodes[right].priority {
+            let merged = self.merge(self.nodes[left].right, right);
+            self.nodes[left].right = merged;
+            self.update(left);
+            left
+        } else {
+            let merged = self.merge(left, self.nodes[right].left);
+            self.nodes[right].left = merged;
+            self.update(right);
+            right
+        }
+    }
+}
+
+impl<I: Idx, T> Default for IndexRope<I, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, T> core::ops::Index<I> for IndexRope<I, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, idx: I) -> &T {
+        match self.get(idx) {
+            Some(value) => value,
+            None => panic!("index {} out of bounds for {}", idx.index(), self.len()),
+        }
+    }
+}
+
+impl<I: Idx, T> core::ops::IndexMut<I> for IndexRope<I, T> {
+    #[inline]
+    fn index_mut(&mut self, idx: I) -> &mut T {
+        let len = self.len();
+        match self.get_mut(idx) {
+            Some(value) => value,
+            None => panic!("index {} out of bounds for {}", idx.index(), len),
+        }
+    }
+}
+
+impl<I: Idx, T> Extend<T> for IndexRope<I, T> {
+    #[inline]
+    fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<I: Idx, T> FromIterator<T> for IndexRope<I, T> {
+    #[inline]
+    fn from_iter<J: IntoIterator<Item = T>>(iter: J) -> Self {
+        let mut rope = Self::new();
+        rope.extend(iter);
+        rope
+    }
+}
+
+impl<I: Idx, T> From<IndexVec<I, T>> for IndexRope<I, T> {
+    #[inline]
+    fn from(vec: IndexVec<I, T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<I: Idx, T: fmt::Debug> fmt::Debug for IndexRope<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_list().entries(self.iter()).finish()
+    }
+}
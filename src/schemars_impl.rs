@@ -0,0 +1,51 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// Same schema as a `Vec<T>`, matching how an `IndexVec` is serialized.
+impl<I: Idx, T: JsonSchema> JsonSchema for IndexVec<I, T> {
+    #[inline]
+    fn inline_schema() -> bool {
+        <Vec<T>>::inline_schema()
+    }
+
+    #[inline]
+    fn schema_name() -> Cow<'static, str> {
+        <Vec<T>>::schema_name()
+    }
+
+    #[inline]
+    fn schema_id() -> Cow<'static, str> {
+        <Vec<T>>::schema_id()
+    }
+
+    #[inline]
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        <Vec<T>>::json_schema(generator)
+    }
+}
+
+/// Same schema as a `[T]`, matching how an `IndexSlice` is serialized.
+impl<I: Idx, T: JsonSchema> JsonSchema for IndexSlice<I, [T]> {
+    #[inline]
+    fn inline_schema() -> bool {
+        <[T]>::inline_schema()
+    }
+
+    #[inline]
+    fn schema_name() -> Cow<'static, str> {
+        <[T]>::schema_name()
+    }
+
+    #[inline]
+    fn schema_id() -> Cow<'static, str> {
+        <[T]>::schema_id()
+    }
+
+    #[inline]
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        <[T]>::json_schema(generator)
+    }
+}
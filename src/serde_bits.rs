@@ -0,0 +1,113 @@
+//! Compact serde encoding shared by the bitset types.
+//!
+//! A bitset is written as a `(domain_size, bytes)` tuple, where `bytes` holds
+//! the words in little-endian order, trimmed to the bytes the domain needs.
+//! Formats with a native byte string type store it as one; the others (e.g.
+//! JSON) still get one number per byte instead of one bool per bit.
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::{
+    de::{self, Deserializer, SeqAccess, Visitor},
+    ser::{SerializeTuple, Serializer},
+};
+
+const WORD_BYTES: usize = (usize::BITS / 8) as usize;
+
+struct Bytes<'a>(&'a [u8]);
+
+impl serde::Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+pub(crate) fn serialize<S: Serializer, W: Iterator<Item = usize>>(
+    domain_size: usize,
+    words: W,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut bytes: Vec<u8> = words.flat_map(usize::to_le_bytes).collect();
+    bytes.truncate(domain_size.div_ceil(8));
+    let mut tuple = serializer.serialize_tuple(2)?;
+    tuple.serialize_element(&(domain_size as u64))?;
+    tuple.serialize_element(&Bytes(&bytes))?;
+    tuple.end()
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a byte string")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        Ok(bytes)
+    }
+}
+
+struct OwnedBytes(Vec<u8>);
+
+impl<'de> serde::Deserialize<'de> for OwnedBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(BytesVisitor).map(OwnedBytes)
+    }
+}
+
+struct BitsVisitor;
+
+impl<'de> Visitor<'de> for BitsVisitor {
+    type Value = (usize, Vec<usize>);
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a (domain_size, bytes) tuple")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let domain_size: u64 =
+            seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let OwnedBytes(bytes) =
+            seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let domain_size = usize::try_from(domain_size)
+            .map_err(|_| de::Error::custom("bitset domain size does not fit in usize"))?;
+        if bytes.len() != domain_size.div_ceil(8) {
+            return Err(de::Error::invalid_length(bytes.len(), &"one bit per index in the domain"));
+        }
+        let mut words: Vec<usize> = bytes
+            .chunks(WORD_BYTES)
+            .map(|chunk| {
+                let mut word = [0; WORD_BYTES];
+                word[..chunk.len()].copy_from_slice(chunk);
+                usize::from_le_bytes(word)
+            })
+            .collect();
+        // Ignore any bits past the domain in the last byte.
+        let tail = domain_size % usize::BITS as usize;
+        if let Some(last) = words.last_mut().filter(|_| tail != 0) {
+            *last &= (1 << tail) - 1;
+        }
+        Ok((domain_size, words))
+    }
+}
+
+/// Returns the domain size and the words, one `usize` per `usize::BITS` bits.
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<(usize, Vec<usize>), D::Error> {
+    deserializer.deserialize_tuple(2, BitsVisitor)
+}
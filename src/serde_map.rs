@@ -0,0 +1,85 @@
+//! Serialize an `IndexVec` as a map from index to value, rather than as a
+//! sequence. Use it with `#[serde(with = "oxc_index::serde_map")]`:
+//!
+//! ```rust,ignore
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Dump {
+//!     #[serde(with = "oxc_index::serde_map")]
+//!     names: IndexVec<NodeIdx, String>,
+//! }
+//! ```
+//!
+//! which writes e.g. `{"names":{"0":"a","1":"b"}}` in JSON.
+//!
+//! When deserializing, the entries may come in any order, but together they
+//! must cover every index from `0` up to the largest one exactly once.
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, Deserialize, Deserializer, MapAccess, Visitor},
+    ser::{Serialize, SerializeMap, Serializer},
+};
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// Serialize `slice` as a map of `index -> value`, in index order.
+pub fn serialize<I, T, S>(slice: &IndexSlice<I, [T]>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    I: Idx,
+    T: Serialize,
+    S: Serializer,
+{
+    let mut map = serializer.serialize_map(Some(slice.len()))?;
+    for (i, value) in slice.raw.iter().enumerate() {
+        map.serialize_entry(&(i as u64), value)?;
+    }
+    map.end()
+}
+
+struct MapVisitor<I: Idx, T>(PhantomData<fn() -> IndexVec<I, T>>);
+
+impl<'de, I: Idx, T: Deserialize<'de>> Visitor<'de> for MapVisitor<I, T> {
+    type Value = IndexVec<I, T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map from index to value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        // Like serde's own impls, don't trust the size hint with more than
+        // 1MiB up front.
+        let max_hint = (1 << 20) / core::mem::size_of::<(usize, T)>().max(1);
+        let mut entries: Vec<(usize, T)> =
+            Vec::with_capacity(map.size_hint().unwrap_or(0).min(max_hint));
+        while let Some((key, value)) = map.next_entry::<u64, T>()? {
+            let i = usize::try_from(key)
+                .ok()
+                .filter(|&i| i <= I::MAX)
+                .ok_or_else(|| de::Error::custom(format_args!("index {key} is out of range")))?;
+            entries.push((i, value));
+        }
+        // Only build the vector once the keys are known to be `0..len`, so a
+        // single huge key can't make us allocate for every index below it.
+        entries.sort_by_key(|&(i, _)| i);
+        for (pos, &(i, _)) in entries.iter().enumerate() {
+            if pos > 0 && entries[pos - 1].0 == i {
+                return Err(de::Error::custom(format_args!("duplicate index {i}")));
+            }
+            if i != pos {
+                return Err(de::Error::custom(format_args!("missing index {pos}")));
+            }
+        }
+        Ok(entries.into_iter().map(|(_, value)| value).collect())
+    }
+}
+
+/// Deserialize an `IndexVec` from a map of `index -> value`.
+pub fn deserialize<'de, I, T, D>(deserializer: D) -> Result<IndexVec<I, T>, D::Error>
+where
+    I: Idx,
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_map(MapVisitor(PhantomData))
+}
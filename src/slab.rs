@@ -0,0 +1,154 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Idx, IndexVec};
+
+/// An `IndexVec` whose elements can be removed, with the freed indices reused
+/// by later insertions.
+///
+/// This replaces the `IndexVec<I, Option<T>>` plus hand-rolled free list
+/// pattern. Indices of the remaining values stay stable across removals.
+/// Unlike [`GenerationalIndexVec`](crate::GenerationalIndexVec), an index
+/// held on to after its value was removed will see whatever gets inserted into
+/// the slot next.
+///
+/// ```rust
+/// use oxc_index::IndexSlab;
+///
+/// oxc_index::define_index_type! {
+///     pub struct SymbolId = u32;
+/// }
+///
+/// let mut symbols = IndexSlab::<SymbolId, &str>::new();
+/// let a = symbols.insert("a");
+/// let b = symbols.insert("b");
+/// assert_eq!(symbols.remove(a), Some("a"));
+/// assert_eq!(symbols.insert("c"), a);
+/// assert_eq!(symbols[b], "b");
+/// ```
+#[derive(Clone)]
+pub struct IndexSlab<I: Idx, T> {
+    slots: IndexVec<I, Option<T>>,
+    free: Vec<I>,
+}
+
+impl<I: Idx, T> IndexSlab<I, T> {
+    /// Construct an empty `IndexSlab`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { slots: IndexVec::new(), free: Vec::new() }
+    }
+
+    /// Construct an empty `IndexSlab` with room for `capacity` values.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { slots: IndexVec::with_capacity(capacity), free: Vec::new() }
+    }
+
+    /// Returns the number of values.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Returns true if there are no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the index the next call to [`IndexSlab::insert`] will use.
+    #[inline]
+    pub fn next_idx(&self) -> I {
+        self.free.last().copied().unwrap_or_else(|| self.slots.next_idx())
+    }
+
+    /// Insert `value`, reusing a freed index if there is one, and return its
+    /// index.
+    pub fn insert(&mut self, value: T) -> I {
+        if let Some(idx) = self.free.pop() {
+            self.slots[idx] = Some(value);
+            idx
+        } else {
+            self.slots.push(Some(value))
+        }
+    }
+
+    /// Remove the value at `idx`, freeing the index for reuse. Returns `None`
+    /// if there was no value there.
+    pub fn remove(&mut self, idx: I) -> Option<T> {
+        let value = self.slots.get_mut(idx)?.take()?;
+        self.free.push(idx);
+        Some(value)
+    }
+
+    /// Returns true if there's a value at `idx`.
+    #[inline]
+    pub fn contains(&self, idx: I) -> bool {
+        self.get(idx).is_some()
+    }
+
+    /// Get the value at `idx`, or `None` if there is none.
+    #[inline]
+    pub fn get(&self, idx: I) -> Option<&T> {
+        self.slots.get(idx)?.as_ref()
+    }
+
+    /// Get the value at `idx` mutably, or `None` if there is none.
+    #[inline]
+    pub fn get_mut(&mut self, idx: I) -> Option<&mut T> {
+        self.slots.get_mut(idx)?.as_mut()
+    }
+
+    /// Remove every value, and forget about the freed indices.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.free.clear();
+    }
+
+    /// Iterate over the values along with their indices, in index order.
+    pub fn iter_enumerated(&self) -> impl Iterator<Item = (I, &T)> + '_ {
+        self.slots.iter_enumerated().filter_map(|(idx, slot)| Some((idx, slot.as_ref()?)))
+    }
+
+    /// Iterate mutably over the values along with their indices, in index
+    /// order.
+    pub fn iter_mut_enumerated(&mut self) -> impl Iterator<Item = (I, &mut T)> + '_ {
+        self.slots.iter_mut_enumerated().filter_map(|(idx, slot)| Some((idx, slot.as_mut()?)))
+    }
+}
+
+impl<I: Idx, T> Default for IndexSlab<I, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, T> core::ops::Index<I> for IndexSlab<I, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, idx: I) -> &T {
+        self.get(idx).unwrap_or_else(|| panic!("no value at index {idx:?}"))
+    }
+}
+
+impl<I: Idx, T> core::ops::IndexMut<I> for IndexSlab<I, T> {
+    #[inline]
+    fn index_mut(&mut self, idx: I) -> &mut T {
+        self.get_mut(idx).unwrap_or_else(|| panic!("no value at index {idx:?}"))
+    }
+}
+
+impl<I: Idx, T> FromIterator<T> for IndexSlab<I, T> {
+    fn from_iter<J: IntoIterator<Item = T>>(iter: J) -> Self {
+        Self { slots: iter.into_iter().map(Some).collect(), free: Vec::new() }
+    }
+}
+
+impl<I: Idx, T: fmt::Debug> fmt::Debug for IndexSlab<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.iter_enumerated()).finish()
+    }
+}
@@ -0,0 +1,197 @@
+use core::{fmt, marker::PhantomData};
+
+use smallvec::SmallVec;
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// A `SmallVec` that only accepts indices of a specific type.
+///
+/// Up to `N` elements are stored inline, so short lists (e.g. the children of
+/// most AST nodes) don't allocate. Like [`IndexVec`], this is a thin wrapper
+/// whose backing `SmallVec` is public (as `raw`), and it derefs to
+/// [`IndexSlice`], so all the methods on that are available too. Requires the
+/// `smallvec` feature.
+///
+/// ```rust
+/// use oxc_index::IndexSmallVec;
+///
+/// oxc_index::define_index_type! {
+///     pub struct ChildIdx = u32;
+/// }
+///
+/// let mut children = IndexSmallVec::<ChildIdx, &str, 2>::new();
+/// assert_eq!(children.push("a"), ChildIdx::new(0));
+/// assert_eq!(children.push("b"), ChildIdx::new(1));
+/// assert!(!children.spilled());
+/// assert_eq!(children[ChildIdx::new(1)], "b");
+/// children.push("c");
+/// assert!(children.spilled());
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IndexSmallVec<I: Idx, T, const N: usize> {
+    /// Our wrapped `SmallVec`.
+    pub raw: SmallVec<[T; N]>,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx, T, const N: usize> IndexSmallVec<I, T, N> {
+    /// Construct an empty `IndexSmallVec`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { raw: SmallVec::new_const(), _marker: PhantomData }
+    }
+
+    /// Construct an empty `IndexSmallVec` with room for `capacity` elements.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { raw: SmallVec::with_capacity(capacity), _marker: PhantomData }
+    }
+
+    /// Construct an `IndexSmallVec` from a `SmallVec`.
+    ///
+    /// Panics if its length is too large for our index type.
+    #[inline]
+    pub fn from_smallvec(raw: SmallVec<[T; N]>) -> Self {
+        // See if `I::from_usize` might be upset by this length.
+        let _ = I::from_usize(raw.len());
+        Self { raw, _marker: PhantomData }
+    }
+
+    /// Gives the next index that will be assigned when `push` is called.
+    #[inline]
+    pub fn next_idx(&self) -> I {
+        I::from_usize(self.raw.len())
+    }
+
+    /// Push a new item onto the vector, and return its index.
+    #[inline]
+    pub fn push(&mut self, value: T) -> I {
+        let idx = self.next_idx();
+        self.raw.push(value);
+        idx
+    }
+
+    /// Pops the last item off, returning it.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.raw.pop()
+    }
+
+    /// Returns true if the elements have moved from inline storage to the
+    /// heap.
+    #[inline]
+    pub fn spilled(&self) -> bool {
+        self.raw.spilled()
+    }
+
+    /// Shortens the vector to `len` elements, dropping the rest.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.raw.truncate(len);
+    }
+
+    /// Remove every element.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.raw.clear();
+    }
+
+    /// Reserve room for `additional` more elements.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.raw.reserve(additional);
+    }
+
+    /// Move the elements into an `IndexVec`. This doesn't reallocate if they
+    /// have already spilled to the heap.
+    #[inline]
+    pub fn into_index_vec(self) -> IndexVec<I, T> {
+        IndexVec::from_vec(self.raw.into_vec())
+    }
+
+    /// Get the elements as an `IndexSlice`.
+    #[inline]
+    pub fn as_slice(&self) -> &IndexSlice<I, [T]> {
+        IndexSlice::new(&self.raw)
+    }
+
+    /// Get the elements as a mutable `IndexSlice`.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut IndexSlice<I, [T]> {
+        IndexSlice::new_mut(&mut self.raw)
+    }
+}
+
+impl<I: Idx, T, const N: usize> core::ops::Deref for IndexSmallVec<I, T, N> {
+    type Target = IndexSlice<I, [T]>;
+
+    #[inline]
+    fn deref(&self) -> &IndexSlice<I, [T]> {
+        self.as_slice()
+    }
+}
+
+impl<I: Idx, T, const N: usize> core::ops::DerefMut for IndexSmallVec<I, T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut IndexSlice<I, [T]> {
+        self.as_mut_slice()
+    }
+}
+
+impl<I: Idx, T, const N: usize> Default for IndexSmallVec<I, T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, T, const N: usize> Extend<T> for IndexSmallVec<I, T, N> {
+    #[inline]
+    fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
+        self.raw.extend(iter);
+        let _ = self.next_idx();
+    }
+}
+
+impl<I: Idx, T, const N: usize> FromIterator<T> for IndexSmallVec<I, T, N> {
+    #[inline]
+    fn from_iter<J: IntoIterator<Item = T>>(iter: J) -> Self {
+        Self::from_smallvec(iter.into_iter().collect())
+    }
+}
+
+impl<I: Idx, T, const N: usize> IntoIterator for IndexSmallVec<I, T, N> {
+    type Item = T;
+    type IntoIter = smallvec::IntoIter<[T; N]>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.into_iter()
+    }
+}
+
+impl<'a, I: Idx, T, const N: usize> IntoIterator for &'a IndexSmallVec<I, T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter()
+    }
+}
+
+impl<'a, I: Idx, T, const N: usize> IntoIterator for &'a mut IndexSmallVec<I, T, N> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter_mut()
+    }
+}
+
+impl<I: Idx, T: fmt::Debug, const N: usize> fmt::Debug for IndexSmallVec<I, T, N> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.raw, fmt)
+    }
+}
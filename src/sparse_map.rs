@@ -0,0 +1,176 @@
+use hashbrown::HashMap;
+
+use crate::{BuildIdxHasher, Idx};
+
+/// A sparse map keyed by an index type.
+///
+/// This is a thin wrapper around a hashbrown [`HashMap`] using identity
+/// hashing, for domains too large to cover with an
+/// [`IndexMap`](crate::IndexMap) but only sparsely populated. Iteration order
+/// is unspecified. Requires the `hashbrown` feature.
+///
+/// ```rust
+/// use oxc_index::SparseIndexMap;
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let mut comments = SparseIndexMap::<NodeIdx, &str>::new();
+/// comments.insert(NodeIdx::new(4_000_000), "hot path");
+/// assert_eq!(comments.get(NodeIdx::new(4_000_000)), Some(&"hot path"));
+/// assert_eq!(comments.len(), 1);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct SparseIndexMap<I: Idx, V> {
+    map: HashMap<I, V, BuildIdxHasher>,
+}
+
+impl<I: Idx, V> SparseIndexMap<I, V> {
+    /// Construct an empty map.
+    #[inline]
+    pub fn new() -> Self {
+        Self { map: HashMap::with_hasher(BuildIdxHasher::default()) }
+    }
+
+    /// Construct an empty map with room for at least `capacity` entries.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { map: HashMap::with_capacity_and_hasher(capacity, BuildIdxHasher::default()) }
+    }
+
+    /// The number of entries in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the map has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Get the value at `idx`, if present.
+    #[inline]
+    pub fn get(&self, idx: I) -> Option<&V> {
+        self.map.get(&idx)
+    }
+
+    /// Get the value at `idx` mutably, if present.
+    #[inline]
+    pub fn get_mut(&mut self, idx: I) -> Option<&mut V> {
+        self.map.get_mut(&idx)
+    }
+
+    /// Returns true if `idx` has a value.
+    #[inline]
+    pub fn contains(&self, idx: I) -> bool {
+        self.map.contains_key(&idx)
+    }
+
+    /// Insert `value` at `idx`, returning the previous value if any.
+    #[inline]
+    pub fn insert(&mut self, idx: I, value: V) -> Option<V> {
+        self.map.insert(idx, value)
+    }
+
+    /// Remove the value at `idx`, returning it if present.
+    #[inline]
+    pub fn remove(&mut self, idx: I) -> Option<V> {
+        self.map.remove(&idx)
+    }
+
+    /// Get the value at `idx`, computing and inserting it with `f` first if
+    /// needed.
+    #[inline]
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, idx: I, f: F) -> &mut V {
+        self.map.entry(idx).or_insert_with(f)
+    }
+
+    /// Keep only the entries for which `f` returns true.
+    #[inline]
+    pub fn retain<F: FnMut(I, &mut V) -> bool>(&mut self, mut f: F) {
+        self.map.retain(|&idx, value| f(idx, value));
+    }
+
+    /// Remove every entry.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Iterate over the entries, in unspecified order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (I, &V)> + '_ {
+        self.map.iter().map(|(&idx, value)| (idx, value))
+    }
+
+    /// Iterate mutably over the entries, in unspecified order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (I, &mut V)> + '_ {
+        self.map.iter_mut().map(|(&idx, value)| (idx, value))
+    }
+
+    /// Iterate over the indices which have a value, in unspecified order.
+    #[inline]
+    pub fn keys(&self) -> impl Iterator<Item = I> + '_ {
+        self.map.keys().copied()
+    }
+
+    /// Iterate over the values, in unspecified order.
+    #[inline]
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.map.values()
+    }
+
+    /// Unwrap the underlying `HashMap`.
+    #[inline]
+    pub fn into_inner(self) -> HashMap<I, V, BuildIdxHasher> {
+        self.map
+    }
+}
+
+impl<I: Idx, V> Default for SparseIndexMap<I, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, V> core::ops::Index<I> for SparseIndexMap<I, V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, idx: I) -> &V {
+        self.get(idx).unwrap_or_else(|| panic!("no value at index {idx:?}"))
+    }
+}
+
+impl<I: Idx, V> core::ops::IndexMut<I> for SparseIndexMap<I, V> {
+    #[inline]
+    fn index_mut(&mut self, idx: I) -> &mut V {
+        self.get_mut(idx).unwrap_or_else(|| panic!("no value at index {idx:?}"))
+    }
+}
+
+impl<I: Idx, V> Extend<(I, V)> for SparseIndexMap<I, V> {
+    #[inline]
+    fn extend<J: IntoIterator<Item = (I, V)>>(&mut self, iter: J) {
+        self.map.extend(iter);
+    }
+}
+
+impl<I: Idx, V> FromIterator<(I, V)> for SparseIndexMap<I, V> {
+    fn from_iter<J: IntoIterator<Item = (I, V)>>(iter: J) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<I: Idx, V: core::fmt::Debug> core::fmt::Debug for SparseIndexMap<I, V> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.map, fmt)
+    }
+}
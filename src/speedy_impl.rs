@@ -0,0 +1,49 @@
+use alloc::vec::Vec;
+
+use speedy::{Context, Error, Readable, Reader, Writable, Writer};
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// Written exactly like a `Vec<T>`.
+impl<C: Context, I: Idx, T: Writable<C>> Writable<C> for IndexVec<I, T> {
+    #[inline]
+    fn write_to<W: ?Sized + Writer<C>>(&self, writer: &mut W) -> Result<(), C::Error> {
+        self.raw.write_to(writer)
+    }
+
+    #[inline]
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        Writable::<C>::bytes_needed(&self.raw)
+    }
+}
+
+/// Written exactly like a `[T]`.
+impl<C: Context, I: Idx, T: Writable<C>> Writable<C> for IndexSlice<I, [T]> {
+    #[inline]
+    fn write_to<W: ?Sized + Writer<C>>(&self, writer: &mut W) -> Result<(), C::Error> {
+        self.raw.write_to(writer)
+    }
+
+    #[inline]
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        Writable::<C>::bytes_needed(&self.raw)
+    }
+}
+
+/// Checks the vector fits our index type, like `IndexVec::from_vec` does, but
+/// with an error rather than a panic.
+impl<'a, C: Context, I: Idx, T: Readable<'a, C>> Readable<'a, C> for IndexVec<I, T> {
+    #[inline]
+    fn read_from<R: Reader<'a, C>>(reader: &mut R) -> Result<Self, C::Error> {
+        let raw: Vec<T> = reader.read_value()?;
+        if I::try_from_usize(raw.len()).is_none() {
+            return Err(Error::custom("`IndexVec` too long for its index type").into());
+        }
+        Ok(Self::from_vec(raw))
+    }
+
+    #[inline]
+    fn minimum_bytes_needed() -> usize {
+        <Vec<T> as Readable<'a, C>>::minimum_bytes_needed()
+    }
+}
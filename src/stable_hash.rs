@@ -0,0 +1,145 @@
+use core::hash::{Hash, Hasher};
+
+const M1: u64 = 0x87c3_7b91_1142_53d5;
+const M2: u64 = 0x4cf5_ad43_2745_937f;
+
+/// A deterministic, platform independent 128-bit [`Hasher`].
+///
+/// `std`'s hashers are randomly seeded, and the default [`Hasher`] methods
+/// write integers in native byte order and `usize`s at native width, so the
+/// same table can hash differently between runs or between a 32-bit and a
+/// 64-bit host. `StableHasher` fixes its seed, hashes every integer by value,
+/// and widens `usize`/`isize` to 64 bits, which makes it suitable for
+/// fingerprinting tables for incremental compilation.
+///
+/// Anything implementing [`Hash`] can be fed to it: index types,
+/// [`IndexVec`](crate::IndexVec), [`IndexBitSet`](crate::IndexBitSet),
+/// [`AtomicIndexBitSet`](crate::AtomicIndexBitSet),
+/// [`IntervalMap`](crate::IntervalMap) and so on. The result is only as
+/// stable as the `Hash` impl being used; in particular, don't hash
+/// `HashMap`s or raw pointers.
+///
+/// This is meant for fingerprints, not for protection against adversarial
+/// input.
+///
+/// ```rust
+/// use oxc_index::{IndexVec, index_vec, stable_hash};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let a: IndexVec<NodeIdx, u32> = index_vec![1, 2, 3];
+/// let b: IndexVec<NodeIdx, u32> = index_vec![1, 2, 3];
+/// assert_eq!(stable_hash(&a), stable_hash(&b));
+/// assert_ne!(stable_hash(&a), stable_hash(&a[..NodeIdx::new(2)]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct StableHasher {
+    a: u64,
+    b: u64,
+    words: u64,
+}
+
+impl StableHasher {
+    /// Construct a hasher in its fixed initial state.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { a: 0x243f_6a88_85a3_08d3, b: 0x1319_8a2e_0370_7344, words: 0 }
+    }
+
+    #[inline]
+    fn absorb(&mut self, word: u64) {
+        self.a = (self.a ^ word).wrapping_mul(M1).rotate_left(29);
+        self.b = (self.b.rotate_left(23) ^ word ^ self.a).wrapping_mul(M2);
+        self.words += 1;
+    }
+
+    /// Finish the hash, producing all 128 bits.
+    #[inline]
+    pub fn finish128(&self) -> u128 {
+        let a = fmix64(self.a ^ self.words);
+        let b = fmix64(self.b ^ a);
+        (u128::from(b) << 64) | u128::from(a.wrapping_add(b))
+    }
+}
+
+impl Default for StableHasher {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The finalizer from MurmurHash3.
+#[inline]
+const fn fmix64(mut x: u64) -> u64 {
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    x ^ (x >> 33)
+}
+
+impl Hasher for StableHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.finish128() as u64
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.absorb(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let mut tail = [0; 8];
+        tail[..chunks.remainder().len()].copy_from_slice(chunks.remainder());
+        self.absorb(u64::from_le_bytes(tail));
+        self.absorb(bytes.len() as u64);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.absorb(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.absorb(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.absorb(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.absorb(i);
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.absorb(i as u64);
+        self.absorb((i >> 64) as u64);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.absorb(i as u64);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.absorb(i as i64 as u64);
+    }
+}
+
+/// Hash `value` with a fresh [`StableHasher`], returning all 128 bits.
+#[inline]
+pub fn stable_hash<T: Hash + ?Sized>(value: &T) -> u128 {
+    let mut hasher = StableHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish128()
+}
@@ -0,0 +1,63 @@
+//! [`proptest`] strategies for index types and `IndexVec`s, which shrink the
+//! same way as the `usize` and `Vec` strategies they wrap.
+//!
+//! ```rust,ignore
+//! use oxc_index::strategy::{any_idx, index_vec};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     #[test]
+//!     fn get_in_bounds(v in index_vec::<NodeIdx, _, _>(any::<u8>(), 1..100), idx in any_idx::<NodeIdx>()) {
+//!         prop_assert_eq!(v.get(idx).is_some(), idx.index() < v.len());
+//!     }
+//! }
+//! ```
+use core::ops::{Bound, RangeBounds};
+
+use proptest::{
+    collection::{self, SizeRange},
+    strategy::Strategy,
+};
+
+use crate::{Idx, IdxRangeBounds, IndexVec};
+
+/// Any valid index of type `I`, i.e. from `0` up to and including `I::MAX`.
+/// Shrinks towards `0`.
+pub fn any_idx<I: Idx>() -> impl Strategy<Value = I> {
+    (0..=I::MAX).prop_map(I::from_usize)
+}
+
+/// Any index in `range`, e.g. `idx_in_range(..v.next_idx())` for the indices
+/// of `v`. An unbounded end stops at `I::MAX`. Shrinks towards the start of
+/// the range.
+///
+/// # Panics
+///
+/// Panics if `range` is empty.
+pub fn idx_in_range<I: Idx, R: IdxRangeBounds<I>>(range: R) -> impl Strategy<Value = I> {
+    let range = range.into_range();
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => Some(end),
+        Bound::Excluded(&end) => end.checked_sub(1),
+        Bound::Unbounded => Some(I::MAX),
+    };
+    let end = end.filter(|&end| start <= end).expect("`idx_in_range` needs a non-empty range");
+    (start..=end).prop_map(I::from_usize)
+}
+
+/// An `IndexVec` with elements from `element`, and a length in `size`
+/// (clamped to `I::MAX`, so the next index is always valid). Shrinks like
+/// [`proptest::collection::vec`].
+pub fn index_vec<I: Idx, S: Strategy, R: Into<SizeRange>>(
+    element: S,
+    size: R,
+) -> impl Strategy<Value = IndexVec<I, S::Value>> {
+    let (start, end) = size.into().start_end_incl();
+    let size = start.min(I::MAX)..=end.min(I::MAX);
+    collection::vec(element, size).prop_map(IndexVec::from_vec)
+}
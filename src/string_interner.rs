@@ -0,0 +1,170 @@
+use alloc::string::String;
+use core::{fmt, hash::BuildHasher};
+use std::collections::HashMap;
+
+use crate::{BuildIdxHasher, Idx, IndexVec};
+
+/// Deduplicates strings, handing out an index per distinct string.
+///
+/// Unlike an [`Interner`](crate::Interner) of `String`s, which allocates
+/// every string separately and stores it twice, this appends the bytes of
+/// each new string to a single buffer. The lookup table only holds hashes and
+/// indices.
+///
+/// ```rust
+/// use oxc_index::StringInterner;
+///
+/// oxc_index::define_index_type! {
+///     pub struct Atom = u32;
+/// }
+///
+/// let mut atoms = StringInterner::<Atom>::new();
+/// let foo = atoms.intern("foo");
+/// let bar = atoms.intern("bar");
+/// assert_eq!(atoms.intern("foo"), foo);
+/// assert_eq!(atoms.resolve(bar), "bar");
+/// assert_eq!(atoms.lookup("baz"), None);
+/// ```
+#[derive(Clone)]
+pub struct StringInterner<I: Idx, S = std::hash::RandomState> {
+    bytes: String,
+    ends: IndexVec<I, usize>,
+    /// The previously interned string with the same hash, if any.
+    prev_same_hash: IndexVec<I, Option<I>>,
+    /// The most recently interned string for each hash.
+    by_hash: HashMap<u64, I, BuildIdxHasher>,
+    hasher: S,
+}
+
+impl<I: Idx> StringInterner<I> {
+    /// Construct an empty `StringInterner`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<I: Idx, S: BuildHasher> StringInterner<I, S> {
+    /// Construct an empty `StringInterner` using `hasher` to hash the strings.
+    #[inline]
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            bytes: String::new(),
+            ends: IndexVec::new(),
+            prev_same_hash: IndexVec::new(),
+            by_hash: HashMap::default(),
+            hasher,
+        }
+    }
+
+    /// Returns the number of distinct strings.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns true if nothing has been interned.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Get the string for `idx`, or `None` if `idx` is out of bounds.
+    #[inline]
+    pub fn get(&self, idx: I) -> Option<&str> {
+        let end = *self.ends.get(idx)?;
+        let start = if idx.index() == 0 { 0 } else { self.ends.raw[idx.index() - 1] };
+        Some(&self.bytes[start..end])
+    }
+
+    /// Get the string for `idx`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    #[inline]
+    pub fn resolve(&self, idx: I) -> &str {
+        self.get(idx)
+            .unwrap_or_else(|| panic!("index {} out of bounds for {}", idx.index(), self.len()))
+    }
+
+    fn find(&self, hash: u64, s: &str) -> Option<I> {
+        let mut candidate = self.by_hash.get(&hash).copied();
+        while let Some(idx) = candidate {
+            if self.resolve(idx) == s {
+                return Some(idx);
+            }
+            candidate = self.prev_same_hash[idx];
+        }
+        None
+    }
+
+    /// Returns the index of `s`, if it has been interned.
+    #[inline]
+    pub fn lookup(&self, s: &str) -> Option<I> {
+        self.find(self.hasher.hash_one(s), s)
+    }
+
+    /// Returns the index of `s`, interning it first if it's new.
+    pub fn intern(&mut self, s: &str) -> I {
+        let hash = self.hasher.hash_one(s);
+        if let Some(idx) = self.find(hash, s) {
+            return idx;
+        }
+        self.bytes.push_str(s);
+        let idx = self.ends.push(self.bytes.len());
+        self.prev_same_hash.push(self.by_hash.insert(hash, idx));
+        idx
+    }
+
+    /// Iterate over the strings along with their indices, in the order they
+    /// were first interned.
+    pub fn iter_enumerated(&self) -> impl Iterator<Item = (I, &str)> + '_ {
+        self.ends.indices().map(|idx| (idx, self.resolve(idx)))
+    }
+}
+
+impl<I: Idx, S: Default> Default for StringInterner<I, S> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            bytes: String::new(),
+            ends: IndexVec::new(),
+            prev_same_hash: IndexVec::new(),
+            by_hash: HashMap::default(),
+            hasher: S::default(),
+        }
+    }
+}
+
+impl<I: Idx, S: BuildHasher> core::ops::Index<I> for StringInterner<I, S> {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, idx: I) -> &str {
+        self.resolve(idx)
+    }
+}
+
+impl<'a, I: Idx, S: BuildHasher> Extend<&'a str> for StringInterner<I, S> {
+    /// Intern every string yielded by `iter`.
+    fn extend<J: IntoIterator<Item = &'a str>>(&mut self, iter: J) {
+        for s in iter {
+            self.intern(s);
+        }
+    }
+}
+
+impl<'a, I: Idx, S: BuildHasher + Default> FromIterator<&'a str> for StringInterner<I, S> {
+    fn from_iter<J: IntoIterator<Item = &'a str>>(iter: J) -> Self {
+        let mut interner = Self::default();
+        interner.extend(iter);
+        interner
+    }
+}
+
+impl<I: Idx, S: BuildHasher> fmt::Debug for StringInterner<I, S> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_list().entries(self.iter_enumerated().map(|(_, s)| s)).finish()
+    }
+}
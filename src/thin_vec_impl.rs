@@ -0,0 +1,296 @@
+use core::{fmt, marker::PhantomData};
+
+use thin_vec::ThinVec;
+
+use crate::{Idx, IdxSliceIndex, IndexSlice, IndexVec};
+
+/// A `ThinVec` that only accepts indices of a specific type.
+///
+/// The length and capacity live in the heap allocation, so the vector itself
+/// is a single pointer, and an empty one doesn't allocate. That makes it a
+/// good fit for mostly-empty lists embedded in large structs. Like
+/// [`IndexVec`], this is a thin wrapper whose backing `ThinVec` is public (as
+/// `raw`), and it derefs to [`IndexSlice`], so all the methods on that are
+/// available too. Requires the `thin-vec` feature.
+///
+/// ```rust
+/// use oxc_index::IndexThinVec;
+///
+/// oxc_index::define_index_type! {
+///     pub struct ArgIdx = u32;
+/// }
+///
+/// assert_eq!(size_of::<IndexThinVec<ArgIdx, u64>>(), size_of::<usize>());
+///
+/// let mut args = IndexThinVec::<ArgIdx, &str>::new();
+/// assert_eq!(args.push("a"), ArgIdx::new(0));
+/// assert_eq!(args.push("b"), ArgIdx::new(1));
+/// assert_eq!(args[ArgIdx::new(1)], "b");
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IndexThinVec<I: Idx, T> {
+    /// Our wrapped `ThinVec`.
+    pub raw: ThinVec<T>,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx, T> IndexThinVec<I, T> {
+    /// Construct an empty `IndexThinVec`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { raw: ThinVec::new(), _marker: PhantomData }
+    }
+
+    /// Construct an empty `IndexThinVec` with room for `capacity` elements.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { raw: ThinVec::with_capacity(capacity), _marker: PhantomData }
+    }
+
+    /// Construct an `IndexThinVec` from a `ThinVec`.
+    ///
+    /// Panics if its length is too large for our index type.
+    #[inline]
+    pub fn from_thin_vec(raw: ThinVec<T>) -> Self {
+        // See if `I::from_usize` might be upset by this length.
+        let _ = I::from_usize(raw.len());
+        Self { raw, _marker: PhantomData }
+    }
+
+    /// Gives the next index that will be assigned when `push` is called.
+    #[inline]
+    pub fn next_idx(&self) -> I {
+        I::from_usize(self.raw.len())
+    }
+
+    /// Push a new item onto the vector, and return its index.
+    #[inline]
+    pub fn push(&mut self, value: T) -> I {
+        let idx = self.next_idx();
+        self.raw.push(value);
+        idx
+    }
+
+    /// Pops the last item off, returning it.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.raw.pop()
+    }
+
+    /// Shortens the vector to `len` elements, dropping the rest.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.raw.truncate(len);
+    }
+
+    /// Remove every element.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.raw.clear();
+    }
+
+    /// Reserve room for `additional` more elements.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.raw.reserve(additional);
+    }
+
+    /// Shrinks the capacity of the vector as much as possible.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.raw.shrink_to_fit();
+    }
+
+    /// Get a ref to the item at the provided index, or None for out of bounds.
+    #[inline]
+    pub fn get<J: IdxSliceIndex<I, T>>(&self, index: J) -> Option<&J::Output> {
+        index.get(self.as_slice())
+    }
+
+    /// Get a mut ref to the item at the provided index, or None for out of
+    /// bounds
+    #[inline]
+    pub fn get_mut<J: IdxSliceIndex<I, T>>(&mut self, index: J) -> Option<&mut J::Output> {
+        index.get_mut(self.as_mut_slice())
+    }
+
+    /// Resize ourselves in-place to `new_len`. See [`ThinVec::resize`].
+    #[inline]
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        self.raw.resize(new_len, value);
+    }
+
+    /// Moves all the elements of `other` into `Self`, leaving `other` empty.
+    /// See [`ThinVec::append`].
+    #[inline]
+    pub fn append(&mut self, other: &mut Self) {
+        self.raw.append(&mut other.raw);
+    }
+
+    /// Splits the collection into two at the given index. See
+    /// [`ThinVec::split_off`].
+    #[inline]
+    #[must_use]
+    pub fn split_off(&mut self, idx: I) -> Self {
+        Self::from_thin_vec(self.raw.split_off(idx.index()))
+    }
+
+    /// Remove the item at `index`. See [`ThinVec::remove`].
+    #[inline]
+    pub fn remove(&mut self, index: I) -> T {
+        self.raw.remove(index.index())
+    }
+
+    /// Remove the item at `index` without maintaining order. See
+    /// [`ThinVec::swap_remove`].
+    #[inline]
+    pub fn swap_remove(&mut self, index: I) -> T {
+        self.raw.swap_remove(index.index())
+    }
+
+    /// Insert an item at `index`. See [`ThinVec::insert`].
+    #[inline]
+    pub fn insert(&mut self, index: I, element: T) {
+        self.raw.insert(index.index(), element);
+    }
+
+    /// Append all items in the slice to the end of our vector.
+    ///
+    /// See [`ThinVec::extend_from_slice`].
+    #[inline]
+    pub fn extend_from_slice(&mut self, other: &IndexSlice<I, [T]>)
+    where
+        T: Clone,
+    {
+        self.raw.extend_from_slice(&other.raw);
+    }
+
+    /// Forwards to the `Vec::retain` implementation.
+    #[inline]
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.raw.retain(f);
+    }
+
+    /// Forwards to the `Vec::dedup_by_key` implementation.
+    #[inline]
+    pub fn dedup_by_key<F: FnMut(&mut T) -> K, K: PartialEq>(&mut self, key: F) {
+        self.raw.dedup_by_key(key);
+    }
+
+    /// Forwards to the `Vec::dedup` implementation.
+    #[inline]
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.raw.dedup();
+    }
+
+    /// Forwards to the `Vec::dedup_by` implementation.
+    #[inline]
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, same_bucket: F) {
+        self.raw.dedup_by(same_bucket);
+    }
+
+    /// Move the elements into an `IndexVec`.
+    #[inline]
+    pub fn into_index_vec(self) -> IndexVec<I, T> {
+        IndexVec::from_vec(self.raw.into())
+    }
+
+    /// Get the elements as an `IndexSlice`.
+    #[inline]
+    pub fn as_slice(&self) -> &IndexSlice<I, [T]> {
+        IndexSlice::new(&self.raw)
+    }
+
+    /// Get the elements as a mutable `IndexSlice`.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut IndexSlice<I, [T]> {
+        IndexSlice::new_mut(self.raw.as_mut_slice())
+    }
+}
+
+impl<I: Idx, T> core::ops::Deref for IndexThinVec<I, T> {
+    type Target = IndexSlice<I, [T]>;
+
+    #[inline]
+    fn deref(&self) -> &IndexSlice<I, [T]> {
+        self.as_slice()
+    }
+}
+
+impl<I: Idx, T> core::ops::DerefMut for IndexThinVec<I, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut IndexSlice<I, [T]> {
+        self.as_mut_slice()
+    }
+}
+
+impl<I: Idx, T> From<IndexVec<I, T>> for IndexThinVec<I, T> {
+    #[inline]
+    fn from(vec: IndexVec<I, T>) -> Self {
+        Self { raw: vec.raw.into(), _marker: PhantomData }
+    }
+}
+
+impl<I: Idx, T> Default for IndexThinVec<I, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Idx, T> Extend<T> for IndexThinVec<I, T> {
+    #[inline]
+    fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
+        self.raw.extend(iter);
+        let _ = self.next_idx();
+    }
+}
+
+impl<I: Idx, T> FromIterator<T> for IndexThinVec<I, T> {
+    #[inline]
+    fn from_iter<J: IntoIterator<Item = T>>(iter: J) -> Self {
+        Self::from_thin_vec(iter.into_iter().collect())
+    }
+}
+
+impl<I: Idx, T> IntoIterator for IndexThinVec<I, T> {
+    type Item = T;
+    type IntoIter = thin_vec::IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.into_iter()
+    }
+}
+
+impl<'a, I: Idx, T> IntoIterator for &'a IndexThinVec<I, T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter()
+    }
+}
+
+impl<'a, I: Idx, T> IntoIterator for &'a mut IndexThinVec<I, T> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter_mut()
+    }
+}
+
+impl<I: Idx, T: fmt::Debug> fmt::Debug for IndexThinVec<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.raw, fmt)
+    }
+}
@@ -0,0 +1,103 @@
+use std::{path::PathBuf, string::String, vec::Vec};
+
+use ts_rs::{TS, TypeVisitor};
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// Same type as a `Vec<T>` (`Array<T>`), matching how an `IndexVec` is
+/// serialized.
+impl<I: Idx, T: TS> TS for IndexVec<I, T> {
+    type WithoutGenerics = <Vec<T> as TS>::WithoutGenerics;
+    type OptionInnerType = Self;
+
+    fn ident() -> String {
+        <Vec<T>>::ident()
+    }
+
+    fn name() -> String {
+        <Vec<T>>::name()
+    }
+
+    fn inline() -> String {
+        <Vec<T>>::inline()
+    }
+
+    fn inline_flattened() -> String {
+        <Vec<T>>::inline_flattened()
+    }
+
+    fn visit_dependencies(v: &mut impl TypeVisitor)
+    where
+        Self: 'static,
+    {
+        <Vec<T>>::visit_dependencies(v);
+    }
+
+    fn visit_generics(v: &mut impl TypeVisitor)
+    where
+        Self: 'static,
+    {
+        <Vec<T>>::visit_generics(v);
+    }
+
+    fn decl() -> String {
+        <Vec<T>>::decl()
+    }
+
+    fn decl_concrete() -> String {
+        <Vec<T>>::decl_concrete()
+    }
+
+    fn output_path() -> Option<PathBuf> {
+        <Vec<T>>::output_path()
+    }
+}
+
+/// Same type as a `[T]` (`Array<T>`), matching how an `IndexSlice` is
+/// serialized.
+impl<I: Idx, T: TS> TS for IndexSlice<I, [T]> {
+    type WithoutGenerics = <[T] as TS>::WithoutGenerics;
+    type OptionInnerType = Self;
+
+    fn ident() -> String {
+        <[T]>::ident()
+    }
+
+    fn name() -> String {
+        <[T]>::name()
+    }
+
+    fn inline() -> String {
+        <[T]>::inline()
+    }
+
+    fn inline_flattened() -> String {
+        <[T]>::inline_flattened()
+    }
+
+    fn visit_dependencies(v: &mut impl TypeVisitor)
+    where
+        Self: 'static,
+    {
+        <[T]>::visit_dependencies(v);
+    }
+
+    fn visit_generics(v: &mut impl TypeVisitor)
+    where
+        Self: 'static,
+    {
+        <[T]>::visit_generics(v);
+    }
+
+    fn decl() -> String {
+        <[T]>::decl()
+    }
+
+    fn decl_concrete() -> String {
+        <[T]>::decl_concrete()
+    }
+
+    fn output_path() -> Option<PathBuf> {
+        <[T]>::output_path()
+    }
+}
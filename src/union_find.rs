@@ -0,0 +1,131 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Idx, IndexVec};
+
+/// A disjoint-set forest over an index domain, with union by rank and path
+/// compression.
+///
+/// ```rust
+/// use oxc_index::UnionFind;
+///
+/// oxc_index::define_index_type! {
+///     pub struct TypeVar = u32;
+/// }
+///
+/// let mut vars = UnionFind::<TypeVar>::new(4);
+/// assert!(vars.union(TypeVar::new(0), TypeVar::new(2)));
+/// assert!(vars.union(TypeVar::new(2), TypeVar::new(3)));
+/// assert!(!vars.union(TypeVar::new(0), TypeVar::new(3)));
+/// assert_eq!(vars.find(TypeVar::new(3)), vars.find(TypeVar::new(0)));
+/// assert_eq!(
+///     vars.classes().collect::<Vec<_>>(),
+///     [vec![TypeVar::new(0), TypeVar::new(2), TypeVar::new(3)], vec![TypeVar::new(1)]],
+/// );
+/// ```
+#[derive(Clone)]
+pub struct UnionFind<I: Idx> {
+    parents: IndexVec<I, I>,
+    ranks: IndexVec<I, u8>,
+}
+
+impl<I: Idx> UnionFind<I> {
+    /// Construct a `UnionFind` with the indices `0..len`, each in its own
+    /// set.
+    #[inline]
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).map(I::from_usize).collect(),
+            ranks: IndexVec::from_vec(alloc::vec![0; len]),
+        }
+    }
+
+    /// Returns the number of indices.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns true if there are no indices.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Add a new index in a set of its own, and return it.
+    #[inline]
+    pub fn push(&mut self) -> I {
+        self.ranks.push(0);
+        let idx = self.parents.next_idx();
+        self.parents.push(idx)
+    }
+
+    /// Returns the representative of the set containing `idx`. Every index
+    /// in a set has the same representative until the next
+    /// [`UnionFind::union`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn find(&mut self, mut idx: I) -> I {
+        // Path halving: point every other node on the path at its grandparent.
+        loop {
+            let parent = self.parents[idx];
+            if parent == idx {
+                return idx;
+            }
+            let grandparent = self.parents[parent];
+            self.parents[idx] = grandparent;
+            idx = grandparent;
+        }
+    }
+
+    /// Merge the sets containing `a` and `b`. Returns false if they were
+    /// already the same set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is out of bounds.
+    pub fn union(&mut self, a: I, b: I) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (child, root) = if self.ranks[a] < self.ranks[b] { (a, b) } else { (b, a) };
+        self.parents[child] = root;
+        if self.ranks[child] == self.ranks[root] {
+            self.ranks[root] += 1;
+        }
+        true
+    }
+
+    /// Returns true if `a` and `b` are in the same set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is out of bounds.
+    #[inline]
+    pub fn same_set(&mut self, a: I, b: I) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Iterate over the sets, each as its indices in ascending order. The
+    /// sets are ordered by their smallest index.
+    pub fn classes(&mut self) -> impl Iterator<Item = Vec<I>> + use<I> {
+        let mut classes: IndexVec<I, Vec<I>> =
+            IndexVec::from_vec(alloc::vec![Vec::new(); self.len()]);
+        let mut first: IndexVec<I, Option<I>> = IndexVec::from_vec(alloc::vec![None; self.len()]);
+        for idx in self.parents.indices() {
+            let root = self.find(idx);
+            let first = *first[root].get_or_insert(idx);
+            classes[first].push(idx);
+        }
+        classes.into_iter().filter(|class| !class.is_empty())
+    }
+}
+
+impl<I: Idx> fmt::Debug for UnionFind<I> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("UnionFind").field("parents", &self.parents).finish()
+    }
+}
@@ -0,0 +1,125 @@
+use core::ops::Deref;
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// An [`IndexVec`] wrapper which reports every insertion and overwrite to a
+/// user-provided callback.
+///
+/// This is useful when there are caches or dirty-tracking layers that depend
+/// on the contents of a table: instead of every mutation site remembering to
+/// invalidate them, they can subscribe to the callback once.
+///
+/// Read access is available through `Deref` to [`IndexSlice`]. Mutable access
+/// only goes through the methods on this type, so that no write can bypass the
+/// callback. If you need unobserved access, use [`WatchedIndexVec::into_inner`].
+///
+/// ```rust
+/// use oxc_index::{IndexVec, WatchedIndexVec};
+///
+/// oxc_index::define_index_type! {
+///     pub struct NodeIdx = u32;
+/// }
+///
+/// let mut dirty = Vec::new();
+/// let mut nodes = WatchedIndexVec::new(IndexVec::<NodeIdx, u32>::new(), |idx| dirty.push(idx));
+/// let a = nodes.push(1);
+/// let b = nodes.push(2);
+/// nodes.set(a, 10);
+/// drop(nodes);
+/// assert_eq!(dirty, [a, b, a]);
+/// ```
+pub struct WatchedIndexVec<I: Idx, T, F: FnMut(I)> {
+    vec: IndexVec<I, T>,
+    on_change: F,
+}
+
+impl<I: Idx, T, F: FnMut(I)> WatchedIndexVec<I, T, F> {
+    /// Wrap `vec`, calling `on_change` with the index of every element which is
+    /// inserted or overwritten from now on.
+    #[inline]
+    pub fn new(vec: IndexVec<I, T>, on_change: F) -> Self {
+        Self { vec, on_change }
+    }
+
+    /// Unwrap the underlying vector, dropping the callback.
+    #[inline]
+    pub fn into_inner(self) -> IndexVec<I, T> {
+        self.vec
+    }
+
+    /// Get the underlying vector.
+    #[inline(always)]
+    pub fn as_index_vec(&self) -> &IndexVec<I, T> {
+        &self.vec
+    }
+
+    /// Push a new item onto the vector, notify the callback, and return its
+    /// index.
+    #[inline]
+    pub fn push(&mut self, value: T) -> I {
+        let idx = self.vec.push(value);
+        (self.on_change)(idx);
+        idx
+    }
+
+    /// Insert an item at `index`, shifting everything after it, and notify the
+    /// callback with `index` and the index of every element which moved up by
+    /// one, in order. See [`alloc::vec::Vec::insert`].
+    #[inline]
+    pub fn insert(&mut self, index: I, value: T) {
+        self.vec.insert(index, value);
+        for i in index.index()..self.vec.len() {
+            (self.on_change)(I::from_usize(i));
+        }
+    }
+
+    /// Overwrite the item at `index`, notify the callback, and return the
+    /// previous value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn set(&mut self, index: I, value: T) -> T {
+        let old = core::mem::replace(&mut self.vec[index], value);
+        (self.on_change)(index);
+        old
+    }
+
+    /// Mutate the item at `index` in place through `f`, then notify the
+    /// callback. Returns whatever `f` returns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn modify<R, G: FnOnce(&mut T) -> R>(&mut self, index: I, f: G) -> R {
+        let result = f(&mut self.vec[index]);
+        (self.on_change)(index);
+        result
+    }
+
+    /// Extend the vector with the contents of an iterator, notifying the
+    /// callback once for each new element.
+    #[inline]
+    pub fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<I: Idx, T, F: FnMut(I)> Deref for WatchedIndexVec<I, T, F> {
+    type Target = IndexSlice<I, [T]>;
+
+    #[inline]
+    fn deref(&self) -> &IndexSlice<I, [T]> {
+        self.vec.as_slice()
+    }
+}
+
+impl<I: Idx, T: core::fmt::Debug, F: FnMut(I)> core::fmt::Debug for WatchedIndexVec<I, T, F> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.vec, fmt)
+    }
+}
@@ -0,0 +1,24 @@
+use zeroize::Zeroize;
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// Zeroes every element, then clears the vector (see `Vec`'s `Zeroize`).
+///
+/// `IndexVec` can't implement `ZeroizeOnDrop` itself, since its `raw` field is
+/// public and may be moved out of; wrap it in [`zeroize::Zeroizing`] instead.
+impl<I: Idx, T: Zeroize> Zeroize for IndexVec<I, T> {
+    #[inline]
+    fn zeroize(&mut self) {
+        self.raw.zeroize();
+    }
+}
+
+impl<I: Idx, T> Zeroize for IndexSlice<I, [T]>
+where
+    [T]: Zeroize,
+{
+    #[inline]
+    fn zeroize(&mut self) {
+        self.raw.zeroize();
+    }
+}
@@ -5,6 +5,8 @@
     clippy::should_panic_without_expect,
     clippy::cast_possible_truncation
 )]
+// `IMPL_STEP` is only tested with `RUSTFLAGS='--cfg nightly' cargo +nightly test`.
+#![cfg_attr(nightly, feature(step_trait))]
 
 use oxc_index::{IndexSlice, IndexVec, index_vec};
 
@@ -619,3 +621,2301 @@ fn test_nonmax_with_attrs() {
     assert_eq!(idx.index(), 42);
     assert_eq!(idx.raw().get(), 42);
 }
+
+#[test]
+fn test_watched() {
+    use oxc_index::WatchedIndexVec;
+
+    let mut changed = Vec::new();
+    let mut v = WatchedIndexVec::new(IndexVec::<Idx32, i32>::new(), |idx| changed.push(idx));
+    let a = v.push(1);
+    let b = v.push(2);
+    v.insert(a, 0);
+    assert_eq!(v.set(b, 5), 1);
+    assert_eq!(v.modify(a, |x| core::mem::replace(x, 7)), 0);
+    v.extend([8, 9]);
+    assert_eq!(v[..], [7, 5, 2, 8, 9]);
+    let v = v.into_inner();
+    assert_eq!(v, [7, 5, 2, 8, 9]);
+    // The insert reports every element it shifted.
+    assert_eq!(changed, [0usize, 1, 0, 1, 2, 1, 0, 3, 4]);
+}
+
+#[test]
+fn test_len_token() {
+    let mut primary: IndexVec<Idx32, &str> = index_vec!["a", "b"];
+    let mut side = IndexVec::<Idx32, u32>::new_for(primary.len_token());
+    assert_eq!(side, [0, 0]);
+    assert!(side.matches_len(primary.len_token()));
+
+    primary.push("c");
+    assert!(!side.matches_len(primary.len_token()));
+    side.resize_for(primary.len_token());
+    side.assert_len_for(primary.len_token());
+
+    let doubled = IndexVec::<Idx32, usize>::new_for_with(primary.len_token(), |i| i.index() * 2);
+    assert_eq!(doubled, [0, 2, 4]);
+    assert!(primary.len_token().contains(Idx32::new(2)));
+    assert!(!primary.len_token().contains(Idx32::new(3)));
+}
+
+#[test]
+#[should_panic]
+fn test_len_token_mismatch() {
+    let primary: IndexVec<Idx32, &str> = index_vec!["a", "b"];
+    let side: IndexVec<Idx32, u32> = index_vec![0];
+    side.assert_len_for(primary.len_token());
+}
+
+#[test]
+fn test_partition_in_place() {
+    let mut v: IndexVec<Idx32, i32> = index_vec![1, 2, 3, 4, 5, 6];
+    assert!(!v.is_partitioned(|&x| x % 2 == 0));
+    let split = v.partition_in_place(|&x| x % 2 == 0);
+    assert_eq!(split, Idx32::new(3));
+    assert!(v.is_partitioned(|&x| x % 2 == 0));
+    assert!(v[..split].iter().all(|&x| x % 2 == 0));
+    assert!(v[split..].iter().all(|&x| x % 2 != 0));
+
+    let mut empty: IndexVec<Idx32, i32> = index_vec![];
+    assert_eq!(empty.partition_in_place(|_| true), Idx32::new(0));
+    assert!(empty.is_partitioned(|_| false));
+}
+
+#[test]
+fn test_all() {
+    assert_eq!(ZeroMax::all().collect::<Vec<_>>(), [ZeroMax::new(0)]);
+    assert_eq!(SmallCheckedEarly::all().count(), 0x80);
+    assert_eq!(SmallCheckedEarly::all().next_back(), Some(SmallCheckedEarly::new(0x7f)));
+    assert_eq!(Idx8::all().map(Idx8::index).sum::<usize>(), (0..=255usize).sum::<usize>());
+}
+
+#[test]
+fn test_try_convert() {
+    let v: IndexVec<Idx32, Idx32> = (0..300).map(Idx32::new).collect();
+    let v = v.try_convert_indices::<Idx8>().unwrap_err();
+    let v = v.try_convert_indices::<Idx16>().unwrap();
+    assert_eq!(v.len(), 300);
+    assert!(v.try_convert_values::<Idx8>().is_none());
+    let narrowed: IndexVec<Idx16, Idx16> = v.try_convert_values().unwrap();
+    assert_eq!(narrowed[Idx16::new(299)], Idx16::new(299));
+}
+
+#[test]
+fn test_idx_hasher() {
+    use std::hash::BuildHasher;
+
+    let build = oxc_index::BuildIdxHasher::default();
+    assert_eq!(build.hash_one(Idx32::new(1234)), 1234);
+    assert_eq!(build.hash_one(Idx8::new(7)), 7);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_idx_hash_map() {
+    let mut map = oxc_index::IdxHashMap::<Idx32, &str>::default();
+    map.insert(Idx32::new(3), "three");
+    assert_eq!(map.get(&Idx32::new(3)), Some(&"three"));
+
+    let mut set = oxc_index::IdxHashSet::<Idx32>::default();
+    assert!(set.insert(Idx32::new(3)));
+    assert!(!set.insert(Idx32::new(3)));
+}
+
+#[test]
+fn test_diff() {
+    let old: IndexVec<Idx32, i32> = index_vec![1, 2, 3];
+    let new: IndexVec<Idx32, i32> = index_vec![1, 5, 3, 4, 6];
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.changed, [Idx32::new(1)]);
+    assert_eq!(diff.appended, Idx32::new(3)..Idx32::new(5));
+    assert!(diff.truncated.is_empty());
+
+    let diff = new.diff(&old);
+    assert_eq!(diff.changed, [Idx32::new(1)]);
+    assert!(diff.appended.is_empty());
+    assert_eq!(diff.truncated, Idx32::new(3)..Idx32::new(5));
+
+    assert!(old.diff(&old).is_empty());
+}
+
+#[test]
+fn test_map_enumerated() {
+    let mut v: IndexVec<Idx32, usize> = index_vec![10, 20, 30];
+    let m = v.map_enumerated(|i, &x| x + i.index());
+    assert_eq!(m, [10, 21, 32]);
+
+    let mut seen = vec![];
+    v.for_each_enumerated(|i, &x| seen.push((i, x)));
+    assert_eq!(seen, [(Idx32::new(0), 10), (Idx32::new(1), 20), (Idx32::new(2), 30)]);
+
+    v.for_each_mut_enumerated(|i, x| *x = i.index());
+    assert_eq!(v, [0, 1, 2]);
+
+    let s: IndexVec<Idx32, String> = v.into_map_enumerated(|i, x| format!("{}:{}", i.index(), x));
+    assert_eq!(s, ["0:0", "1:1", "2:2"]);
+}
+
+#[test]
+fn test_rotate() {
+    let mut v: IndexVec<Idx32, i32> = index_vec![0, 1, 2, 3, 4];
+    v.rotate_left(Idx32::new(2));
+    assert_eq!(v, [2, 3, 4, 0, 1]);
+    v.rotate_right(Idx32::new(2));
+    assert_eq!(v, [0, 1, 2, 3, 4]);
+    v.rotate_left_by(1);
+    assert_eq!(v, [1, 2, 3, 4, 0]);
+    v.rotate_right_by(1);
+    assert_eq!(v, [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn test_truncate_trailing() {
+    let mut v: IndexVec<Idx32, Option<i32>> = index_vec![Some(1), None, Some(2), None, None];
+    assert_eq!(v.truncate_trailing(Option::is_none), Idx32::new(3));
+    assert_eq!(v, [Some(1), None, Some(2)]);
+    assert_eq!(v.truncate_trailing(Option::is_none), Idx32::new(3));
+    assert_eq!(v.truncate_trailing(|_| true), Idx32::new(0));
+    assert!(v.is_empty());
+}
+
+#[test]
+fn test_prefix_sum() {
+    let counts: IndexVec<Idx32, usize> = index_vec![2, 0, 3, 1];
+    let (offsets, total) = counts.prefix_sum();
+    assert_eq!(offsets, [0, 2, 2, 5]);
+    assert_eq!(total, 6);
+    assert_eq!(counts.inclusive_prefix_sum(), [2, 2, 5, 6]);
+
+    let empty: IndexVec<Idx32, usize> = index_vec![];
+    assert_eq!(empty.prefix_sum(), (index_vec![], 0));
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn test_par_prefix_sum() {
+    let counts: IndexVec<Idx32, usize> = (0..1000).map(|i| i % 7).collect();
+    assert_eq!(counts.par_prefix_sum(), counts.prefix_sum());
+
+    let empty: IndexVec<Idx32, usize> = index_vec![];
+    assert_eq!(empty.par_prefix_sum(), (index_vec![], 0));
+}
+
+#[test]
+fn test_atomic_bitset() {
+    let mut set = oxc_index::AtomicIndexBitSet::<Idx32>::new(130);
+    std::thread::scope(|s| {
+        for t in 0..4 {
+            let set = &set;
+            s.spawn(move || {
+                for i in (t..130).step_by(4) {
+                    if i % 3 == 0 {
+                        set.insert(Idx32::new(i));
+                    }
+                }
+            });
+        }
+    });
+    assert_eq!(set.count(), 44);
+    assert!(set.contains(Idx32::new(129)));
+    assert!(!set.contains(Idx32::new(128)));
+    assert!(set.remove(Idx32::new(129)));
+    assert!(!set.remove(Idx32::new(129)));
+    assert_eq!(set.iter().take(3).collect::<Vec<_>>(), [0usize, 3, 6]);
+    set.clear();
+    assert_eq!(set.count(), 0);
+}
+
+#[test]
+#[cfg(feature = "dashmap")]
+fn test_concurrent_map() {
+    let map = oxc_index::ConcurrentIndexMap::<Idx32, usize>::new();
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for i in 0..50 {
+                    let v = *map.get_or_insert_with(Idx32::new(i), || i * 2);
+                    assert_eq!(v, i * 2);
+                }
+            });
+        }
+    });
+    assert_eq!(map.len(), 50);
+    assert_eq!(map.insert(Idx32::new(0), 7), Some(0));
+    assert_eq!(map.get(Idx32::new(0)).as_deref(), Some(&7));
+    assert_eq!(map.remove(Idx32::new(0)), Some(7));
+    assert!(!map.contains(Idx32::new(0)));
+
+    // `DashMap` shards by the top bits of the hash, so those have to vary.
+    use std::hash::BuildHasher;
+    let map = map.into_inner();
+    let top_bits: std::collections::HashSet<u64> =
+        (0..64).map(|i| map.hasher().hash_one(Idx32::new(i)) >> 58).collect();
+    assert!(top_bits.len() > 1);
+}
+
+#[test]
+fn test_once_index_vec() {
+    let v = oxc_index::OnceIndexVec::<Idx32, String>::new(3);
+    assert_eq!(v.get(Idx32::new(1)), None);
+    assert_eq!(v.get_or_init(Idx32::new(1), || "a".to_string()), "a");
+    assert_eq!(v.get_or_init(Idx32::new(1), || unreachable!()), "a");
+    assert_eq!(v.set(Idx32::new(1), "b".to_string()), Err("b".to_string()));
+    assert_eq!(v.set(Idx32::new(2), "c".to_string()), Ok(()));
+    assert_eq!(v.into_index_vec(), [None, Some("a".to_string()), Some("c".to_string())]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_sync_once_index_vec() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let calls = AtomicUsize::new(0);
+    let v = oxc_index::SyncOnceIndexVec::<Idx32, usize>::new(10);
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for i in 0..10 {
+                    let x = v.get_or_init(Idx32::new(i), || {
+                        calls.fetch_add(1, Ordering::Relaxed);
+                        i * i
+                    });
+                    assert_eq!(*x, i * i);
+                }
+            });
+        }
+    });
+    assert_eq!(calls.load(Ordering::Relaxed), 10);
+}
+
+#[test]
+fn test_index_rope() {
+    use oxc_index::IndexRope;
+
+    let mut rope = IndexRope::<Idx32, usize>::new();
+    let mut model = Vec::new();
+    // Deterministic pseudo-random edits, checked against a plain Vec.
+    let mut x = 12345usize;
+    for _ in 0..2000 {
+        x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let r = x >> 33;
+        if model.is_empty() || r % 3 != 0 {
+            let pos = r % (model.len() + 1);
+            rope.insert(Idx32::new(pos), r);
+            model.insert(pos, r);
+        } else {
+            let pos = r % model.len();
+            assert_eq!(rope.remove(Idx32::new(pos)), model.remove(pos));
+        }
+    }
+    assert_eq!(rope.len(), model.len());
+    assert!(rope.iter().eq(model.iter()));
+    for (i, v) in model.iter().enumerate() {
+        assert_eq!(rope[Idx32::new(i)], *v);
+    }
+
+    rope[Idx32::new(0)] = 7;
+    assert_eq!(rope.get(Idx32::new(0)), Some(&7));
+    assert_eq!(rope.get(rope.next_idx()), None);
+    let v = rope.to_index_vec();
+    assert_eq!(v.len(), model.len());
+    assert_eq!(rope.pop(), model.pop());
+    rope.clear();
+    assert!(rope.is_empty());
+}
+
+#[test]
+fn test_sort_by_index_key() {
+    let mut v: IndexVec<Idx32, (Idx16, &str)> = index_vec![
+        (Idx16::new(3), "a"),
+        (Idx16::new(1), "b"),
+        (Idx16::new(3), "c"),
+        (Idx16::new(0), "d"),
+        (Idx16::new(1), "e"),
+    ];
+    v.sort_by_index_key(|&(k, _)| k);
+    assert_eq!(v.iter().map(|&(_, s)| s).collect::<String>(), "dbeac");
+
+    let mut ids: IndexVec<Idx32, Idx8> = [5, 2, 9, 2, 0].into_iter().map(Idx8::new).collect();
+    ids.sort_indices();
+    assert_eq!(ids, [0usize, 2, 2, 5, 9].map(Idx8::new));
+
+    let mut empty: IndexVec<Idx32, Idx8> = index_vec![];
+    empty.sort_indices();
+    assert!(empty.is_empty());
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn test_par_from_fn() {
+    let v = IndexVec::<Idx32, usize>::par_from_fn(1000, |i| i.index() * 3);
+    assert_eq!(v.len(), 1000);
+    assert!(v.iter_enumerated().all(|(i, &x)| x == i.index() * 3));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_atomic_bitset_serde() {
+    let set: oxc_index::AtomicIndexBitSet<Idx32> = [1, 9, 70].into_iter().map(Idx32::new).collect();
+    assert_eq!(set.domain_size(), 71);
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(json, "[71,[2,2,0,0,0,0,0,0,64]]");
+    let mut back: oxc_index::AtomicIndexBitSet<Idx32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.iter().collect::<Vec<_>>(), [1usize, 9, 70]);
+    assert!(serde_json::from_str::<oxc_index::AtomicIndexBitSet<Idx32>>("[71,[1]]").is_err());
+}
+
+#[test]
+fn test_group_by_key() {
+    use oxc_index::{IndexMultiMap, Len};
+
+    let words = ["apple", "bob", "avocado", "cat", "banana"];
+    let key = |w: &&str| Idx8::new((w.as_bytes()[0] - b'a') as usize);
+
+    let buckets = IndexVec::<Idx8, Vec<&str>>::group_by_key(Len::new(4), words, key);
+    assert_eq!(buckets, [vec!["apple", "avocado"], vec!["bob", "banana"], vec!["cat"], vec![]]);
+
+    let mut map = IndexMultiMap::group_by_key(Len::new(4), words, key);
+    assert_eq!(map.len(), 4);
+    for (k, bucket) in buckets.iter_enumerated() {
+        assert_eq!(map[k], bucket[..]);
+    }
+    assert_eq!(map.get(Idx8::new(4)), None);
+    map.get_mut(Idx8::new(2)).unwrap()[0] = "cow";
+    assert_eq!(map.values(), ["apple", "avocado", "bob", "banana", "cow"]);
+    assert_eq!(map.starts(), &[0, 2, 4, 5]);
+}
+
+#[test]
+fn test_rle_index_vec() {
+    let rle: oxc_index::RleIndexVec<Idx32, char> = "aaabccccd".chars().collect();
+    assert_eq!(rle.len(), 9);
+    assert_eq!(rle.run_count(), 4);
+    assert_eq!(rle[Idx32::new(3)], 'b');
+    assert_eq!(rle.get(Idx32::new(8)), Some(&'d'));
+    assert_eq!(rle.get(Idx32::new(9)), None);
+    assert_eq!(rle.iter().collect::<String>(), "aaabccccd");
+    assert_eq!(rle.to_index_vec().len(), 9);
+
+    let runs: Vec<_> = rle.runs_in(Idx32::new(2)..Idx32::new(6)).collect();
+    assert_eq!(
+        runs,
+        [
+            (Idx32::new(2)..Idx32::new(3), &'a'),
+            (Idx32::new(3)..Idx32::new(4), &'b'),
+            (Idx32::new(4)..Idx32::new(6), &'c'),
+        ]
+    );
+    assert_eq!(rle.runs_in(..).count(), 4);
+    assert_eq!(rle.runs_in(Idx32::new(9)..).count(), 0);
+}
+
+#[test]
+fn test_interval_map() {
+    let mut map = oxc_index::IntervalMap::new();
+    assert_eq!(map.insert(Idx32::new(10)..Idx32::new(20), 'b'), Ok(()));
+    assert_eq!(map.insert(Idx32::new(0)..Idx32::new(5), 'a'), Ok(()));
+    assert_eq!(map.insert(Idx32::new(20)..Idx32::new(30), 'c'), Ok(()));
+    assert_eq!(map.insert(Idx32::new(4)..Idx32::new(11), 'x'), Err('x'));
+    assert_eq!(map.insert(Idx32::new(12)..Idx32::new(13), 'x'), Err('x'));
+    assert_eq!(map.insert(Idx32::new(7)..Idx32::new(7), 'x'), Err('x'));
+    assert_eq!(map.len(), 3);
+
+    assert_eq!(map.get(Idx32::new(0)), Some(&'a'));
+    assert_eq!(map.get(Idx32::new(5)), None);
+    assert_eq!(map.get(Idx32::new(19)), Some(&'b'));
+    assert_eq!(map.get_entry(Idx32::new(20)), Some((Idx32::new(20)..Idx32::new(30), &'c')));
+    assert_eq!(map.get(Idx32::new(30)), None);
+
+    *map.get_mut(Idx32::new(15)).unwrap() = 'B';
+    assert_eq!(map.iter().map(|(_, v)| *v).collect::<String>(), "aBc");
+    assert_eq!(map.remove(Idx32::new(3)), Some((Idx32::new(0)..Idx32::new(5), 'a')));
+    assert!(!map.contains(Idx32::new(3)));
+    assert_eq!(map.insert(Idx32::new(0)..Idx32::new(10), 'z'), Ok(()));
+}
+
+#[test]
+fn test_cow_index_slice() {
+    use std::borrow::Cow;
+
+    fn bump_if_odd(v: &IndexSlice<Idx32, [u32]>) -> Cow<'_, IndexSlice<Idx32, [u32]>> {
+        if v.iter().any(|x| x % 2 == 1) {
+            v.iter().map(|x| x + (x % 2)).collect::<IndexVec<Idx32, u32>>().into()
+        } else {
+            v.into()
+        }
+    }
+
+    let even: IndexVec<Idx32, u32> = index_vec![2, 4];
+    assert!(matches!(bump_if_odd(&even), Cow::Borrowed(_)));
+    let odd: IndexVec<Idx32, u32> = index_vec![1, 4];
+    let bumped = bump_if_odd(&odd);
+    assert!(matches!(bumped, Cow::Owned(_)));
+    assert_eq!(bumped[Idx32::new(0)], 2);
+    let owned: IndexVec<Idx32, u32> = Cow::from(&even).into_owned();
+    assert_eq!(owned, even);
+}
+
+#[test]
+fn test_check_sorted() {
+    let v: IndexVec<Idx32, u32> = index_vec![1, 2, 2, 5, 3, 1];
+    assert!(!v.is_sorted());
+    assert_eq!(v.check_sorted(), Err(Idx32::new(4)));
+    assert_eq!(v[..Idx32::new(4)].check_sorted(), Ok(()));
+    assert!(v.is_sorted_by_key(|_| 0));
+    assert_eq!(v.check_sorted_by_key(|&x| std::cmp::Reverse(x)), Err(Idx32::new(1)));
+
+    let empty: IndexVec<Idx32, f32> = index_vec![];
+    assert_eq!(empty.check_sorted(), Ok(()));
+    let nan: IndexVec<Idx32, f32> = index_vec![1.0, f32::NAN];
+    assert_eq!(nan.check_sorted(), Err(Idx32::new(1)));
+}
+
+#[test]
+fn test_stable_hash() {
+    use oxc_index::{AtomicIndexBitSet, StableHasher, stable_hash};
+    use std::hash::Hasher;
+
+    // Pinned so that any change to the algorithm (or a platform dependence
+    // sneaking in) shows up as a test failure.
+    let v: IndexVec<Idx32, u32> = index_vec![1, 2, 3];
+    assert_eq!(stable_hash(&v), PINNED_INDEX_VEC_HASH);
+
+    let mut a = StableHasher::new();
+    a.write_usize(7);
+    let mut b = StableHasher::new();
+    b.write_u64(7);
+    assert_eq!(a.finish128(), b.finish128());
+
+    assert_eq!(stable_hash(&Idx32::new(5)), stable_hash(&5u32));
+    assert_ne!(stable_hash("ab"), stable_hash("a"));
+    assert_ne!(stable_hash(&[0u8; 3][..]), stable_hash(&[0u8; 4][..]));
+
+    let set: AtomicIndexBitSet<Idx32> = [Idx32::new(1), Idx32::new(70)].into_iter().collect();
+    let same: AtomicIndexBitSet<Idx32> = [Idx32::new(70), Idx32::new(1)].into_iter().collect();
+    assert_eq!(stable_hash(&set), stable_hash(&same));
+    same.remove(Idx32::new(1));
+    assert_ne!(stable_hash(&set), stable_hash(&same));
+}
+
+const PINNED_INDEX_VEC_HASH: u128 = 57640698182866291746676455903060134672;
+
+#[test]
+fn test_dirty_index_vec() {
+    let mut v = oxc_index::DirtyIndexVec::<Idx32, u32>::new(index_vec![0; 100]);
+    assert!(!v.has_dirty());
+    v.set(Idx32::new(99), 1);
+    v.set(Idx32::new(3), 1);
+    v.mark_dirty(Idx32::new(64));
+    assert!(v.is_dirty(Idx32::new(3)));
+    assert!(!v.is_dirty(Idx32::new(4)));
+    assert_eq!(v.take_dirty(), [Idx32::new(3), Idx32::new(64), Idx32::new(99)]);
+    assert!(!v.has_dirty());
+
+    let pushed = v.push(5);
+    v.extend([6, 7]);
+    assert_eq!(v.pop(), Some(7));
+    assert!(v.get_mut(Idx32::new(200)).is_none());
+    assert_eq!(v.take_dirty(), [pushed, Idx32::new(101)]);
+    assert_eq!(v[pushed], 5);
+    assert_eq!(v.into_inner().len(), 102);
+}
+
+#[cfg(feature = "arc-swap")]
+#[test]
+fn test_arc_index_vec() {
+    use oxc_index::ArcIndexVec;
+    use std::sync::Arc;
+
+    let table = Arc::new(ArcIndexVec::<Idx32, u32>::default());
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            let table = Arc::clone(&table);
+            s.spawn(move || {
+                let snapshot = table.load_full();
+                assert!(snapshot.iter().all(|&x| x == snapshot.len() as u32));
+            });
+        }
+        for n in 1..=8 {
+            table.store(index_vec![n; n as usize]);
+        }
+    });
+    let old = table.replace(index_vec![1]);
+    assert_eq!(old.len(), 8);
+    let prev = table.update(|v| v.iter().map(|x| x + 1).collect());
+    assert_eq!(prev.raw, [1]);
+    assert_eq!(Arc::try_unwrap(table).unwrap().into_inner().raw, [2]);
+}
+
+#[test]
+fn test_index_counter() {
+    let mut ids = oxc_index::IndexCounter::<Idx32>::new();
+    assert!(ids.is_empty());
+    assert_eq!(ids.push(), Idx32::new(0));
+    assert_eq!(ids.push_n(3), Idx32::new(1)..Idx32::new(4));
+    assert_eq!(ids.next_idx(), Idx32::new(4));
+    assert_eq!(ids.len(), 4);
+    assert!(ids.contains(Idx32::new(3)));
+    assert!(!ids.contains(Idx32::new(4)));
+    assert_eq!(ids.indices().next_back(), Some(Idx32::new(3)));
+    assert_eq!(std::mem::size_of_val(&ids), std::mem::size_of::<usize>());
+}
+
+#[test]
+fn test_iter_adjacent() {
+    let offsets: IndexVec<Idx32, u32> = index_vec![0, 3, 4, 9];
+    let deltas: Vec<_> = offsets.iter_adjacent().map(|((i, a), (_, b))| (i, b - a)).collect();
+    assert_eq!(deltas, [(Idx32::new(0), 3), (Idx32::new(1), 1), (Idx32::new(2), 5)]);
+    assert_eq!(offsets.iter_adjacent().len(), 3);
+    assert_eq!(offsets[..Idx32::new(1)].iter_adjacent().count(), 0);
+
+    let mut v: IndexVec<Idx32, u32> = index_vec![1, 1, 1, 1];
+    v.for_each_adjacent_mut(|(_, prev), (_, cur)| *cur += *prev);
+    assert_eq!(v, [1, 2, 3, 4]);
+}
+
+oxc_index::define_index_type! {
+    pub struct LocalIdx = u8;
+    CONSTANTS = { RETURN_PLACE = 0, FIRST_ARG = 1, };
+    MAX_INDEX = 100;
+}
+
+#[test]
+fn test_index_constants() {
+    assert_eq!(LocalIdx::RETURN_PLACE, LocalIdx::new(0));
+    assert_eq!(LocalIdx::FIRST_ARG.raw(), 1u8);
+    assert_eq!(LocalIdx::MAX_INDEX, 100);
+}
+
+oxc_index::define_index_type! {
+    pub struct CfgIdx = u16;
+    #[cfg(test)]
+    MAX_INDEX = 1000;
+    #[cfg(not(test))]
+    MAX_INDEX = 10;
+    #[cfg(any())]
+    DISPLAY_FORMAT = "never";
+    DEBUG_FORMAT = "c{}";
+}
+
+#[test]
+fn test_cfg_options() {
+    assert_eq!(CfgIdx::MAX_INDEX, 1000);
+    assert_eq!(format!("{:?}", CfgIdx::new(500)), "c500");
+}
+
+#[test]
+fn test_index_vec_parts() {
+    use oxc_index::IndexVecPart;
+
+    let v: IndexVec<Idx32, u32> = (0..10).collect();
+    let parts = v.clone().into_parts(3);
+    let ranges: Vec<_> = parts.iter().map(IndexVecPart::range).collect();
+    assert_eq!(
+        ranges,
+        [Idx32::new(0)..Idx32::new(4), Idx32::new(4)..Idx32::new(7), Idx32::new(7)..Idx32::new(10)]
+    );
+    assert_eq!(parts[1][Idx32::new(5)], 5);
+    assert_eq!(parts[1].get(Idx32::new(3)), None);
+    assert_eq!(IndexVec::from_parts(parts.into_iter().rev()), v);
+
+    let (a, mut b) = v.clone().split_at_owned(Idx32::new(10));
+    assert_eq!((a.len(), b.len()), (10, 0));
+    assert_eq!(b.iter_mut_enumerated().count(), 0);
+    assert_eq!(IndexVec::from_parts([a, b]), v);
+    assert_eq!(IndexVec::<Idx32, u32>::new().into_parts(4).len(), 4);
+}
+
+#[test]
+#[should_panic(expected = "parts don't cover the table contiguously")]
+fn test_index_vec_parts_gap() {
+    let v: IndexVec<Idx32, u32> = (0..10).collect();
+    let mut parts = v.into_parts(3);
+    parts.remove(1);
+    let _ = IndexVec::from_parts(parts);
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_zeroize() {
+    use oxc_index::zeroize::{Zeroize, Zeroizing};
+
+    let mut v: IndexVec<Idx32, u8> = index_vec![1, 2, 3];
+    v[..Idx32::new(2)].zeroize();
+    assert_eq!(v, [0, 0, 3]);
+    v.zeroize();
+    assert!(v.is_empty());
+
+    let secrets = Zeroizing::new(IndexVec::<Idx32, String>::from_vec(vec!["hunter2".into()]));
+    assert_eq!(secrets[Idx32::new(0)], "hunter2");
+
+    let mut map = oxc_index::IndexMultiMap::group_by_key(
+        oxc_index::Len::<Idx32>::new(2),
+        [1u8, 2, 3],
+        |&x| Idx32::new(usize::from(x % 2)),
+    );
+    map.zeroize();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_diff_debug() {
+    let a: IndexVec<Idx32, u32> = (0..100).collect();
+    let mut b = a.clone();
+    oxc_index::assert_index_vec_eq!(a, b);
+    b[Idx32::new(7)] = 0;
+    b.push(100);
+    let report = a.diff_debug(&b).to_string();
+    assert_eq!(
+        report,
+        "lengths differ: left has 100, right has 101\n  [Test(7)] left: 7, right: 0\n  [Test(100)] left: <missing>, right: 100\n"
+    );
+
+    let zeros: IndexVec<Idx32, u32> = index_vec![0; 100];
+    let report = a.diff_debug(&zeros).to_string();
+    assert_eq!(report.lines().count(), 33);
+    assert!(report.ends_with("  ... and 67 more\n"));
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion `left == right` failed: tables differ\n  [Test(1)] left: 2, right: 3"
+)]
+fn test_assert_index_vec_eq_fails() {
+    let a: IndexVec<Idx32, u32> = index_vec![1, 2];
+    let b: IndexVec<Idx32, u32> = index_vec![1, 3];
+    oxc_index::assert_index_vec_eq!(a, b, "tables {}", "differ");
+}
+
+#[test]
+fn test_iter_segments() {
+    let bindings: IndexVec<Idx32, &str> = index_vec!["a", "b", "c", "d"];
+    let lengths: IndexVec<IdxSz, usize> = index_vec![2, 0, 1, 1];
+    let segments: Vec<_> =
+        bindings.iter_segments(&lengths).map(|(k, s)| (k.index(), s.raw.to_vec())).collect();
+    assert_eq!(segments, [(0, vec!["a", "b"]), (1, vec![]), (2, vec!["c"]), (3, vec!["d"])]);
+
+    let mut v: IndexVec<Idx32, u32> = index_vec![1, 1, 1, 1];
+    for (k, segment) in v.iter_segments_mut(&lengths) {
+        segment.raw.fill(k.index() as u32);
+    }
+    assert_eq!(v, [0, 0, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "segment lengths add up to 3, but the slice has 4 elements")]
+fn test_iter_segments_mismatch() {
+    let v: IndexVec<Idx32, u32> = index_vec![1, 1, 1, 1];
+    let lengths: IndexVec<IdxSz, usize> = index_vec![2, 1];
+    let _ = v.iter_segments(&lengths);
+}
+
+#[test]
+fn test_idx_allocator() {
+    use oxc_index::IdxAllocator;
+
+    let ids = IdxAllocator::<Idx32>::new();
+    let mut seen: Vec<Idx32> = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut mine = vec![ids.alloc()];
+                    mine.extend(ids.reserve(5));
+                    mine
+                })
+            })
+            .collect();
+        handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+    });
+    seen.sort();
+    seen.dedup();
+    assert_eq!(seen.len(), 24);
+    assert_eq!(ids.len_token().get(), 24);
+    assert_eq!(ids.into_counter().next_idx(), Idx32::new(24));
+
+    let small = IdxAllocator::<Idx8>::starting_at(250);
+    assert!(small.try_reserve(7).is_none());
+    assert_eq!(small.len(), 250);
+    assert_eq!(small.reserve(6).next_back(), Some(Idx8::new(255)));
+    assert_eq!(small.try_alloc(), None);
+    assert_eq!(small.try_reserve(0).map(|b| b.len()), Some(0));
+}
+
+#[cfg(feature = "defmt")]
+#[test]
+fn test_defmt_format() {
+    fn assert_format<T: oxc_index::defmt::Format>() {}
+    assert_format::<Idx32>();
+    assert_format::<IdxSz>();
+}
+
+#[test]
+fn test_idx_range() {
+    use oxc_index::IdxRange;
+
+    let range = IdxRange::new(Idx32::new(2), Idx32::new(6));
+    assert_eq!(range.len(), 4);
+    assert!(range.contains(Idx32::new(5)) && !range.contains(Idx32::new(6)));
+    assert_eq!(range.clone().rev().map(Idx32::index).collect::<Vec<_>>(), [5, 4, 3, 2]);
+    let mut iter = range.clone();
+    assert_eq!(iter.nth(1), Some(Idx32::new(3)));
+    assert_eq!(iter.next_back(), Some(Idx32::new(5)));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(format!("{range:?}"), "Test(2)..Test(6)");
+
+    let empty = IdxRange::new(Idx32::new(3), Idx32::new(1));
+    assert!(empty.is_empty());
+    assert_eq!(empty.len(), 0);
+    assert_eq!(empty.count(), 0);
+
+    let mut v: IndexVec<Idx32, u32> = (0..8).collect();
+    assert_eq!(v[range.clone()], [2, 3, 4, 5]);
+    assert_eq!(v.get(IdxRange::new(Idx32::new(7), Idx32::new(9))), None);
+    v[range.clone()].raw.fill(0);
+    assert_eq!(v.drain(range).count(), 4);
+    assert_eq!(v, [0, 1, 6, 7]);
+    assert_eq!(IdxRange::from(Idx32::new(0)..Idx32::new(1)).collect::<Vec<_>>(), [Idx32::new(0)]);
+}
+
+#[test]
+fn test_get_many_mut() {
+    let mut v: IndexVec<Idx32, u32> = index_vec![1, 2, 3];
+    let [a, c] = v.get_many_mut([Idx32::new(0), Idx32::new(2)]).unwrap();
+    std::mem::swap(a, c);
+    assert_eq!(v, [3, 2, 1]);
+    assert!(v.get_many_mut([Idx32::new(1), Idx32::new(1)]).is_none());
+    assert!(v.get_many_mut([Idx32::new(3)]).is_none());
+    let [b, a] = v.index_many_mut([Idx32::new(1), Idx32::new(0)]);
+    *a += *b;
+    assert_eq!(v, [5, 2, 1]);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds for 3 or overlap")]
+fn test_index_many_mut_overlap() {
+    let mut v: IndexVec<Idx32, u32> = index_vec![1, 2, 3];
+    let _ = v.index_many_mut([Idx32::new(2), Idx32::new(2)]);
+}
+
+// A safe `Idx` impl whose equality doesn't match its position: `Tagged(2, 0)`
+// and `Tagged(2, 1)` are different indices for the same slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Tagged(usize, u8);
+
+impl oxc_index::Idx for Tagged {
+    const MAX: usize = usize::MAX;
+
+    unsafe fn from_usize_unchecked(idx: usize) -> Self {
+        Self(idx, 0)
+    }
+
+    fn index(self) -> usize {
+        self.0
+    }
+}
+
+#[test]
+fn test_get_many_mut_same_position() {
+    let mut v: IndexVec<Tagged, u32> = index_vec![1, 2, 3];
+    assert!(v.get_many_mut([Tagged(2, 0), Tagged(2, 1)]).is_none());
+    assert!(v.get_many_mut([Tagged(0, 0), Tagged(3, 1)]).is_none());
+}
+
+#[test]
+#[should_panic(expected = "out of bounds for 3 or overlap")]
+fn test_index_many_mut_same_position() {
+    let mut v: IndexVec<Tagged, u32> = index_vec![1, 2, 3];
+    let _ = v.index_many_mut([Tagged(1, 0), Tagged(1, 1)]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_iter_enumerated() {
+    use rayon::prelude::*;
+
+    let mut v: IndexVec<Idx32, u32> = (0..1000).collect();
+    assert!(v.par_iter_enumerated().all(|(i, &x)| i.index() == x as usize));
+    v.par_iter_mut_enumerated().for_each(|(i, x)| *x += i.raw());
+    assert_eq!(v[Idx32::new(10)], 20);
+
+    let slice = &mut v[Idx32::new(500)..];
+    slice.into_par_iter().for_each(|x| *x = 0);
+    assert_eq!((&v[..Idx32::new(500)]).into_par_iter().filter(|&&x| x == 0).count(), 1);
+
+    let pairs: Vec<(Idx32, u32)> = v.into_par_iter_enumerated().filter(|(_, x)| *x == 0).collect();
+    assert_eq!(pairs.len(), 501);
+    assert_eq!(pairs[1].0, Idx32::new(500));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_index_vec_serde() {
+    let v: IndexVec<Idx32, u32> = vec![3, 1, 4].into();
+    let json = serde_json::to_string(&v).unwrap();
+    assert_eq!(json, "[3,1,4]");
+    assert_eq!(serde_json::to_string(&v[Idx32::new(1)..]).unwrap(), "[1,4]");
+    assert_eq!(serde_json::to_string(&v.clone().into_boxed_slice()).unwrap(), json);
+
+    let back: IndexVec<Idx32, u32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, v);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_serde_map() {
+    let v: IndexVec<Idx32, &str> = vec!["a", "b", "c"].into();
+    let mut json = Vec::new();
+    oxc_index::serde_map::serialize(&v, &mut serde_json::Serializer::new(&mut json)).unwrap();
+    assert_eq!(json, br#"{"0":"a","1":"b","2":"c"}"#);
+
+    let from_str = |s: &'static str| {
+        oxc_index::serde_map::deserialize::<Idx32, &str, _>(
+            &mut serde_json::Deserializer::from_str(s),
+        )
+    };
+    assert_eq!(from_str(r#"{"2":"c","0":"a","1":"b"}"#).unwrap(), v);
+    assert!(from_str("{}").unwrap().is_empty());
+    assert!(from_str(r#"{"0":"a","2":"c"}"#).unwrap_err().to_string().contains("missing index 1"));
+    assert!(
+        from_str(r#"{"0":"a","0":"b"}"#).unwrap_err().to_string().contains("duplicate index 0")
+    );
+    // Rejected without allocating a slot for every index below it.
+    assert!(from_str(r#"{"4294967040":"a"}"#).unwrap_err().to_string().contains("missing index 0"));
+    assert!(
+        from_str(r#"{"1":"b","4294967040":"a","0":"c"}"#)
+            .unwrap_err()
+            .to_string()
+            .contains("missing index 2")
+    );
+}
+
+#[test]
+fn test_try_from_usize() {
+    use oxc_index::Idx;
+
+    assert_eq!(SmallChecked::try_from_usize(255), Some(SmallChecked::new(255)));
+    assert_eq!(SmallChecked::try_from_usize(256), None);
+    assert_eq!(SmallCheckedEarly::try_from_usize(0x80), None);
+    // Checked even when `from_usize` wouldn't be.
+    assert_eq!(SmallUncheckedEarly::try_from_usize(0x80), None);
+    assert_eq!(<ZeroMax as Idx>::try_from_usize(0), Some(ZeroMax::new(0)));
+    assert_eq!(<ZeroMax as Idx>::try_from_usize(1), None);
+    const ONE: Option<Idx8> = Idx8::try_from_usize(1);
+    assert_eq!(ONE, Some(Idx8::new(1)));
+}
+
+#[test]
+#[cfg(feature = "nonmax")]
+fn test_try_from_usize_nonmax() {
+    assert_eq!(
+        IdxNonMax::try_from_usize(IdxNonMax::MAX_INDEX),
+        Some(IdxNonMax::new(IdxNonMax::MAX_INDEX))
+    );
+    assert_eq!(IdxNonMax::try_from_usize(IdxNonMax::MAX_INDEX + 1), None);
+}
+
+oxc_index::define_index_type! {
+    pub struct ParsedIdx = u16;
+    MAX_INDEX = 1000;
+    IMPL_FROM_STR = true;
+}
+
+#[test]
+fn test_from_str() {
+    use oxc_index::ParseIdxError;
+
+    assert_eq!("0".parse::<ParsedIdx>(), Ok(ParsedIdx::new(0)));
+    assert_eq!(ParsedIdx::try_from("1000"), Ok(ParsedIdx::new(1000)));
+    assert_eq!("1001".parse::<ParsedIdx>(), Err(ParseIdxError::TooLarge));
+    assert!(matches!("-1".parse::<ParsedIdx>(), Err(ParseIdxError::Invalid(_))));
+    assert!(matches!("".parse::<ParsedIdx>(), Err(ParseIdxError::Invalid(_))));
+    assert_eq!(ParseIdxError::TooLarge.to_string(), "index is out of range for the index type");
+}
+
+#[test]
+fn test_checked_arith() {
+    assert_eq!(Idx8::new(254).checked_add(1), Some(Idx8::new(255)));
+    assert_eq!(Idx8::new(255).checked_add(1), None);
+    assert_eq!(IdxSz::new(1).checked_add(usize::MAX), None);
+    assert_eq!(Idx8::new(1).checked_sub(1), Some(Idx8::new(0)));
+    assert_eq!(Idx8::new(0).checked_sub(1), None);
+    assert_eq!(SmallUncheckedEarly::new(0x7f).checked_add(1), None);
+}
+
+#[test]
+#[cfg(feature = "nonmax")]
+fn test_checked_arith_nonmax() {
+    let max = IdxNonMax::new(IdxNonMax::MAX_INDEX);
+    assert_eq!(max.checked_add(1), None);
+    assert_eq!(max.checked_sub(1), Some(IdxNonMax::new(IdxNonMax::MAX_INDEX - 1)));
+    assert_eq!(IdxNonMax::new(0).checked_sub(1), None);
+}
+
+#[test]
+fn test_saturating_arith() {
+    assert_eq!(Idx8::new(250).saturating_add(10), Idx8::new(255));
+    assert_eq!(IdxSz::new(1).saturating_add(usize::MAX), IdxSz::new(usize::MAX));
+    assert_eq!(SmallCheckedEarly::new(0x70).saturating_add(0x20), SmallCheckedEarly::new(0x7f));
+    assert_eq!(Idx8::new(3).saturating_sub(10), Idx8::new(0));
+    assert_eq!(Idx8::new(3).saturating_sub(1), Idx8::new(2));
+}
+
+#[test]
+#[cfg(feature = "nonmax")]
+fn test_saturating_arith_nonmax() {
+    assert_eq!(IdxNonMax::new(5).saturating_add(usize::MAX), IdxNonMax::new(IdxNonMax::MAX_INDEX));
+    assert_eq!(IdxNonMax::new(5).saturating_sub(6), IdxNonMax::new(0));
+}
+
+oxc_index::define_index_type! {
+    pub struct OpaqueIdx = u32;
+    NO_ARITHMETIC = true;
+    DEBUG_FORMAT = "Opaque({})";
+}
+
+#[test]
+fn test_no_arithmetic() {
+    let v: IndexVec<OpaqueIdx, &str> = index_vec!["a", "b"];
+    assert_eq!(v.indices().collect::<Vec<_>>(), [OpaqueIdx::new(0), OpaqueIdx::new(1)]);
+    assert_eq!(OpaqueIdx::new(0).checked_add(1), Some(OpaqueIdx::new(1)));
+    assert_eq!(format!("{:?}", OpaqueIdx::new(1)), "Opaque(1)");
+}
+
+oxc_index::define_index_type! {
+    pub struct DerivesIdx = u16;
+    CONSTANTS = { ROOT = 0 };
+    DERIVES = [Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default];
+    #[cfg(all())]
+    DEBUG_FORMAT = "D({})";
+}
+
+impl core::hash::Hash for DerivesIdx {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write_u16(self.raw());
+    }
+}
+
+#[test]
+fn test_custom_derives() {
+    assert_eq!(DerivesIdx::default(), DerivesIdx::ROOT);
+    assert_eq!(format!("{:?}", DerivesIdx::new(3)), "D(3)");
+    let v: IndexVec<DerivesIdx, u8> = index_vec![1, 2];
+    assert_eq!(v[DerivesIdx::new(1)], 2);
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use oxc_index::{Idx, IndexVec, index_vec};
+
+    #[derive(Idx, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    #[idx(max = 1000, debug_format = "N({})", display_format = "n{}")]
+    pub struct NodeId(u16);
+
+    #[derive(Idx, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    #[idx(no_arithmetic, default = OpaqueId::from_raw_unchecked(u8::MAX))]
+    pub(crate) struct OpaqueId(u8);
+
+    #[test]
+    fn test_derive_idx() {
+        assert_eq!(NodeId::MAX_INDEX, 1000);
+        assert_eq!(NodeId::try_from_usize(1001), None);
+        assert_eq!(format!("{:?} {}", NodeId::new(4), NodeId::new(4)), "N(4) n4");
+        assert_eq!(NodeId::new(1) + 2, NodeId::new(3));
+
+        let v: IndexVec<NodeId, &str> = index_vec!["a", "b"];
+        assert_eq!(v[NodeId::new(1)], "b");
+        assert_eq!(<OpaqueId as Idx>::MAX, 255);
+        assert_eq!(OpaqueId::default().index(), 255);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_derive_idx_max() {
+        let _ = NodeId::new(1001);
+    }
+}
+
+#[cfg(feature = "nonmax")]
+oxc_index::define_nonmax_index_type! {
+    pub struct NonMax8 = u8;
+}
+
+#[cfg(feature = "nonmax")]
+oxc_index::define_nonmax_index_type! {
+    pub struct NonMax16 = u16;
+}
+
+#[cfg(feature = "nonmax")]
+oxc_index::define_nonmax_index_type! {
+    pub struct NonMax64 = u64;
+}
+
+#[test]
+#[cfg(feature = "nonmax")]
+fn test_nonmax_widths() {
+    use core::mem::size_of;
+
+    assert_eq!(size_of::<Option<NonMax8>>(), 1);
+    assert_eq!(size_of::<Option<NonMax16>>(), 2);
+    assert_eq!(size_of::<Option<NonMax64>>(), 8);
+
+    assert_eq!(NonMax8::MAX_INDEX, 254);
+    assert_eq!(NonMax8::all().count(), 255);
+    assert_eq!(NonMax8::try_from_usize(255), None);
+    assert_eq!(NonMax16::new(65534).index(), 65534);
+    assert_eq!(format!("{:?}", NonMax16::new(7)), "NonMax16(7)");
+    assert_eq!(NonMax64::new(1 << 40).index(), 1 << 40);
+
+    let v: IndexVec<NonMax16, u8> = index_vec![1, 2, 3];
+    assert_eq!(v[NonMax16::new(2)], 3);
+}
+
+#[test]
+#[should_panic]
+#[cfg(feature = "nonmax")]
+fn test_nonmax_u8_overflow() {
+    let _ = NonMax8::new(255);
+}
+
+oxc_index::define_nonzero_index_type! {
+    pub struct NonZero32 = u32;
+}
+
+oxc_index::define_nonzero_index_type! {
+    pub struct NonZero8 = u8;
+}
+
+#[test]
+fn test_nonzero_index_type() {
+    use core::{mem::size_of, num::NonZeroU32};
+
+    assert_eq!(size_of::<Option<NonZero32>>(), 4);
+    assert_eq!(size_of::<Option<NonZero8>>(), 1);
+
+    assert_eq!(NonZero32::MAX_INDEX, u32::MAX as usize - 1);
+    assert_eq!(NonZero32::new(NonZero32::MAX_INDEX).raw().get(), u32::MAX);
+    assert_eq!(NonZero32::from_raw(NonZeroU32::new(1).unwrap()), NonZero32::new(0));
+    assert_eq!(NonZero32::new(5) + 1, NonZero32::new(6));
+    assert_eq!(format!("{:?}", NonZero32::new(5)), "NonZero32(5)");
+
+    assert_eq!(NonZero8::all().map(|i| i.index()).next_back(), Some(254));
+    assert_eq!(NonZero8::try_from_usize(255), None);
+    assert_eq!(NonZero8::new(0).checked_sub(1), None);
+
+    let v: IndexVec<NonZero8, &str> = index_vec!["a", "b"];
+    assert_eq!(v.last_idx(), NonZero8::new(1));
+}
+
+#[test]
+#[should_panic]
+fn test_nonzero_index_type_overflow() {
+    let _ = NonZero8::new(255);
+}
+
+#[cfg(feature = "nonmax")]
+oxc_index::define_nonmax_index_type! {
+    pub struct ConfiguredNonMax = u32;
+    MAX_INDEX = 1000;
+    DEBUG_FORMAT = "C({})";
+    DISPLAY_FORMAT = "c{}";
+    DEFAULT = ConfiguredNonMax::new(7);
+}
+
+oxc_index::define_nonzero_index_type! {
+    pub struct UncheckedNonZero = u8;
+    MAX_INDEX = 100;
+    DISABLE_MAX_INDEX_CHECK = true;
+}
+
+#[test]
+#[cfg(feature = "nonmax")]
+fn test_nonmax_config() {
+    assert_eq!(ConfiguredNonMax::MAX_INDEX, 1000);
+    assert_eq!(ConfiguredNonMax::try_from_usize(1001), None);
+    assert_eq!(format!("{:?} {}", ConfiguredNonMax::new(3), ConfiguredNonMax::new(3)), "C(3) c3");
+    assert_eq!(ConfiguredNonMax::default().index(), 7);
+    assert_eq!(format!("{:?}", IdxNonMax::new(3)), "IdxNonMax(3)");
+}
+
+#[test]
+#[should_panic]
+#[cfg(feature = "nonmax")]
+fn test_nonmax_config_max() {
+    let _ = ConfiguredNonMax::new(1001);
+}
+
+#[test]
+fn test_nonzero_unchecked() {
+    assert!(!UncheckedNonZero::CHECKS_MAX_INDEX);
+    assert_eq!(UncheckedNonZero::new(101).index(), 101);
+    assert_eq!(UncheckedNonZero::try_from_usize(101), None);
+}
+
+#[cfg(feature = "nonmax")]
+oxc_index::define_nonmax_u32_index_type! {
+    pub struct RawNonMax;
+    MAX_INDEX = 1000;
+    IMPL_RAW_CONVERSIONS = true;
+}
+
+oxc_index::define_nonzero_index_type! {
+    pub struct RawNonZero = u16;
+    IMPL_RAW_CONVERSIONS = true;
+}
+
+#[test]
+#[cfg(feature = "nonmax")]
+fn test_nonmax_raw_conversions() {
+    use oxc_index::{TryFromIdxError, nonmax::NonMaxU32};
+
+    let idx = RawNonMax::try_from(5u32).unwrap();
+    assert_eq!(u32::from(idx), 5);
+    assert_eq!(NonMaxU32::from(idx), NonMaxU32::new(5).unwrap());
+    assert_eq!(RawNonMax::from(NonMaxU32::new(6).unwrap()), RawNonMax::new(6));
+    assert_eq!(RawNonMax::try_from(1001u32), Err(TryFromIdxError));
+}
+
+#[test]
+fn test_nonzero_raw_conversions() {
+    use core::num::NonZeroU16;
+
+    let idx = RawNonZero::try_from(0u16).unwrap();
+    assert_eq!(u16::from(idx), 0);
+    assert_eq!(NonZeroU16::from(idx).get(), 1);
+    assert!(RawNonZero::try_from(u16::MAX).is_err());
+}
+
+oxc_index::define_index_type! {
+    pub struct SentinelIdx = u16;
+    MAX_INDEX = 1000;
+    SENTINEL = u16::MAX as usize;
+    DEBUG_FORMAT = "S({})";
+}
+
+#[test]
+fn test_sentinel() {
+    assert!(SentinelIdx::NONE.is_none());
+    assert!(!SentinelIdx::NONE.is_some());
+    assert_eq!(SentinelIdx::NONE.get(), None);
+    assert_eq!(SentinelIdx::NONE.raw(), u16::MAX);
+    assert!(SentinelIdx::new(1000).is_some());
+    assert_eq!(SentinelIdx::new(4).get(), Some(SentinelIdx::new(4)));
+    assert_eq!(format!("{:?} {:?}", SentinelIdx::NONE, SentinelIdx::new(4)), "None S(4)");
+}
+
+#[test]
+#[cfg(any(feature = "serde", feature = "bincode", feature = "speedy"))]
+fn test_sentinel_roundtrip() {
+    let values = [SentinelIdx::new(4), SentinelIdx::NONE];
+
+    #[cfg(feature = "serde")]
+    for idx in values {
+        let json = serde_json::to_string(&idx).unwrap();
+        assert_eq!(serde_json::from_str::<SentinelIdx>(&json).unwrap(), idx);
+    }
+    #[cfg(feature = "bincode")]
+    for idx in values {
+        use oxc_index::bincode::{self, config};
+
+        let bytes = bincode::encode_to_vec(idx, config::standard()).unwrap();
+        let (back, _): (SentinelIdx, _) =
+            bincode::decode_from_slice(&bytes, config::standard()).unwrap();
+        assert_eq!(idx, back);
+    }
+    #[cfg(feature = "speedy")]
+    for idx in values {
+        use oxc_index::speedy::{Readable, Writable};
+
+        let bytes = idx.write_to_vec().unwrap();
+        assert_eq!(SentinelIdx::read_from_buffer(&bytes).unwrap(), idx);
+    }
+    // Values between `MAX_INDEX` and the sentinel are still rejected.
+    #[cfg(feature = "speedy")]
+    {
+        use oxc_index::speedy::Readable;
+        assert!(SentinelIdx::read_from_buffer(&1001u16.to_le_bytes()).is_err());
+    }
+}
+
+#[test]
+fn test_packed_option() {
+    use core::mem::size_of;
+    use oxc_index::PackedOption;
+
+    assert_eq!(size_of::<PackedOption<Idx32>>(), size_of::<Idx32>());
+
+    let mut p = PackedOption::<Idx8>::default();
+    assert!(p.is_none());
+    assert_eq!(p.expand(), None);
+    assert_eq!(p.replace(Idx8::new(3)), None);
+    assert_eq!(p.map(|i| i.index()), Some(3));
+    assert_eq!(format!("{:?}", p), "Some(3)");
+    assert_eq!(p.take(), Some(Idx8::new(3)));
+    assert_eq!(p, PackedOption::none());
+
+    assert_eq!(PackedOption::from(Some(Idx8::new(254))).unwrap(), Idx8::new(254));
+    assert_eq!(Option::from(PackedOption::<Idx8>::from(None)), None::<Idx8>);
+
+    let table: IndexVec<Idx8, PackedOption<Idx8>> = index_vec![PackedOption::none(); 4];
+    assert!(table.iter().all(|p| p.is_none()));
+}
+
+#[test]
+#[should_panic(expected = "reserved")]
+fn test_packed_option_max() {
+    let _ = oxc_index::PackedOption::some(Idx8::new(255));
+}
+
+#[test]
+fn test_option_idx() {
+    use core::mem::size_of;
+    use oxc_index::OptionIdx;
+
+    assert_eq!(size_of::<OptionIdx<NonZero32>>(), 4);
+    let mut o = OptionIdx::from(Some(NonZero32::new(2)));
+    assert!(o.is_some());
+    assert_eq!(o.map(|i| i.index() * 2), Some(4));
+    assert_eq!(o.take(), Some(NonZero32::new(2)));
+    assert!(o.is_none());
+    assert_eq!(o.unwrap_or(NonZero32::new(7)), NonZero32::new(7));
+    assert_eq!(format!("{:?}", OptionIdx::some(NonZero32::new(1))), "Some(NonZero32(1))");
+    assert_eq!(Option::from(OptionIdx::<NonZero32>::default()), None::<NonZero32>);
+}
+
+#[test]
+fn test_generational_index_vec() {
+    use oxc_index::GenerationalIndexVec;
+
+    let mut v = GenerationalIndexVec::<Idx8, u32>::new();
+    let a = v.insert(1);
+    let b = v.insert(2);
+    assert_eq!(v.len(), 2);
+    assert_eq!(v.remove(a), Some(1));
+    assert_eq!(v.remove(a), None);
+    assert!(!v.contains(a));
+
+    let c = v.insert(3);
+    assert_eq!((c.index(), c.generation()), (a.index(), 1));
+    assert_eq!(v.get(a), None);
+    v[c] += 10;
+    assert_eq!(v.iter().map(|(k, &x)| (k, x)).collect::<Vec<_>>(), [(c, 13), (b, 2)]);
+    assert_eq!(format!("{:?}", v), "{0v1: 13, 1v0: 2}");
+
+    v.clear();
+    assert!(v.is_empty());
+    assert!(!v.contains(b) && !v.contains(c));
+    let d = v.insert(4);
+    assert_eq!(v.len(), 1);
+    assert_eq!(v.get(d), Some(&4));
+}
+
+#[test]
+#[should_panic(expected = "stale key")]
+fn test_generational_index_vec_stale() {
+    let mut v = oxc_index::GenerationalIndexVec::<Idx8, u32>::new();
+    let a = v.insert(1);
+    v.remove(a);
+    let _ = v[a];
+}
+
+#[test]
+fn test_index_slab() {
+    use oxc_index::IndexSlab;
+
+    let mut slab: IndexSlab<Idx8, &str> = ["a", "b", "c"].into_iter().collect();
+    assert_eq!(slab.remove(Idx8::new(1)), Some("b"));
+    assert_eq!(slab.remove(Idx8::new(1)), None);
+    assert_eq!(slab.remove(Idx8::new(9)), None);
+    assert_eq!(slab.len(), 2);
+    assert!(!slab.contains(Idx8::new(1)));
+    assert_eq!(slab.next_idx(), Idx8::new(1));
+    assert_eq!(slab.insert("d"), Idx8::new(1));
+    assert_eq!(slab.insert("e"), Idx8::new(3));
+    slab[Idx8::new(0)] = "z";
+    assert_eq!(
+        slab.iter_enumerated().map(|(i, &s)| (i.index(), s)).collect::<Vec<_>>(),
+        [(0, "z"), (1, "d"), (2, "c"), (3, "e")]
+    );
+    slab.clear();
+    assert!(slab.is_empty());
+    assert_eq!(slab.insert("f"), Idx8::new(0));
+}
+
+#[test]
+#[should_panic(expected = "no value at index")]
+fn test_index_slab_vacant() {
+    let mut slab = oxc_index::IndexSlab::<Idx8, u32>::new();
+    let a = slab.insert(1);
+    slab.remove(a);
+    let _ = slab[a];
+}
+
+#[test]
+fn test_branded_index_vec() {
+    use oxc_index::BrandedIndexVec;
+
+    let v: IndexVec<Idx8, u32> = index_vec![10, 20];
+    let v = BrandedIndexVec::scope(v, |mut v| {
+        assert!(v.check(Idx8::new(2)).is_none());
+        let c = v.push(30);
+        let b = v.check(Idx8::new(1)).unwrap();
+        assert_eq!((v[b], v[c]), (20, 30));
+        assert_eq!(b.idx(), Idx8::new(1));
+        assert!(b < c);
+        v[b] += 1;
+        v.as_mut_index_slice()[Idx8::new(0)] = 0;
+        assert_eq!(v.indices().rev().map(|i| v[i]).collect::<Vec<_>>(), [30, 21, 0]);
+        v.into_inner()
+    });
+    assert_eq!(v.raw, [0, 21, 30]);
+}
+
+#[test]
+fn test_branded_index_vec_lying_idx() {
+    use oxc_index::{BrandedIndexVec, Idx};
+
+    // A safe, but wrong, `Idx` impl whose `index()` doesn't round-trip.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Lying(usize);
+
+    impl Idx for Lying {
+        const MAX: usize = usize::MAX;
+
+        unsafe fn from_usize_unchecked(idx: usize) -> Self {
+            Self(idx)
+        }
+
+        fn index(self) -> usize {
+            self.0 * 1000
+        }
+    }
+
+    BrandedIndexVec::scope(IndexVec::<Lying, u32>::new(), |mut v| {
+        v.push(1);
+        let b = v.push(2);
+        assert_eq!(v[b], 2);
+        assert_eq!(v.indices().map(|i| v[i]).collect::<Vec<_>>(), [1, 2]);
+    });
+}
+
+#[test]
+fn test_index_bitset() {
+    use oxc_index::IndexBitSet;
+
+    let mut a = IndexBitSet::<Idx32>::new(130);
+    assert!(a.is_empty());
+    assert!(a.insert(Idx32::new(0)));
+    assert!(a.insert(Idx32::new(129)));
+    assert!(!a.insert(Idx32::new(129)));
+    assert!(a.contains(Idx32::new(129)));
+    assert_eq!(a.count(), 2);
+
+    let b: IndexBitSet<Idx32> = [Idx32::new(5), Idx32::new(129)].into_iter().collect();
+    assert_eq!(b.domain_size(), 130);
+    let mut u = a.clone();
+    assert!(u.union(&b));
+    assert!(!u.union(&b));
+    assert_eq!(u.iter().map(Idx32::index).collect::<Vec<_>>(), [0, 5, 129]);
+    let mut i = a.clone();
+    assert!(i.intersect(&b));
+    assert_eq!(i.iter().map(Idx32::index).collect::<Vec<_>>(), [129]);
+    assert!(a.difference(&b));
+    assert_eq!(a.iter().map(Idx32::index).collect::<Vec<_>>(), [0]);
+    assert!(a.remove(Idx32::new(0)));
+    assert!(!a.remove(Idx32::new(0)));
+    assert!(a.is_empty());
+
+    // The domain size is hashed as a `u64` on every platform, then the bytes.
+    use std::hash::{BuildHasher, Hasher};
+    let build =
+        std::hash::BuildHasherDefault::<std::collections::hash_map::DefaultHasher>::default();
+    let set: IndexBitSet<Idx32> = [1, 9].into_iter().map(Idx32::new).collect();
+    let mut expected = build.build_hasher();
+    expected.write_u64(10);
+    expected.write(&[0b10, 0b10]);
+    assert_eq!(build.hash_one(&set), expected.finish());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_index_bitset_serde() {
+    let set: oxc_index::IndexBitSet<Idx32> = [1, 9, 70].into_iter().map(Idx32::new).collect();
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(json, "[71,[2,2,0,0,0,0,0,0,64]]");
+    assert_eq!(serde_json::from_str::<oxc_index::IndexBitSet<Idx32>>(&json).unwrap(), set);
+    // Bits past the domain are dropped.
+    let padded: oxc_index::IndexBitSet<Idx32> = serde_json::from_str("[3,[255]]").unwrap();
+    assert_eq!(padded.count(), 3);
+}
+
+#[test]
+fn test_chunked_index_bitset() {
+    use oxc_index::ChunkedIndexBitSet;
+
+    // Two full chunks and a partial one.
+    let domain = 2 * 2048 + 100;
+    let mut a = ChunkedIndexBitSet::<Idx32>::new(domain);
+    assert!(a.is_empty());
+    for i in 0..2048 {
+        a.insert(Idx32::new(i));
+    }
+    assert!(a.insert(Idx32::new(domain - 1)));
+    assert!(!a.insert(Idx32::new(domain - 1)));
+    assert!(a.contains(Idx32::new(2047)));
+    assert!(!a.contains(Idx32::new(2048)));
+    assert_eq!(a.count(), 2049);
+
+    assert!(a.remove(Idx32::new(5)));
+    assert!(!a.remove(Idx32::new(5)));
+    assert!(a.insert(Idx32::new(5)));
+
+    let mut b = ChunkedIndexBitSet::new(domain);
+    b.extend([3, 3000, domain - 1].map(Idx32::new));
+    let mut u = a.clone();
+    assert!(u.union(&b));
+    assert!(!u.union(&b));
+    assert_eq!(u.count(), 2050);
+    let mut i = a.clone();
+    assert!(i.intersect(&b));
+    assert_eq!(i.iter().map(Idx32::index).collect::<Vec<_>>(), [3, domain - 1]);
+    assert!(a.difference(&b));
+    assert_eq!(a.count(), 2047);
+    assert!(!a.contains(Idx32::new(3)));
+
+    // Filling and emptying a chunk bit by bit ends up equal to the compact form.
+    let mut full = ChunkedIndexBitSet::<Idx32>::new(domain);
+    full.extend((4096..domain).map(Idx32::new));
+    let mut ones = ChunkedIndexBitSet::new(domain);
+    ones.union(&full);
+    assert_eq!(full, ones);
+    for i in 4096..domain {
+        full.remove(Idx32::new(i));
+    }
+    assert!(full.is_empty());
+    assert_eq!(full, ChunkedIndexBitSet::new(domain));
+
+    // A one-bit chunk goes straight between all zeros and all ones.
+    let mut tail = ChunkedIndexBitSet::<Idx32>::new(2049);
+    assert!(tail.insert(Idx32::new(2048)));
+    assert_eq!(tail.count(), 1);
+    assert!(tail.remove(Idx32::new(2048)));
+    assert_eq!(tail, ChunkedIndexBitSet::new(2049));
+
+    // Hashes like the flat bitset, whatever form its chunks are in.
+    use oxc_index::{IndexBitSet, stable_hash};
+    let indices = || (0..2048).chain([3000, domain - 1]).map(Idx32::new);
+    let chunked: ChunkedIndexBitSet<Idx32> = indices().collect();
+    let flat: IndexBitSet<Idx32> = indices().collect();
+    assert_eq!(stable_hash(&chunked), stable_hash(&flat));
+    let mut other = chunked.clone();
+    other.remove(Idx32::new(3000));
+    assert_ne!(stable_hash(&chunked), stable_hash(&other));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_chunked_index_bitset_serde() {
+    use oxc_index::{ChunkedIndexBitSet, IndexBitSet};
+
+    // The same encoding as `IndexBitSet`, so they can read each other's.
+    let set: ChunkedIndexBitSet<Idx32> = [1, 9, 70].into_iter().map(Idx32::new).collect();
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(json, "[71,[2,2,0,0,0,0,0,0,64]]");
+    assert_eq!(serde_json::from_str::<ChunkedIndexBitSet<Idx32>>(&json).unwrap(), set);
+
+    let mut big = ChunkedIndexBitSet::<Idx32>::new(2 * 2048 + 100);
+    big.extend((0..2048).chain([3000, 4100]).map(Idx32::new));
+    let json = serde_json::to_string(&big).unwrap();
+    let flat: IndexBitSet<Idx32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(flat.iter().collect::<Vec<_>>(), big.iter().collect::<Vec<_>>());
+    // Compacted again on the way in, so it compares equal.
+    assert_eq!(serde_json::from_str::<ChunkedIndexBitSet<Idx32>>(&json).unwrap(), big);
+}
+
+#[test]
+fn test_bit_matrix() {
+    use oxc_index::BitMatrix;
+
+    let mut m = BitMatrix::<Idx8, Idx32>::new(3, 70);
+    assert!(m.insert(Idx8::new(0), Idx32::new(1)));
+    assert!(!m.insert(Idx8::new(0), Idx32::new(1)));
+    assert!(m.insert(Idx8::new(0), Idx32::new(69)));
+    assert!(m.insert(Idx8::new(2), Idx32::new(5)));
+    assert!(m.contains(Idx8::new(0), Idx32::new(69)));
+    assert!(!m.contains(Idx8::new(1), Idx32::new(69)));
+
+    assert!(m.union_rows(Idx8::new(0), Idx8::new(2)));
+    assert!(!m.union_rows(Idx8::new(0), Idx8::new(2)));
+    assert_eq!(m.count(Idx8::new(2)), 3);
+    assert_eq!(m.iter(Idx8::new(2)).map(Idx32::index).collect::<Vec<_>>(), [1, 5, 69]);
+    assert_eq!(m.iter(Idx8::new(1)).count(), 0);
+    assert!(m.remove(Idx8::new(2), Idx32::new(5)));
+    assert_eq!(format!("{m:?}"), "{0: {Test(1), Test(69)}, 2: {Test(1), Test(69)}}");
+}
+
+#[test]
+#[should_panic(expected = "column 70 out of bounds for 70")]
+fn test_bit_matrix_column_bounds() {
+    oxc_index::BitMatrix::<Idx8, Idx32>::new(1, 70).insert(Idx8::new(0), Idx32::new(70));
+}
+
+#[test]
+fn test_union_find() {
+    use oxc_index::UnionFind;
+
+    let mut sets = UnionFind::<Idx32>::new(5);
+    assert!(sets.union(Idx32::new(4), Idx32::new(1)));
+    assert!(sets.union(Idx32::new(3), Idx32::new(4)));
+    assert!(!sets.union(Idx32::new(1), Idx32::new(3)));
+    assert!(sets.same_set(Idx32::new(1), Idx32::new(3)));
+    assert!(!sets.same_set(Idx32::new(0), Idx32::new(3)));
+    let new = sets.push();
+    assert_eq!(new, Idx32::new(5));
+    assert!(sets.union(new, Idx32::new(0)));
+    assert_eq!(sets.len(), 6);
+
+    let classes: Vec<Vec<usize>> =
+        sets.classes().map(|class| class.into_iter().map(Idx32::index).collect()).collect();
+    assert_eq!(classes, [vec![0, 5], vec![1, 3, 4], vec![2]]);
+}
+
+#[test]
+fn test_multimap_from_pairs() {
+    use oxc_index::{IndexMultiMap, Len};
+
+    let pairs = [(Idx8::new(2), 'a'), (Idx8::new(0), 'b'), (Idx8::new(2), 'c')];
+    let map = IndexMultiMap::from_pairs(Len::<Idx8>::new(4), pairs);
+    assert_eq!(map.len(), 4);
+    assert_eq!(map[Idx8::new(2)], ['a', 'c']);
+    assert_eq!(map[Idx8::new(0)], ['b']);
+    assert!(map[Idx8::new(3)].is_empty());
+
+    let collected: IndexMultiMap<Idx8, char> = pairs.into_iter().collect();
+    assert_eq!(collected.len(), 3);
+    assert_eq!(collected.values(), ['b', 'a', 'c']);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_interner() {
+    use oxc_index::{BuildIdxHasher, Interner};
+
+    let mut names: Interner<Idx8, String> = ["a", "b", "a"].map(String::from).into_iter().collect();
+    assert_eq!(names.len(), 2);
+    assert_eq!(names.lookup("a"), Some(Idx8::new(0)));
+    assert_eq!(names.intern("c".to_string()), Idx8::new(2));
+    assert_eq!(names.intern("b".to_string()), Idx8::new(1));
+    assert_eq!(names[Idx8::new(2)], "c");
+    assert_eq!(names.get(Idx8::new(3)), None);
+    assert_eq!(names.into_values().raw, ["a", "b", "c"]);
+
+    let mut ids = Interner::<Idx8, u32, BuildIdxHasher>::with_hasher(BuildIdxHasher::default());
+    assert_eq!(ids.intern(7), Idx8::new(0));
+    assert_eq!(ids.lookup(&7), Some(Idx8::new(0)));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_string_interner() {
+    use core::hash::BuildHasherDefault;
+    use oxc_index::StringInterner;
+
+    let mut names: StringInterner<Idx8> = ["a", "", "bc", "a"].into_iter().collect();
+    assert_eq!(names.len(), 3);
+    assert_eq!(names.lookup(""), Some(Idx8::new(1)));
+    assert_eq!(names.intern("bc"), Idx8::new(2));
+    assert_eq!(names.intern("d"), Idx8::new(3));
+    assert_eq!(&names[Idx8::new(2)], "bc");
+    assert_eq!(names.get(Idx8::new(4)), None);
+    assert_eq!(format!("{names:?}"), r#"["a", "", "bc", "d"]"#);
+
+    // Every string hashes the same, so lookups have to walk the chain.
+    #[derive(Default)]
+    struct Collide;
+    impl core::hash::Hasher for Collide {
+        fn finish(&self) -> u64 {
+            0
+        }
+        fn write(&mut self, _: &[u8]) {}
+    }
+    let colliding: StringInterner<Idx8, BuildHasherDefault<Collide>> =
+        ["a", "b", "c", "b"].into_iter().collect();
+    assert_eq!(colliding.len(), 3);
+    assert_eq!(colliding.lookup("b"), Some(Idx8::new(1)));
+    assert_eq!(colliding.lookup("a"), Some(Idx8::new(0)));
+    assert_eq!(colliding.lookup("d"), None);
+}
+
+#[test]
+fn test_index_map() {
+    use std::rc::Rc;
+
+    use oxc_index::IndexMap;
+
+    let mut map = IndexMap::<Idx32, String>::new();
+    assert_eq!(map.insert(Idx32::new(70), "a".to_string()), None);
+    assert_eq!(map.insert(Idx32::new(2), "b".to_string()), None);
+    assert_eq!(map.insert(Idx32::new(70), "c".to_string()).as_deref(), Some("a"));
+    assert_eq!(map.len(), 2);
+    assert!(map.contains(Idx32::new(2)));
+    assert!(!map.contains(Idx32::new(3)));
+    assert!(!map.contains(Idx32::new(1000)));
+    map[Idx32::new(2)].push('!');
+    for (_, v) in map.iter_mut() {
+        v.push('?');
+    }
+    assert_eq!(map.keys().map(Idx32::index).collect::<Vec<_>>(), [2, 70]);
+    assert_eq!(format!("{map:?}"), r#"{Test(2): "b!?", Test(70): "c?"}"#);
+    assert_eq!(map.clone(), map);
+    assert_eq!(map.remove(Idx32::new(2)).as_deref(), Some("b!?"));
+    assert_eq!(map.remove(Idx32::new(2)), None);
+    assert_eq!(map.get(Idx32::new(70)).map(String::as_str), Some("c?"));
+
+    // Values are dropped exactly once.
+    let rc = Rc::new(());
+    let mut rcs: IndexMap<Idx8, Rc<()>> =
+        (0..3).map(|i| (Idx8::new(i * 40), Rc::clone(&rc))).collect();
+    rcs.remove(Idx8::new(40));
+    assert_eq!(Rc::strong_count(&rc), 3);
+    drop(rcs);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+#[cfg(feature = "hashbrown")]
+fn test_sparse_index_map() {
+    use oxc_index::SparseIndexMap;
+
+    let mut map: SparseIndexMap<Idx32, u32> =
+        [(Idx32::new(1_000_000), 1), (Idx32::new(3), 2)].into_iter().collect();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.insert(Idx32::new(3), 5), Some(2));
+    assert!(map.contains(Idx32::new(1_000_000)));
+    *map.get_or_insert_with(Idx32::new(7), || 0) += 1;
+    map[Idx32::new(7)] += 1;
+    let mut entries: Vec<_> = map.iter().map(|(i, &v)| (i.index(), v)).collect();
+    entries.sort_unstable();
+    assert_eq!(entries, [(3, 5), (7, 2), (1_000_000, 1)]);
+    map.retain(|i, _| i.index() < 10);
+    assert_eq!(map.remove(Idx32::new(3)), Some(5));
+    assert_eq!(map.keys().collect::<Vec<_>>(), [Idx32::new(7)]);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "nohash-hasher"))]
+fn test_nohash_aliases() {
+    use oxc_index::{IndexHashMap, IndexHashSet};
+
+    let mut map = IndexHashMap::<Idx32, &str>::default();
+    map.insert(Idx32::new(3), "three");
+    assert_eq!(map[&Idx32::new(3)], "three");
+    let set: IndexHashSet<NonZero32> = [NonZero32::new(1), NonZero32::new(1)].into_iter().collect();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn test_index_range_set() {
+    use oxc_index::IndexRangeSet;
+
+    let r = |a: usize, b: usize| Idx32::new(a)..Idx32::new(b);
+    let ranges = |set: &IndexRangeSet<Idx32>| {
+        set.iter_ranges().map(|x| x.start().index()..x.end().index() + 1).collect::<Vec<_>>()
+    };
+
+    let mut set: IndexRangeSet<Idx32> = [r(10, 20), r(30, 40), r(50, 60)].into_iter().collect();
+    assert!(!set.insert_range(r(12, 18)));
+    assert!(!set.insert_range(r(5, 5)));
+    assert!(set.insert_range(r(15, 35)));
+    assert_eq!(ranges(&set), [10..40, 50..60]);
+    assert!(set.insert(Idx32::new(40)));
+    assert_eq!(ranges(&set), [10..41, 50..60]);
+    assert_eq!(set.count(), 41);
+    assert!(set.contains(Idx32::new(10)));
+    assert!(!set.contains(Idx32::new(41)));
+
+    assert!(set.remove_range(r(20, 55)));
+    assert!(!set.remove_range(r(20, 55)));
+    assert_eq!(ranges(&set), [10..20, 55..60]);
+    assert!(set.remove(Idx32::new(15)));
+    assert_eq!(ranges(&set), [10..15, 16..20, 55..60]);
+
+    let other: IndexRangeSet<Idx32> = [r(0, 12), r(14, 17), r(57, 100)].into_iter().collect();
+    let mut u = set.clone();
+    assert!(u.union(&other));
+    assert_eq!(ranges(&u), [0..20, 55..100]);
+    let mut i = set.clone();
+    assert!(i.intersect(&other));
+    assert_eq!(ranges(&i), [10..12, 14..15, 16..17, 57..60]);
+    assert!(set.difference(&other));
+    assert!(!set.difference(&other));
+    assert_eq!(ranges(&set), [12..14, 17..20, 55..57]);
+    assert_eq!(set.iter().map(Idx32::index).collect::<Vec<_>>(), [12, 13, 17, 18, 19, 55, 56]);
+
+    // The maximum index has no successor to end a range with.
+    let max = Idx8::new(Idx8::MAX_INDEX);
+    let mut set = IndexRangeSet::new();
+    assert!(set.insert(max));
+    assert!(!set.insert(max));
+    assert!(set.insert(Idx8::new(Idx8::MAX_INDEX - 1)));
+    assert!(set.contains(max));
+    assert_eq!(set.num_ranges(), 1);
+    assert_eq!(set.iter_ranges().collect::<Vec<_>>(), [Idx8::new(Idx8::MAX_INDEX - 1)..=max]);
+    assert_eq!(set.iter().collect::<Vec<_>>(), [Idx8::new(Idx8::MAX_INDEX - 1), max]);
+    assert!(set.remove(max));
+    assert!(!set.contains(max));
+    assert_eq!(set.count(), 1);
+}
+
+#[test]
+fn test_interval_map_idx_range() {
+    use oxc_index::{IdxRange, IntervalMap};
+
+    let mut files = IntervalMap::new();
+    files.insert(IdxRange::new(Idx32::new(0), Idx32::new(10)), "a").unwrap();
+    assert_eq!(files.insert(IdxRange::new(Idx32::new(5), Idx32::new(15)), "b"), Err("b"));
+    files.insert(Idx32::new(10)..Idx32::new(15), "b").unwrap();
+    assert_eq!(files.get(Idx32::new(9)), Some(&"a"));
+    assert_eq!(files.get(Idx32::new(10)), Some(&"b"));
+}
+
+#[test]
+fn test_index_priority_queue() {
+    use oxc_index::IndexPriorityQueue;
+
+    let priorities = [5, 1, 8, 3, 9, 2, 7, 4, 6, 0];
+    let mut queue: IndexPriorityQueue<Idx8, i32> =
+        priorities.iter().enumerate().map(|(i, &p)| (Idx8::new(i), p)).collect();
+    assert_eq!(queue.len(), 10);
+    assert_eq!(queue.peek(), Some((Idx8::new(4), &9)));
+
+    assert_eq!(queue.change_priority(Idx8::new(9), 10), Some(0));
+    assert_eq!(queue.push(Idx8::new(4), -1), Some(9));
+    assert_eq!(queue.remove(Idx8::new(2)), Some(8));
+    assert_eq!(queue.remove(Idx8::new(2)), None);
+    assert_eq!(queue.change_priority(Idx8::new(2), 1), None);
+    assert_eq!(queue.priority(Idx8::new(6)), Some(&7));
+
+    let mut order = vec![];
+    while let Some((idx, priority)) = queue.pop() {
+        assert!(!queue.contains(idx));
+        order.push((idx.index(), priority));
+    }
+    assert_eq!(order, [(9, 10), (6, 7), (8, 6), (0, 5), (7, 4), (3, 3), (5, 2), (1, 1), (4, -1)]);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn test_index_vec_deque() {
+    use oxc_index::IndexVecDeque;
+
+    let mut queue: IndexVecDeque<Idx8, u32> = (1..=3).collect();
+    assert_eq!(queue.push_back(4), Idx8::new(3));
+    queue.push_front(0);
+    assert_eq!(queue[Idx8::new(4)], 4);
+    queue[Idx8::new(0)] = 10;
+    assert_eq!(queue.pop_front(), Some(10));
+    assert_eq!(queue.pop_back(), Some(4));
+    assert_eq!(
+        queue.iter_enumerated().map(|(i, &v)| (i.index(), v)).collect::<Vec<_>>(),
+        [(0, 1), (1, 2), (2, 3)]
+    );
+    for (i, v) in queue.iter_mut_enumerated() {
+        *v += i.index() as u32;
+    }
+    assert_eq!(queue.indices().map(|i| queue[i]).collect::<Vec<_>>(), [1, 3, 5]);
+    assert_eq!(format!("{queue:?}"), "[1, 3, 5]");
+}
+
+#[test]
+#[should_panic]
+fn test_index_vec_deque_overflow() {
+    let mut queue: oxc_index::IndexVecDeque<SmallCheckedEarly, ()> =
+        (0..0x7f).map(|_| ()).collect();
+    assert_eq!(queue.push_back(()).index(), 0x7f);
+    queue.push_back(());
+}
+
+#[test]
+fn test_index_array() {
+    use oxc_index::IndexArray;
+
+    const NAMES: IndexArray<Idx8, &str, 3> = IndexArray::new(["a", "b", "c"]);
+    assert_eq!(NAMES[Idx8::new(2)], "c");
+    assert_eq!(NAMES.len(), 3);
+    assert_eq!(NAMES.last_idx(), Idx8::new(2));
+
+    let mut counts = IndexArray::<Idx8, u32, 4>::default();
+    counts[Idx8::new(1)] += 2;
+    for (i, c) in counts.iter_mut_enumerated() {
+        *c += i.index() as u32;
+    }
+    assert_eq!(counts.into_inner(), [0, 3, 2, 3]);
+    assert_eq!(format!("{counts:?}"), "[0, 3, 2, 3]");
+}
+
+#[test]
+#[should_panic]
+fn test_index_array_too_long() {
+    let _ = oxc_index::IndexArray::<SmallCheckedEarly, (), 0x81>::from_fn(|_| ());
+}
+
+#[test]
+fn test_index_array_vec() {
+    use std::rc::Rc;
+
+    use oxc_index::IndexArrayVec;
+
+    let mut v: IndexArrayVec<Idx8, u32, 4> = [1, 2].into_iter().collect();
+    assert_eq!(v.push(3), Idx8::new(2));
+    assert_eq!(v.try_push(4), Ok(Idx8::new(3)));
+    assert!(v.is_full());
+    assert_eq!(v.try_push(5), Err(5));
+    v[Idx8::new(0)] = 10;
+    assert_eq!(v.iter_enumerated().next_back(), Some((Idx8::new(3), &4)));
+    assert_eq!(v.pop(), Some(4));
+    assert_eq!(v.clone(), v);
+    assert_eq!(format!("{v:?}"), "[10, 2, 3]");
+    v.truncate(1);
+    assert_eq!(v.len(), 1);
+
+    let rc = Rc::new(());
+    let mut rcs = IndexArrayVec::<Idx8, Rc<()>, 3>::new();
+    rcs.extend([Rc::clone(&rc), Rc::clone(&rc)]);
+    let cloned = rcs.clone();
+    assert_eq!(Rc::strong_count(&rc), 5);
+    drop((rcs, cloned));
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+#[should_panic(expected = "`IndexArrayVec` is full (capacity 1)")]
+fn test_index_array_vec_full() {
+    let mut v = oxc_index::IndexArrayVec::<Idx8, (), 1>::new();
+    v.push(());
+    v.push(());
+}
+
+#[test]
+#[cfg(feature = "smallvec")]
+fn test_index_small_vec() {
+    use oxc_index::IndexSmallVec;
+
+    let mut v: IndexSmallVec<Idx8, u32, 2> = [1].into_iter().collect();
+    assert_eq!(v.push(2), Idx8::new(1));
+    assert!(!v.spilled());
+    v.extend([3, 4]);
+    assert!(v.spilled());
+    v[Idx8::new(0)] = 10;
+    assert_eq!(v.last_idx(), Idx8::new(3));
+    assert_eq!(v.iter_enumerated().map(|(i, &x)| (i.index(), x)).nth(2), Some((2, 3)));
+    assert_eq!(v.pop(), Some(4));
+    assert_eq!(format!("{v:?}"), "[10, 2, 3]");
+    assert_eq!(v.into_index_vec().raw, [10, 2, 3]);
+}
+
+#[test]
+#[cfg(feature = "thin-vec")]
+fn test_index_thin_vec() {
+    use oxc_index::IndexThinVec;
+
+    assert_eq!(size_of::<IndexThinVec<Idx8, u32>>(), size_of::<usize>());
+
+    let mut v: IndexThinVec<Idx8, u32> = [1, 2].into_iter().collect();
+    assert_eq!(v.push(3), Idx8::new(2));
+    v.insert(Idx8::new(0), 0);
+    assert_eq!(v.remove(Idx8::new(1)), 1);
+    v[Idx8::new(0)] = 10;
+    assert_eq!(v.get(Idx8::new(2)), Some(&3));
+    assert_eq!(v.last_idx(), Idx8::new(2));
+    let tail = v.split_off(Idx8::new(2));
+    assert_eq!(tail.raw.as_slice(), [3]);
+    assert_eq!(format!("{v:?}"), "[10, 2]");
+    assert_eq!(v.into_index_vec().raw, [10, 2]);
+
+    let from_vec: IndexThinVec<Idx8, u32> = IndexVec::from_vec(vec![5, 6]).into();
+    assert_eq!(from_vec.iter().copied().sum::<u32>(), 11);
+}
+
+#[test]
+fn test_index_box_roundtrip() {
+    use oxc_index::IndexBox;
+
+    let mut v = IndexVec::<Idx32, u32>::with_capacity(8);
+    v.extend([1, 2, 3]);
+    let frozen: IndexBox<Idx32, [u32]> = v.into_boxed_slice();
+    assert_eq!(frozen.last_idx(), Idx32::new(2));
+
+    let raw: Box<[u32]> = frozen.into();
+    assert_eq!(&*raw, [1, 2, 3]);
+    let frozen: IndexBox<Idx32, [u32]> = raw.into();
+    assert_eq!(frozen.into_vec().raw, [1, 2, 3]);
+}
+
+#[test]
+fn test_frozen_index_vec() {
+    use oxc_index::FrozenIndexVec;
+
+    let v = FrozenIndexVec::<Idx32, String>::new();
+    let first = &v[v.push("0".to_string())];
+    // Push well past the first few chunks while `first` is still borrowed.
+    for i in 1..100 {
+        assert_eq!(v.push(i.to_string()), Idx32::new(i));
+    }
+    assert_eq!(first, "0");
+    assert_eq!(v.len(), 100);
+    assert!(v.iter_enumerated().all(|(i, s)| *s == i.index().to_string()));
+    assert_eq!(v.get(Idx32::new(100)), None);
+
+    let mut v = v;
+    v[Idx32::new(50)].push('!');
+    assert_eq!(v.iter().nth(50).map(String::as_str), Some("50!"));
+    let raw = v.into_index_vec().raw;
+    assert_eq!(raw.len(), 100);
+    assert_eq!(raw[99], "99");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_concurrent_index_vec() {
+    use oxc_index::ConcurrentIndexVec;
+
+    let v = ConcurrentIndexVec::<Idx32, (usize, usize)>::with_shards(3);
+    let pushed = std::sync::Mutex::new(Vec::new());
+    std::thread::scope(|s| {
+        for t in 0..4 {
+            let (v, pushed) = (&v, &pushed);
+            s.spawn(move || {
+                for i in 0..25 {
+                    let idx = v.push((t, i));
+                    pushed.lock().unwrap().push((idx, (t, i)));
+                }
+            });
+        }
+    });
+    assert_eq!(v.len(), 100);
+    let v = v.into_index_vec();
+    for (idx, value) in pushed.into_inner().unwrap() {
+        assert_eq!(v[idx], value);
+    }
+}
+
+#[test]
+fn test_atomic_idx() {
+    use core::sync::atomic::Ordering;
+    use oxc_index::AtomicIdx;
+
+    let a = AtomicIdx::from(Idx32::new(1));
+    assert_eq!(a.swap(Idx32::new(2), Ordering::Relaxed), Idx32::new(1));
+    assert_eq!(
+        a.compare_exchange(Idx32::new(1), Idx32::new(3), Ordering::Relaxed, Ordering::Relaxed),
+        Err(Idx32::new(2)),
+    );
+    assert_eq!(
+        a.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |i| Some(i + 1)),
+        Ok(Idx32::new(2)),
+    );
+    assert_eq!(format!("{a:?}"), "Test(3)");
+    assert_eq!(a.into_inner(), Idx32::new(3));
+
+    // Stored in the atomic matching the raw type.
+    assert_eq!(size_of::<AtomicIdx<Idx32>>(), 4);
+    assert_eq!(size_of::<AtomicIdx<Idx16>>(), 2);
+    assert_eq!(size_of::<AtomicIdx<NonZero32>>(), 4);
+    assert_eq!(AtomicIdx::new(NonZero32::new(9)).into_inner(), NonZero32::new(9));
+}
+
+#[test]
+fn test_cow_index_vec() {
+    use oxc_index::CowIndexVec;
+
+    let mut a: CowIndexVec<Idx32, u32> = [1, 2].into_iter().collect();
+    assert!(a.get_mut().is_some());
+    let b = a.clone();
+    assert!(a.get_mut().is_none());
+    assert_eq!(a, b);
+
+    a.make_mut()[Idx32::new(0)] = 10;
+    assert_eq!(b[Idx32::new(0)], 1);
+    assert_eq!(format!("{a:?}"), "[10, 2]");
+    // `a` is the only owner now, so this doesn't clone.
+    assert!(a.get_mut().is_some());
+    assert_eq!(a.into_index_vec().raw, [10, 2]);
+    std::thread::spawn(move || assert_eq!(b.iter().sum::<u32>(), 3)).join().unwrap();
+}
+
+#[test]
+#[cfg(feature = "bumpalo")]
+fn test_arena_index_vec() {
+    use oxc_index::{ArenaIndexVec, bumpalo::Bump};
+
+    let arena = Bump::new();
+    let mut v = ArenaIndexVec::<Idx8, u32>::from_iter_in([1, 2], &arena);
+    assert_eq!(v.push(3), Idx8::new(2));
+    v.insert(Idx8::new(0), 0);
+    assert_eq!(v.swap_remove(Idx8::new(0)), 0);
+    assert_eq!(v.get(Idx8::new(0)), Some(&3));
+    v.extend([4]);
+    assert_eq!(format!("{v:?}"), "[3, 1, 2, 4]");
+    let slice = v.into_bump_slice_mut();
+    slice[Idx8::new(1)] = 10;
+    assert_eq!(slice.raw, [3, 10, 2, 4]);
+}
+
+#[test]
+fn test_try_reserve() {
+    let mut v = IndexVec::<Idx32, u64>::try_with_capacity(4).unwrap();
+    assert!(v.raw.capacity() >= 4);
+    v.try_reserve(10).unwrap();
+    v.try_reserve_exact(20).unwrap();
+    assert!(v.raw.capacity() >= 20);
+    assert!(v.try_reserve(usize::MAX).is_err());
+    assert!(IndexVec::<Idx32, u64>::try_with_capacity(usize::MAX).is_err());
+}
+
+#[test]
+fn test_raw_parts_roundtrip() {
+    let v: IndexVec<Idx32, String> = index_vec!["a".to_string(), "b".to_string()];
+    let (ptr, len, cap) = v.into_raw_parts();
+    assert_eq!(len, 2);
+    // SAFETY: the parts came straight from `into_raw_parts`.
+    let raw = unsafe { Vec::from_raw_parts(ptr, len, cap) };
+    let (ptr, len, cap) = IndexVec::<Idx32, _>::from_vec(raw).into_raw_parts();
+    // SAFETY: as above.
+    let v = unsafe { IndexVec::<Idx32, String>::from_raw_parts(ptr, len, cap) };
+    assert_eq!(v[Idx32::new(1)], "b");
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn test_bytemuck() {
+    use oxc_index::IndexSlice;
+
+    let v: IndexVec<Idx8, Idx32> = index_vec![Idx32::new(1), Idx32::new(2)];
+    assert_eq!(oxc_index::bytemuck::cast_slice::<Idx32, u32>(&v.raw), [1, 2]);
+    assert_eq!(<Idx32 as oxc_index::bytemuck::Zeroable>::zeroed(), Idx32::new(0));
+
+    let bytes = v.as_bytes().to_vec();
+    assert_eq!(bytes.len(), 8);
+    let words: IndexVec<Idx8, u32> = index_vec![3, 4];
+    let loaded = IndexSlice::<Idx8, [u32]>::from_bytes(words.as_bytes());
+    assert_eq!(loaded[Idx8::new(1)], 4);
+    assert!(IndexSlice::<Idx8, [u32]>::try_from_bytes(&words.as_bytes()[1..]).is_err());
+
+    let mut words = words;
+    words.as_bytes_mut().fill(0);
+    assert_eq!(words.raw, [0, 0]);
+}
+
+#[test]
+#[cfg(feature = "rkyv")]
+fn test_rkyv_roundtrip() {
+    use oxc_index::rkyv::{self, Archived, rancor::Error};
+
+    let v: IndexVec<Idx8, Idx32> = index_vec![Idx32::new(3), Idx32::new(70000)];
+    let bytes = rkyv::to_bytes::<Error>(&v).unwrap();
+    // SAFETY: `bytes` was just produced by `to_bytes` for this type.
+    let archived = unsafe { rkyv::access_unchecked::<Archived<IndexVec<Idx8, Idx32>>>(&bytes) };
+    assert_eq!(archived[1].get(), Idx32::new(70000));
+    let back: IndexVec<Idx8, Idx32> = rkyv::deserialize::<_, Error>(archived).unwrap();
+    assert_eq!(back, v);
+
+    let id = NonZero32::new(5);
+    let bytes = rkyv::to_bytes::<Error>(&id).unwrap();
+    assert_eq!(bytes.len(), 4);
+    // SAFETY: as above.
+    let archived = unsafe { rkyv::access_unchecked::<Archived<NonZero32>>(&bytes) };
+    assert_eq!(rkyv::deserialize::<NonZero32, Error>(archived).unwrap(), id);
+
+    // Out of range values are an error rather than a panic.
+    let bytes = rkyv::to_bytes::<Error>(&0xffu8).unwrap();
+    // SAFETY: a `SmallCheckedEarly` is archived as a bare `u8`.
+    let archived = unsafe { rkyv::access_unchecked::<Archived<SmallCheckedEarly>>(&bytes) };
+    assert!(archived.try_get().is_none());
+    assert!(rkyv::deserialize::<SmallCheckedEarly, Error>(archived).is_err());
+}
+
+#[test]
+#[cfg(feature = "bincode")]
+fn test_bincode_roundtrip() {
+    use oxc_index::bincode::{self, config};
+
+    let config = config::standard().with_fixed_int_encoding();
+    // `Idx16` is encoded as a `u16`, not a `usize`.
+    assert_eq!(bincode::encode_to_vec(Idx16::new(7), config).unwrap(), [7, 0]);
+
+    let v: IndexVec<Idx8, Idx32> = index_vec![Idx32::new(3), Idx32::new(70000)];
+    let bytes = bincode::encode_to_vec(&v, config).unwrap();
+    assert_eq!(bytes, bincode::encode_to_vec(&v[..], config).unwrap());
+    let (back, _): (IndexVec<Idx8, Idx32>, _) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(back, v);
+
+    let id = NonZero32::new(5);
+    let bytes = bincode::encode_to_vec(id, config).unwrap();
+    assert_eq!(bincode::decode_from_slice::<NonZero32, _>(&bytes, config).unwrap().0, id);
+
+    // Out of range values are an error rather than a panic.
+    let bytes = bincode::encode_to_vec(0xffu8, config).unwrap();
+    assert!(bincode::decode_from_slice::<SmallCheckedEarly, _>(&bytes, config).is_err());
+}
+
+#[test]
+#[cfg(feature = "schemars")]
+fn test_schemars_schema() {
+    use oxc_index::schemars::schema_for;
+
+    let schema = schema_for!(SmallCheckedEarly);
+    assert_eq!(schema.get("type").unwrap(), "integer");
+    assert_eq!(schema.get("maximum").unwrap(), 0x7f);
+
+    let schema = schema_for!(IndexVec<Idx8, Idx16>);
+    assert_eq!(schema.get("type").unwrap(), "array");
+    assert_eq!(schema.get("items").unwrap().get("maximum").unwrap(), Idx16::MAX_INDEX);
+}
+
+#[test]
+#[cfg(feature = "arbitrary")]
+fn test_arbitrary_in_range() {
+    use oxc_index::arbitrary::{Arbitrary, Unstructured};
+
+    let data = [0xff; 64];
+    let mut u = Unstructured::new(&data);
+    assert!(SmallCheckedEarly::arbitrary(&mut u).unwrap().index() <= 0x7f);
+    assert!(NonZero32::arbitrary(&mut u).unwrap().index() <= NonZero32::MAX_INDEX);
+
+    // Never so many elements that the next index would be out of range.
+    let data = [0xff; 1024];
+    let v =
+        IndexVec::<SmallCheckedEarly, u8>::arbitrary_take_rest(Unstructured::new(&data)).unwrap();
+    assert_eq!(v.len(), 0x7f);
+
+    let mut u = Unstructured::new(&[]);
+    assert_eq!(Idx32::arbitrary(&mut u).unwrap(), Idx32::new(0));
+}
+
+#[test]
+#[cfg(feature = "proptest")]
+fn test_proptest_strategies() {
+    use oxc_index::{
+        proptest::{
+            strategy::{Strategy, ValueTree},
+            test_runner::TestRunner,
+        },
+        strategy::{any_idx, idx_in_range, index_vec},
+    };
+
+    let mut runner = TestRunner::deterministic();
+    for _ in 0..100 {
+        let idx = any_idx::<SmallCheckedEarly>().new_tree(&mut runner).unwrap().current();
+        assert!(idx.index() <= 0x7f);
+
+        let idx = idx_in_range(Idx32::new(3)..Idx32::new(5)).new_tree(&mut runner).unwrap();
+        assert!((3..5).contains(&idx.current().index()));
+
+        // The length is clamped so the next index stays valid.
+        let v =
+            index_vec::<SmallCheckedEarly, _, _>(0u8..10, 200..300).new_tree(&mut runner).unwrap();
+        assert_eq!(v.current().len(), 0x7f);
+    }
+
+    // Shrinks towards the start of the range.
+    let mut tree = idx_in_range(Idx32::new(10)..).new_tree(&mut runner).unwrap();
+    while tree.simplify() {}
+    assert_eq!(tree.current(), Idx32::new(10));
+}
+
+#[cfg(feature = "ts-rs")]
+oxc_index::define_index_type! {
+    pub struct BrandedTsIdx = u32;
+    TS_BRANDED = true;
+}
+
+#[cfg(feature = "ts-rs")]
+oxc_index::define_nonzero_index_type! {
+    pub struct BrandedTsNonZero = u32;
+    TS_BRANDED = true;
+}
+
+#[test]
+#[cfg(feature = "ts-rs")]
+fn test_ts_rs_types() {
+    use oxc_index::ts_rs::TS;
+
+    assert_eq!(<IndexVec<Idx8, u32>>::name(), "Array<number>");
+    assert_eq!(Idx32::name(), "number");
+    assert_eq!(<IndexVec<Idx8, NonZero32>>::name(), "Array<number>");
+    assert_eq!(Idx32::output_path(), None);
+
+    // Branding is per type, so it doesn't affect `Idx32` above.
+    assert_eq!(BrandedTsIdx::name(), "BrandedTsIdx");
+    assert_eq!(
+        BrandedTsIdx::decl(),
+        r#"type BrandedTsIdx = number & { readonly __brand: "BrandedTsIdx" };"#
+    );
+    assert_eq!(<IndexVec<Idx8, BrandedTsNonZero>>::name(), "Array<BrandedTsNonZero>");
+}
+
+#[test]
+#[cfg(feature = "napi")]
+fn test_napi_type_name() {
+    use oxc_index::napi::{ValueType, bindgen_prelude::TypeName};
+
+    assert_eq!(Idx32::type_name(), "Idx32");
+    assert_eq!(NonZero32::value_type(), ValueType::Number);
+}
+
+#[test]
+#[cfg(feature = "speedy")]
+fn test_speedy_roundtrip() {
+    use oxc_index::speedy::{Readable, Writable};
+
+    // `Idx16` is written as a `u16`, not a `usize`.
+    assert_eq!(Idx16::new(7).write_to_vec().unwrap(), [7, 0]);
+
+    let v: IndexVec<Idx8, Idx32> = index_vec![Idx32::new(3), Idx32::new(70000)];
+    let bytes = v.write_to_vec().unwrap();
+    assert_eq!(bytes, v[..].write_to_vec().unwrap());
+    assert_eq!(IndexVec::<Idx8, Idx32>::read_from_buffer(&bytes).unwrap(), v);
+
+    let id = NonZero32::new(5);
+    assert_eq!(NonZero32::read_from_buffer(&id.write_to_vec().unwrap()).unwrap(), id);
+
+    // Out of range values are an error rather than a panic.
+    assert!(SmallCheckedEarly::read_from_buffer(&[0xff]).is_err());
+}
+
+#[cfg(nightly)]
+oxc_index::define_index_type! {
+    struct StepIdx = u8;
+    MAX_INDEX = 100;
+    IMPL_STEP = true;
+}
+
+#[test]
+#[cfg(nightly)]
+fn test_impl_step() {
+    let v: Vec<usize> = (StepIdx::new(2)..StepIdx::new(5)).map(StepIdx::index).collect();
+    assert_eq!(v, [2, 3, 4]);
+    assert_eq!((StepIdx::new(0)..=StepIdx::new(3)).rev().next(), Some(StepIdx::new(3)));
+    assert_eq!((StepIdx::new(10)..StepIdx::new(4)).count(), 0);
+
+    // Stepping stops at `MAX_INDEX` rather than producing an invalid index.
+    let max = StepIdx::new(StepIdx::MAX_INDEX);
+    assert_eq!((StepIdx::new(99)..=max).collect::<Vec<_>>(), [StepIdx::new(99), max]);
+    assert_eq!(core::iter::Step::forward_checked(StepIdx::new(100), 1), None::<StepIdx>);
+    assert_eq!(core::iter::Step::backward_checked(StepIdx::new(0), 1), None::<StepIdx>);
+}
+
+// Without `bytemuck`, the struct doesn't get a `repr` of its own.
+#[cfg(not(feature = "bytemuck"))]
+oxc_index::define_index_type! {
+    #[repr(align(8))]
+    pub struct AlignedIdx = u32;
+}
+
+#[test]
+#[cfg(not(feature = "bytemuck"))]
+fn test_user_repr() {
+    assert_eq!(align_of::<AlignedIdx>(), 8);
+    assert_eq!(AlignedIdx::new(3).index(), 3);
+}